@@ -0,0 +1,620 @@
+//! Subcommand handlers: resolve the accounts each instruction needs, then sign and
+//! send (or simulate) it.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::instruction::Instruction;
+use anyhow::{anyhow, Context, Result};
+use reputation_oracle::state::{
+    AuthorDispute, AuthorDisputeLiabilityScope, AuthorDisputeRuling, AuthorDisputeVouchLink,
+    AgentProfile, SkillListing, SkillStatus, TrustTier, Vouch,
+};
+use reputation_oracle::instructions::account_migration::{
+    MigratableAccount, MigratableAccountKind,
+};
+use reputation_oracle_client::accounts::{
+    agent_profile_needs_migration, decode_account, decode_agent_profile,
+    decode_reputation_config, decode_versioned, needs_migration,
+    reputation_config_needs_migration,
+};
+use reputation_oracle_client::instructions::{self as ix, BackingVouch, SkillListingContent};
+use reputation_oracle_client::pda::{find_agent_pda, find_author_dispute_pda, find_config_pda};
+use reputation_oracle_client::PROGRAM_ID;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+
+use crate::rpc::{Filter, RpcClient, Simulation};
+use crate::{
+    AgentCommand, BondCommand, Cli, Command, ConfigCommand, DisputeCommand, ListingCommand,
+    ListingContentArgs, VouchCommand,
+};
+
+/// Byte offset of `Vouch::voucher`, after the discriminator.
+const VOUCH_VOUCHER_OFFSET: usize = 8;
+/// Byte offset of `Vouch::vouchee`, after the discriminator and `voucher`.
+const VOUCH_VOUCHEE_OFFSET: usize = 8 + 32;
+/// Byte offset of `SkillListing::author`, after the discriminator.
+const LISTING_AUTHOR_OFFSET: usize = 8;
+/// Byte offset of `AuthorDisputeVouchLink::author_dispute`, after the discriminator.
+const LINK_AUTHOR_DISPUTE_OFFSET: usize = 8;
+
+struct Session {
+    rpc: RpcClient,
+    payer: Keypair,
+    agent_id: Pubkey,
+    dry_run: bool,
+    json: bool,
+}
+
+/// What a transaction command printed: the signature once confirmed, or the
+/// simulation when `--dry-run` was set.
+#[derive(Serialize)]
+struct Report {
+    action: String,
+    signer: String,
+    dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    simulation: Option<Simulation>,
+}
+
+pub fn run(cli: Cli) -> Result<()> {
+    let payer = read_keypair_file(expand_home(&cli.keypair))
+        .map_err(|err| anyhow!("failed to read keypair {}: {err}", cli.keypair))?;
+    let session = Session {
+        rpc: RpcClient::new(cli.rpc_url),
+        agent_id: cli.agent_id.unwrap_or_else(|| payer.pubkey()),
+        payer,
+        dry_run: cli.dry_run,
+        json: cli.json,
+    };
+
+    match cli.command {
+        Command::Config(command) => config(&session, command),
+        Command::Agent(command) => agent(&session, command),
+        Command::Vouch(command) => vouch(&session, command),
+        Command::Bond(command) => bond(&session, command),
+        Command::Listing(command) => listing(&session, command),
+        Command::Purchase { skill_listing } => purchase(&session, &skill_listing),
+        Command::Claim { skill_listing } => claim(&session, &skill_listing),
+        Command::Dispute(command) => dispute(&session, command),
+        Command::Snapshot => {
+            let dump = session.rpc.program_account_dump(&PROGRAM_ID)?;
+            println!("{}", serde_json::to_string(&dump)?);
+            Ok(())
+        }
+        Command::Migrate { batch_size } => migrate(&session, batch_size),
+    }
+}
+
+/// Scans every program account and sends `migrate_accounts` for the stale ones,
+/// `batch_size` at a time.
+fn migrate(session: &Session, batch_size: usize) -> Result<()> {
+    if batch_size == 0 {
+        return Err(anyhow!("--batch-size must be at least 1"));
+    }
+    let stale: Vec<Pubkey> = session
+        .rpc
+        .program_accounts(&PROGRAM_ID, &[])?
+        .into_iter()
+        .filter(|(_, data)| match MigratableAccountKind::from_data(data) {
+            Some(MigratableAccountKind::AgentProfile) => needs_migration::<AgentProfile>(data),
+            Some(MigratableAccountKind::Vouch) => needs_migration::<Vouch>(data),
+            Some(MigratableAccountKind::SkillListing) => needs_migration::<SkillListing>(data),
+            Some(MigratableAccountKind::AuthorDispute) => needs_migration::<AuthorDispute>(data),
+            Some(MigratableAccountKind::ReputationConfig) | None => false,
+        })
+        .map(|(address, _)| address)
+        .collect();
+
+    if stale.is_empty() {
+        return print_value(session, json!({ "action": "migrate", "stale_accounts": 0 }));
+    }
+    let authority = session.payer.pubkey();
+    for batch in stale.chunks(batch_size) {
+        execute(session, "migrate", vec![ix::migrate_accounts(&authority, batch)])?;
+    }
+    Ok(())
+}
+
+fn config(session: &Session, command: ConfigCommand) -> Result<()> {
+    let authority = session.payer.pubkey();
+    let instruction = match command {
+        ConfigCommand::Show => return show_config(session),
+        ConfigCommand::Init {
+            min_stake,
+            dispute_bond,
+            min_author_bond_for_free_listing,
+            slash_percentage,
+            cooldown_period,
+            chain_context,
+        } => ix::initialize_config(
+            &authority,
+            min_stake,
+            dispute_bond,
+            min_author_bond_for_free_listing,
+            slash_percentage,
+            cooldown_period,
+            chain_context,
+        ),
+        ConfigCommand::Migrate { chain_context } => ix::migrate_config(&authority, chain_context),
+        ConfigCommand::UpdateTrustThresholds {
+            verified_min_vouchers,
+            verified_min_total_stake,
+            verified_min_author_bond,
+            verified_dispute_lookback,
+            verified_min_account_age,
+        } => ix::update_trust_thresholds(
+            &authority,
+            verified_min_vouchers,
+            verified_min_total_stake,
+            verified_min_author_bond,
+            verified_dispute_lookback,
+            verified_min_account_age,
+        ),
+        ConfigCommand::UpdateVouchWeighting {
+            min_voucher_age,
+            min_voucher_reputation,
+            full_weight_voucher_reputation,
+        } => ix::update_vouch_weighting(
+            &authority,
+            min_voucher_age,
+            min_voucher_reputation,
+            full_weight_voucher_reputation,
+        ),
+        ConfigCommand::UpdateChainContext { chain_context } => {
+            ix::update_chain_context(&authority, chain_context)
+        }
+        ConfigCommand::UpdateAgentRegistry {
+            agent_registry_program,
+            agent_registry_collection,
+        } => ix::update_agent_registry(
+            &authority,
+            agent_registry_program,
+            agent_registry_collection,
+        ),
+    };
+    execute(session, "config", vec![instruction])
+}
+
+fn agent(session: &Session, command: AgentCommand) -> Result<()> {
+    let signer = session.payer.pubkey();
+    let instruction = match command {
+        AgentCommand::Show { agent_id } => {
+            return show_agent(session, &agent_id.unwrap_or(session.agent_id))
+        }
+        AgentCommand::Register { metadata_uri } => ix::register_agent(&signer, metadata_uri),
+        AgentCommand::Migrate { metadata_uri } => ix::migrate_agent(&signer, metadata_uri),
+        AgentCommand::AdminMigrate { agent_id } => ix::admin_migrate_agent(&signer, &agent_id),
+        AgentCommand::RepairRegisteredAt {
+            agent_id,
+            registered_at,
+        } => ix::repair_agent_registered_at(&signer, &agent_id, registered_at),
+        AgentCommand::Reconcile { agent_id } => {
+            let counter_accounts = counter_accounts(session, &agent_id)?;
+            ix::reconcile_agent_profile(&signer, &agent_id, &counter_accounts)
+        }
+        AgentCommand::Sync { agent_id } => {
+            let agent_id = agent_id.unwrap_or(session.agent_id);
+            let counter_accounts = counter_accounts(session, &agent_id)?;
+            ix::sync_agent_profile(&signer, &agent_id, &counter_accounts)
+        }
+    };
+    execute(session, "agent", vec![instruction])
+}
+
+fn vouch(session: &Session, command: VouchCommand) -> Result<()> {
+    let signer = session.payer.pubkey();
+    let instruction = match command {
+        VouchCommand::Create {
+            vouchee_agent_id,
+            stake_lamports,
+        } => ix::vouch(&signer, &session.agent_id, &vouchee_agent_id, stake_lamports),
+        VouchCommand::Revoke { vouchee_agent_id } => {
+            ix::revoke_vouch(&signer, &session.agent_id, &vouchee_agent_id)
+        }
+        VouchCommand::Decrease {
+            vouchee_agent_id,
+            amount_lamports,
+        } => ix::decrease_vouch_stake(&signer, &session.agent_id, &vouchee_agent_id, amount_lamports),
+    };
+    execute(session, "vouch", vec![instruction])
+}
+
+fn bond(session: &Session, command: BondCommand) -> Result<()> {
+    let signer = session.payer.pubkey();
+    let instruction = match command {
+        BondCommand::Deposit { amount_lamports } => {
+            ix::deposit_author_bond(&signer, &session.agent_id, amount_lamports)
+        }
+        BondCommand::Withdraw { amount_lamports } => {
+            ix::withdraw_author_bond(&signer, &session.agent_id, amount_lamports)
+        }
+    };
+    execute(session, "bond", vec![instruction])
+}
+
+fn listing(session: &Session, command: ListingCommand) -> Result<()> {
+    let signer = session.payer.pubkey();
+    let instruction = match command {
+        ListingCommand::Create { skill_id, content } => {
+            ix::create_skill_listing(&signer, &session.agent_id, skill_id, content.into())
+        }
+        ListingCommand::Update { skill_id, content } => {
+            ix::update_skill_listing(&signer, &session.agent_id, skill_id, content.into())
+        }
+        ListingCommand::Remove { skill_id } => {
+            ix::remove_skill_listing(&signer, &session.agent_id, skill_id)
+        }
+        ListingCommand::Close { skill_id } => {
+            ix::close_skill_listing(&signer, &session.agent_id, skill_id)
+        }
+    };
+    execute(session, "listing", vec![instruction])
+}
+
+impl From<ListingContentArgs> for SkillListingContent {
+    fn from(args: ListingContentArgs) -> Self {
+        SkillListingContent {
+            skill_uri: args.uri,
+            name: args.name,
+            description: args.description,
+            price_lamports: args.price_lamports,
+        }
+    }
+}
+
+fn purchase(session: &Session, skill_listing: &Pubkey) -> Result<()> {
+    let listing: SkillListing = fetch(session, skill_listing)?;
+    let author = fetch_agent_profile(session, &listing.author)?;
+    let instruction = ix::purchase_skill(
+        &session.payer.pubkey(),
+        skill_listing,
+        &listing.author,
+        &author.authority,
+    );
+    execute(session, "purchase", vec![instruction])
+}
+
+fn claim(session: &Session, skill_listing: &Pubkey) -> Result<()> {
+    let listing: SkillListing = fetch(session, skill_listing)?;
+    let instruction = ix::claim_voucher_revenue(
+        &session.payer.pubkey(),
+        &session.agent_id,
+        skill_listing,
+        &listing.author,
+    );
+    execute(session, "claim", vec![instruction])
+}
+
+fn dispute(session: &Session, command: DisputeCommand) -> Result<()> {
+    let signer = session.payer.pubkey();
+    let instruction = match command {
+        DisputeCommand::Open {
+            author_agent_id,
+            dispute_id,
+            skill_listing,
+            purchase,
+            reason,
+            evidence_uri,
+        } => {
+            let backing_vouches = live_backing_vouches(session, &author_agent_id)?;
+            ix::open_author_dispute(
+                &signer,
+                &author_agent_id,
+                dispute_id,
+                &skill_listing,
+                purchase,
+                reason.into(),
+                evidence_uri,
+                &backing_vouches,
+            )
+        }
+        DisputeCommand::Resolve {
+            author_agent_id,
+            dispute_id,
+            ruling,
+        } => {
+            let ruling = AuthorDisputeRuling::from(ruling);
+            let dispute_address = find_author_dispute_pda(&author_agent_id, dispute_id).0;
+            let dispute: AuthorDispute = fetch(session, &dispute_address)?;
+            let author = fetch_agent_profile(session, &author_agent_id)?;
+            let backing = if ruling == AuthorDisputeRuling::Upheld
+                && dispute.liability_scope == AuthorDisputeLiabilityScope::AuthorBondThenVouchers
+            {
+                linked_backing_vouches(session, &dispute_address)?
+            } else {
+                Vec::new()
+            };
+            ix::resolve_author_dispute(
+                &signer,
+                &author_agent_id,
+                dispute_id,
+                &dispute.challenger,
+                ruling,
+                author.author_bond_lamports > 0,
+                &backing,
+            )
+        }
+    };
+    execute(session, "dispute", vec![instruction])
+}
+
+/// Every vouch an author-wide dispute must link: live vouches whose vouchee is the
+/// author's profile.
+fn live_backing_vouches(session: &Session, author_agent_id: &Pubkey) -> Result<Vec<Pubkey>> {
+    let author_profile = find_agent_pda(author_agent_id).0;
+    let vouches = session.rpc.program_accounts(
+        &PROGRAM_ID,
+        &[
+            Filter::Memcmp {
+                offset: 0,
+                bytes: Vouch::DISCRIMINATOR.to_vec(),
+            },
+            Filter::Memcmp {
+                offset: VOUCH_VOUCHEE_OFFSET,
+                bytes: author_profile.to_bytes().to_vec(),
+            },
+        ],
+    )?;
+    let mut backing = Vec::new();
+    for (address, data) in vouches {
+        let vouch: Vouch = decode_versioned(&data, unix_now())
+            .with_context(|| format!("failed to decode vouch {address}"))?;
+        if vouch.status.counts_toward_author_wide_backing_snapshot() {
+            backing.push(address);
+        }
+    }
+    Ok(backing)
+}
+
+/// Every account an agent's counters summarize: every vouch account it gave or received,
+/// settled or not, since sync checks them against the profile's vouch account counts,
+/// and listings it has not removed. Removed listings count for nothing, so leaving them
+/// out keeps the transaction small.
+fn counter_accounts(session: &Session, agent_id: &Pubkey) -> Result<Vec<Pubkey>> {
+    let agent_profile = find_agent_pda(agent_id).0;
+    let mut accounts = Vec::new();
+    for offset in [VOUCH_VOUCHER_OFFSET, VOUCH_VOUCHEE_OFFSET] {
+        let vouches = session.rpc.program_accounts(
+            &PROGRAM_ID,
+            &[
+                Filter::Memcmp {
+                    offset: 0,
+                    bytes: Vouch::DISCRIMINATOR.to_vec(),
+                },
+                Filter::Memcmp {
+                    offset,
+                    bytes: agent_profile.to_bytes().to_vec(),
+                },
+            ],
+        )?;
+        accounts.extend(vouches.into_iter().map(|(address, _)| address));
+    }
+    let listings = session.rpc.program_accounts(
+        &PROGRAM_ID,
+        &[
+            Filter::Memcmp {
+                offset: 0,
+                bytes: SkillListing::DISCRIMINATOR.to_vec(),
+            },
+            Filter::Memcmp {
+                offset: LISTING_AUTHOR_OFFSET,
+                bytes: agent_id.to_bytes().to_vec(),
+            },
+        ],
+    )?;
+    for (address, data) in listings {
+        let listing: SkillListing = decode_versioned(&data, unix_now())
+            .with_context(|| format!("failed to decode skill listing {address}"))?;
+        if listing.status != SkillStatus::Removed {
+            accounts.push(address);
+        }
+    }
+    Ok(accounts)
+}
+
+/// The vouches a dispute linked when it opened, with each voucher's profile.
+fn linked_backing_vouches(session: &Session, author_dispute: &Pubkey) -> Result<Vec<BackingVouch>> {
+    let links = session.rpc.program_accounts(
+        &PROGRAM_ID,
+        &[
+            Filter::Memcmp {
+                offset: 0,
+                bytes: AuthorDisputeVouchLink::DISCRIMINATOR.to_vec(),
+            },
+            Filter::Memcmp {
+                offset: LINK_AUTHOR_DISPUTE_OFFSET,
+                bytes: author_dispute.to_bytes().to_vec(),
+            },
+        ],
+    )?;
+    links
+        .into_iter()
+        .map(|(address, data)| {
+            let link: AuthorDisputeVouchLink = decode_account(&data)
+                .with_context(|| format!("failed to decode dispute link {address}"))?;
+            let vouch: Vouch = fetch(session, &link.vouch)?;
+            Ok(BackingVouch {
+                vouch: link.vouch,
+                voucher_profile: vouch.voucher,
+            })
+        })
+        .collect()
+}
+
+fn show_config(session: &Session) -> Result<()> {
+    let address = find_config_pda().0;
+    let data = fetch_data(session, &address)?;
+    let config = decode_reputation_config(&data)?;
+    print_value(
+        session,
+        json!({
+            "address": address.to_string(),
+            "needs_migration": reputation_config_needs_migration(&data),
+            "authority": config.authority.to_string(),
+            "min_stake": config.min_stake,
+            "dispute_bond": config.dispute_bond,
+            "min_author_bond_for_free_listing": config.min_author_bond_for_free_listing,
+            "slash_percentage": config.slash_percentage,
+            "cooldown_period": config.cooldown_period,
+            "stake_weight": config.stake_weight,
+            "vouch_weight": config.vouch_weight,
+            "longevity_bonus": config.longevity_bonus,
+            "transitive_trust_bps": config.transitive_trust_bps,
+            "max_trust_depth": config.max_trust_depth,
+            "verified_min_vouchers": config.verified_min_vouchers,
+            "verified_min_total_stake": config.verified_min_total_stake,
+            "verified_min_author_bond": config.verified_min_author_bond,
+            "verified_dispute_lookback": config.verified_dispute_lookback,
+            "verified_min_account_age": config.verified_min_account_age,
+            "min_voucher_age": config.min_voucher_age,
+            "min_voucher_reputation": config.min_voucher_reputation,
+            "full_weight_voucher_reputation": config.full_weight_voucher_reputation,
+            "chain_context": config.chain_context,
+            "agent_registry_program": config.agent_registry_program.to_string(),
+            "agent_registry_collection": config.agent_registry_collection.to_string(),
+        }),
+    )
+}
+
+fn show_agent(session: &Session, agent_id: &Pubkey) -> Result<()> {
+    let address = find_agent_pda(agent_id).0;
+    let data = fetch_data(session, &address)?;
+    let profile = decode_agent_profile(&data, unix_now())?;
+    let trust_tier = match profile.trust_tier {
+        TrustTier::Unverified => "unverified",
+        TrustTier::Backed => "backed",
+        TrustTier::Verified => "verified",
+    };
+    print_value(
+        session,
+        json!({
+            "address": address.to_string(),
+            "needs_migration": agent_profile_needs_migration(&data),
+            "agent_id": profile.agent_id.to_string(),
+            "authority": profile.authority.to_string(),
+            "delegate": profile.delegate.to_string(),
+            "metadata_uri": profile.metadata_uri,
+            "reputation_score": profile.reputation_score,
+            "trust_tier": trust_tier,
+            "total_vouches_received": profile.total_vouches_received,
+            "total_vouches_given": profile.total_vouches_given,
+            "total_staked_for": profile.total_staked_for,
+            "effective_vouch_weight": profile.effective_vouch_weight,
+            "claimed_foreign_staked_for": profile.claimed_foreign_staked_for,
+            "author_bond_lamports": profile.author_bond_lamports,
+            "active_skill_listings": profile.active_skill_listings,
+            "active_free_skill_listings": profile.active_free_skill_listings,
+            "counters_verified": profile.counters_verified,
+            "open_author_disputes": profile.open_author_disputes,
+            "registered_at": profile.registered_at,
+        }),
+    )
+}
+
+fn fetch_data(session: &Session, address: &Pubkey) -> Result<Vec<u8>> {
+    session.rpc
+        .account_data(address)?
+        .ok_or_else(|| anyhow!("account {address} does not exist"))
+}
+
+fn fetch<T: MigratableAccount>(session: &Session, address: &Pubkey) -> Result<T> {
+    let data = fetch_data(session, address)?;
+    decode_versioned(&data, unix_now())
+        .with_context(|| format!("failed to decode account {address}"))
+}
+
+fn fetch_agent_profile(
+    session: &Session,
+    agent_id: &Pubkey,
+) -> Result<AgentProfile> {
+    let data = fetch_data(session, &find_agent_pda(agent_id).0)?;
+    Ok(decode_agent_profile(&data, unix_now())?)
+}
+
+/// Signs with the payer, then simulates under `--dry-run` or sends and waits for
+/// confirmation. A failed simulation is reported, then returned as an error so
+/// scripts see a non-zero exit.
+fn execute(session: &Session, action: &str, instructions: Vec<Instruction>) -> Result<()> {
+    let payer = session.payer.pubkey();
+    let blockhash = session.rpc.latest_blockhash()?;
+    let transaction =
+        Transaction::new_signed_with_payer(&instructions, Some(&payer), &[&session.payer], blockhash);
+
+    let mut report = Report {
+        action: action.to_string(),
+        signer: payer.to_string(),
+        dry_run: session.dry_run,
+        signature: None,
+        simulation: None,
+    };
+    if session.dry_run {
+        report.simulation = Some(session.rpc.simulate(&transaction)?);
+    } else {
+        report.signature = Some(session.rpc.send_and_confirm(&transaction)?);
+    }
+
+    let failed = report
+        .simulation
+        .as_ref()
+        .and_then(|simulation| simulation.err.clone());
+    print_report(session, &report)?;
+    match failed {
+        Some(err) => Err(anyhow!("simulation failed: {err}")),
+        None => Ok(()),
+    }
+}
+
+fn print_report(session: &Session, report: &Report) -> Result<()> {
+    if session.json {
+        println!("{}", serde_json::to_string(report)?);
+        return Ok(());
+    }
+    println!("{} signed by {}", report.action, report.signer);
+    if let Some(signature) = &report.signature {
+        println!("signature: {signature}");
+    }
+    if let Some(simulation) = &report.simulation {
+        match &simulation.err {
+            Some(err) => println!("simulation: failed ({err})"),
+            None => println!("simulation: ok"),
+        }
+        if let Some(units) = simulation.units_consumed {
+            println!("compute units: {units}");
+        }
+        for log in &simulation.logs {
+            println!("  {log}");
+        }
+    }
+    Ok(())
+}
+
+fn print_value(session: &Session, value: Value) -> Result<()> {
+    if session.json {
+        println!("{}", serde_json::to_string(&value)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(&value)?);
+    }
+    Ok(())
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{home}/{rest}"),
+        _ => path.to_string(),
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,307 @@
+//! Operator and agent CLI for the reputation-oracle program.
+//!
+//! Every transaction command signs with `--keypair`, and `--dry-run` simulates instead
+//! of sending. `--json` prints one JSON object per command for scripting.
+
+mod commands;
+mod rpc;
+
+use anyhow::Result;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use anchor_lang::prelude::Pubkey;
+use reputation_oracle::state::{AuthorDisputeReason, AuthorDisputeRuling};
+
+#[derive(Parser)]
+#[command(name = "reputation-oracle-cli", version, about)]
+pub struct Cli {
+    /// Solana JSON-RPC endpoint.
+    #[arg(long, global = true, env = "SOLANA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub rpc_url: String,
+
+    /// Keypair file that signs and pays for transactions.
+    #[arg(long, global = true, env = "SOLANA_KEYPAIR", default_value = "~/.config/solana/id.json")]
+    pub keypair: String,
+
+    /// Stable agent ID of the signer's profile. Defaults to the signer pubkey; set it
+    /// after an authority rotation or when signing as a delegate.
+    #[arg(long, global = true)]
+    pub agent_id: Option<Pubkey>,
+
+    /// Simulate the transaction and print its logs instead of sending it.
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
+    /// Print machine-readable JSON.
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Program config administration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Agent profile registration and maintenance.
+    #[command(subcommand)]
+    Agent(AgentCommand),
+    /// Vouches from the signer's agent.
+    #[command(subcommand)]
+    Vouch(VouchCommand),
+    /// The signer's author bond.
+    #[command(subcommand)]
+    Bond(BondCommand),
+    /// The signer's skill listings.
+    #[command(subcommand)]
+    Listing(ListingCommand),
+    /// Buy a skill listing.
+    Purchase { skill_listing: Pubkey },
+    /// Claim voucher revenue from a skill listing the signer's agent backs.
+    Claim { skill_listing: Pubkey },
+    /// Author disputes.
+    #[command(subcommand)]
+    Dispute(DisputeCommand),
+    /// Print every program account as a JSON snapshot for the reconciler.
+    Snapshot,
+    /// Config authority: rewrite every agent profile, vouch, listing, and dispute still
+    /// in an older layout with `migrate_accounts`. The config itself uses `config migrate`.
+    Migrate {
+        /// Accounts per transaction.
+        #[arg(long, default_value_t = 8)]
+        batch_size: usize,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the decoded config, including legacy layouts.
+    Show,
+    Init {
+        #[arg(long)]
+        min_stake: u64,
+        #[arg(long)]
+        dispute_bond: u64,
+        #[arg(long)]
+        min_author_bond_for_free_listing: u64,
+        #[arg(long)]
+        slash_percentage: u8,
+        #[arg(long)]
+        cooldown_period: i64,
+        /// CAIP-2 chain ID of this deployment, e.g. `solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1`.
+        #[arg(long)]
+        chain_context: String,
+    },
+    Migrate {
+        /// Required when the stored config predates the chain context.
+        #[arg(long)]
+        chain_context: Option<String>,
+    },
+    UpdateTrustThresholds {
+        #[arg(long)]
+        verified_min_vouchers: u32,
+        #[arg(long)]
+        verified_min_total_stake: u64,
+        #[arg(long)]
+        verified_min_author_bond: u64,
+        #[arg(long)]
+        verified_dispute_lookback: i64,
+        #[arg(long)]
+        verified_min_account_age: i64,
+    },
+    UpdateVouchWeighting {
+        #[arg(long)]
+        min_voucher_age: i64,
+        #[arg(long)]
+        min_voucher_reputation: u64,
+        #[arg(long)]
+        full_weight_voucher_reputation: u64,
+    },
+    UpdateChainContext { chain_context: String },
+    UpdateAgentRegistry {
+        #[arg(long)]
+        agent_registry_program: Pubkey,
+        #[arg(long)]
+        agent_registry_collection: Pubkey,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AgentCommand {
+    /// Print a decoded agent profile, including legacy layouts.
+    Show {
+        /// Agent ID to inspect; defaults to the signer's.
+        agent_id: Option<Pubkey>,
+    },
+    Register {
+        #[arg(long, default_value = "")]
+        metadata_uri: String,
+    },
+    /// Rewrite the signer's own profile in the current layout.
+    Migrate {
+        #[arg(long, default_value = "")]
+        metadata_uri: String,
+    },
+    /// Config authority: rewrite another agent's profile in the current layout.
+    AdminMigrate { agent_id: Pubkey },
+    /// Config authority: correct an agent's registration timestamp.
+    RepairRegisteredAt { agent_id: Pubkey, registered_at: i64 },
+    /// Config authority: recompute an agent's counters from its live vouches and listings.
+    Reconcile { agent_id: Pubkey },
+    /// Recompute an agent's stake, weight, and bond totals and score from its vouch
+    /// accounts and listings. Anyone may sync; defaults to the signer's own profile.
+    Sync { agent_id: Option<Pubkey> },
+}
+
+#[derive(Subcommand)]
+pub enum VouchCommand {
+    /// Vouch for an agent, or top up an existing vouch.
+    Create {
+        vouchee_agent_id: Pubkey,
+        #[arg(long)]
+        stake_lamports: u64,
+    },
+    Revoke { vouchee_agent_id: Pubkey },
+    Decrease {
+        vouchee_agent_id: Pubkey,
+        #[arg(long)]
+        amount_lamports: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BondCommand {
+    Deposit {
+        #[arg(long)]
+        amount_lamports: u64,
+    },
+    Withdraw {
+        #[arg(long)]
+        amount_lamports: u64,
+    },
+}
+
+#[derive(Args)]
+pub struct ListingContentArgs {
+    #[arg(long)]
+    pub uri: String,
+    #[arg(long)]
+    pub name: String,
+    #[arg(long, default_value = "")]
+    pub description: String,
+    #[arg(long)]
+    pub price_lamports: u64,
+}
+
+#[derive(Subcommand)]
+pub enum ListingCommand {
+    Create {
+        skill_id: String,
+        #[command(flatten)]
+        content: ListingContentArgs,
+    },
+    Update {
+        skill_id: String,
+        #[command(flatten)]
+        content: ListingContentArgs,
+    },
+    Remove { skill_id: String },
+    /// Close a removed listing and reclaim its rent.
+    Close { skill_id: String },
+}
+
+#[derive(Subcommand)]
+pub enum DisputeCommand {
+    /// Open a dispute against an author, linking every live vouch backing them.
+    Open {
+        author_agent_id: Pubkey,
+        dispute_id: u64,
+        #[arg(long)]
+        skill_listing: Pubkey,
+        #[arg(long)]
+        purchase: Option<Pubkey>,
+        #[arg(long, value_enum)]
+        reason: ReasonArg,
+        #[arg(long)]
+        evidence_uri: String,
+    },
+    /// Config authority: resolve a dispute, settling linked vouches when upheld.
+    Resolve {
+        author_agent_id: Pubkey,
+        dispute_id: u64,
+        #[arg(long, value_enum)]
+        ruling: RulingArg,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReasonArg {
+    MaliciousSkill,
+    FraudulentClaims,
+    FailedDelivery,
+    Other,
+}
+
+impl From<ReasonArg> for AuthorDisputeReason {
+    fn from(reason: ReasonArg) -> Self {
+        match reason {
+            ReasonArg::MaliciousSkill => AuthorDisputeReason::MaliciousSkill,
+            ReasonArg::FraudulentClaims => AuthorDisputeReason::FraudulentClaims,
+            ReasonArg::FailedDelivery => AuthorDisputeReason::FailedDelivery,
+            ReasonArg::Other => AuthorDisputeReason::Other,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum RulingArg {
+    Upheld,
+    Dismissed,
+}
+
+impl From<RulingArg> for AuthorDisputeRuling {
+    fn from(ruling: RulingArg) -> Self {
+        match ruling {
+            RulingArg::Upheld => AuthorDisputeRuling::Upheld,
+            RulingArg::Dismissed => AuthorDisputeRuling::Dismissed,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    commands::run(Cli::parse())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_dispute_resolution_with_global_flags() {
+        let author = Pubkey::new_unique();
+        let cli = Cli::try_parse_from([
+            "reputation-oracle-cli",
+            "dispute",
+            "resolve",
+            &author.to_string(),
+            "4",
+            "--ruling",
+            "upheld",
+            "--dry-run",
+            "--json",
+        ])
+        .unwrap();
+        assert!(cli.dry_run && cli.json);
+        assert!(matches!(
+            cli.command,
+            Command::Dispute(DisputeCommand::Resolve { dispute_id: 4, ruling: RulingArg::Upheld, .. })
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+//! Just enough Solana JSON-RPC for the CLI: blockhashes, account reads, and
+//! simulating, sending, and confirming transactions.
+
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_transaction::Transaction;
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct RpcClient {
+    url: String,
+}
+
+/// Result of `simulateTransaction`, trimmed to what operators act on.
+#[derive(Debug, Serialize)]
+pub struct Simulation {
+    pub err: Option<Value>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+}
+
+/// A `getProgramAccounts` filter.
+pub enum Filter {
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl Filter {
+    fn to_json(&self) -> Value {
+        match self {
+            Filter::Memcmp { offset, bytes } => json!({
+                "memcmp": { "offset": offset, "bytes": BASE64.encode(bytes), "encoding": "base64" }
+            }),
+        }
+    }
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = ureq::post(&self.url)
+            .send_json(json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+            .with_context(|| format!("{method} request to {} failed", self.url))?
+            .into_json()
+            .with_context(|| format!("{method} returned invalid JSON"))?;
+        if let Some(error) = response.get("error") {
+            bail!("{method} failed: {error}");
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("{method} response has no result"))
+    }
+
+    pub fn latest_blockhash(&self) -> Result<Hash> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| anyhow!("getLatestBlockhash response has no blockhash"))?;
+        Hash::from_str(blockhash).map_err(|err| anyhow!("invalid blockhash {blockhash}: {err}"))
+    }
+
+    /// Raw account data, or `None` when the account does not exist.
+    pub fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>> {
+        let result = self.call(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+        match &result["value"] {
+            Value::Null => Ok(None),
+            account => decode_account_data(account).map(Some),
+        }
+    }
+
+    pub fn program_accounts(
+        &self,
+        program_id: &Pubkey,
+        filters: &[Filter],
+    ) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+        let filters: Vec<Value> = filters.iter().map(Filter::to_json).collect();
+        let result = self.call(
+            "getProgramAccounts",
+            json!([
+                program_id.to_string(),
+                { "encoding": "base64", "commitment": "confirmed", "filters": filters }
+            ]),
+        )?;
+        result
+            .as_array()
+            .ok_or_else(|| anyhow!("getProgramAccounts response is not a list"))?
+            .iter()
+            .map(|entry| {
+                let pubkey = entry["pubkey"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("getProgramAccounts entry has no pubkey"))?;
+                let pubkey = Pubkey::from_str(pubkey)
+                    .map_err(|err| anyhow!("invalid pubkey {pubkey}: {err}"))?;
+                Ok((pubkey, decode_account_data(&entry["account"])?))
+            })
+            .collect()
+    }
+
+    /// Every account the program owns, as the raw `getProgramAccounts` list with base64
+    /// data. This is the snapshot format the reconciler reads.
+    pub fn program_account_dump(&self, program_id: &Pubkey) -> Result<Value> {
+        self.call(
+            "getProgramAccounts",
+            json!([program_id.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )
+    }
+
+    pub fn simulate(&self, transaction: &Transaction) -> Result<Simulation> {
+        let result = self.call(
+            "simulateTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                {
+                    "encoding": "base64",
+                    "commitment": "confirmed",
+                    "sigVerify": false,
+                    "replaceRecentBlockhash": true
+                }
+            ]),
+        )?;
+        let value = &result["value"];
+        Ok(Simulation {
+            err: Some(value["err"].clone()).filter(|err| !err.is_null()),
+            logs: value["logs"]
+                .as_array()
+                .map(|logs| logs.iter().filter_map(|log| log.as_str().map(String::from)).collect())
+                .unwrap_or_default(),
+            units_consumed: value["unitsConsumed"].as_u64(),
+        })
+    }
+
+    /// Sends with preflight and waits until the cluster reports the signature confirmed.
+    pub fn send_and_confirm(&self, transaction: &Transaction) -> Result<String> {
+        let result = self.call(
+            "sendTransaction",
+            json!([
+                encode_transaction(transaction)?,
+                { "encoding": "base64", "preflightCommitment": "confirmed" }
+            ]),
+        )?;
+        let signature = result
+            .as_str()
+            .ok_or_else(|| anyhow!("sendTransaction returned no signature"))?
+            .to_string();
+
+        let started = Instant::now();
+        loop {
+            let statuses = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &statuses["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    bail!("transaction {signature} failed: {}", status["err"]);
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+            if started.elapsed() > CONFIRM_TIMEOUT {
+                bail!("timed out waiting for {signature} to confirm");
+            }
+            sleep(CONFIRM_POLL_INTERVAL);
+        }
+    }
+}
+
+fn encode_transaction(transaction: &Transaction) -> Result<String> {
+    Ok(BASE64.encode(bincode::serialize(transaction)?))
+}
+
+fn decode_account_data(account: &Value) -> Result<Vec<u8>> {
+    let data = account["data"][0]
+        .as_str()
+        .ok_or_else(|| anyhow!("account data is not base64 encoded"))?;
+    BASE64.decode(data).context("account data is not valid base64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memcmp_filters_use_base64_bytes() {
+        let filter = Filter::Memcmp {
+            offset: 40,
+            bytes: vec![1, 2, 3],
+        };
+        assert_eq!(
+            filter.to_json(),
+            json!({ "memcmp": { "offset": 40, "bytes": "AQID", "encoding": "base64" } })
+        );
+    }
+
+    #[test]
+    fn decodes_base64_account_data() {
+        let account = json!({ "data": ["AQID", "base64"], "lamports": 1 });
+        assert_eq!(decode_account_data(&account).unwrap(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Account decoders.
+//!
+//! Versioned accounts (`AgentProfile`, `ReputationConfig`, `Vouch`, `SkillListing`,
+//! `AuthorDispute`) may still be in a layout from before their last migration. Their
+//! decoders reuse the program's migration parsers, so a client sees exactly the values
+//! a migration would write, including the canonical bump.
+
+use anchor_lang::prelude::*;
+use reputation_oracle::instructions::account_migration::{is_current_layout, MigratableAccount};
+use reputation_oracle::instructions::agent_profile_migration;
+use reputation_oracle::instructions::decode_reputation_config as decode_config_layout;
+use reputation_oracle::state::{AgentProfile, ReputationConfig};
+
+use crate::pda::{find_agent_pda, find_config_pda};
+
+/// Decodes any account whose on-chain layout is current.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut slice = data;
+    T::try_deserialize(&mut slice)
+}
+
+/// Decodes current, bonded, and legacy agent profiles. `now` (unix seconds) replaces an
+/// implausible stored `registered_at`, as the migration would.
+pub fn decode_agent_profile(data: &[u8], now: i64) -> Result<AgentProfile> {
+    let parsed = agent_profile_migration::decode_agent_profile(data, now)?;
+    let (_, bump) = find_agent_pda(&parsed.agent_id);
+    Ok(parsed.into_agent_profile(bump))
+}
+
+/// Decodes current, bonded, and legacy config layouts, filling fields older layouts
+/// lack with the program defaults. Layouts that predate the chain context decode with
+/// an empty one.
+pub fn decode_reputation_config(data: &[u8]) -> Result<ReputationConfig> {
+    let (_, bump) = find_config_pda();
+    Ok(decode_config_layout(data, bump)?.into_reputation_config())
+}
+
+/// Decodes any shipped layout of a versioned account. `now` (unix seconds) only matters
+/// for agent profiles, as in [`decode_agent_profile`].
+pub fn decode_versioned<T: MigratableAccount>(data: &[u8], now: i64) -> Result<T> {
+    Ok(T::parse_layout(data, now)?.0)
+}
+
+/// True when the account is not yet at its current length and version, so
+/// `migrate_accounts` would rewrite it.
+pub fn needs_migration<T: MigratableAccount>(data: &[u8]) -> bool {
+    !is_current_layout::<T>(data)
+}
+
+/// True when the account still needs `migrate_agent`, `admin_migrate_agent`, or
+/// `migrate_accounts`.
+pub fn agent_profile_needs_migration(data: &[u8]) -> bool {
+    needs_migration::<AgentProfile>(data)
+}
+
+/// True when the config account still needs `migrate_config`.
+pub fn reputation_config_needs_migration(data: &[u8]) -> bool {
+    needs_migration::<ReputationConfig>(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reputation_oracle::state::{TrustTier, Vouch, VouchStatus};
+
+    const NOW: i64 = 1_750_000_000;
+
+    fn legacy_config(authority: Pubkey) -> Vec<u8> {
+        let mut data = ReputationConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes()); // min_stake
+        data.extend_from_slice(&5_000u64.to_le_bytes()); // dispute_bond
+        data.push(50); // slash_percentage
+        data.extend_from_slice(&86_400i64.to_le_bytes()); // cooldown_period
+        data.extend_from_slice(&1u32.to_le_bytes()); // stake_weight
+        data.extend_from_slice(&100u32.to_le_bytes()); // vouch_weight
+        data.extend_from_slice(&25u32.to_le_bytes()); // dispute_penalty
+        data.extend_from_slice(&10u32.to_le_bytes()); // longevity_bonus
+        data.push(0); // stale bump
+        data
+    }
+
+    fn legacy_agent_profile(authority: Pubkey, registered_at: i64) -> Vec<u8> {
+        let uri = "https://legacy.agent";
+        let mut data = AgentProfile::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&(uri.len() as u32).to_le_bytes());
+        data.extend_from_slice(uri.as_bytes());
+        data.extend_from_slice(&42u64.to_le_bytes()); // reputation_score
+        data.extend_from_slice(&2u32.to_le_bytes()); // total_vouches_received
+        data.extend_from_slice(&1u32.to_le_bytes()); // total_vouches_given
+        data.extend_from_slice(&700u64.to_le_bytes()); // total_staked_for
+        data.extend_from_slice(&registered_at.to_le_bytes());
+        data.extend_from_slice(&0i64.to_le_bytes()); // legacy registered_at slot
+        data.push(0); // stale bump
+        data
+    }
+
+    #[test]
+    fn decodes_legacy_config_with_defaults_and_canonical_bump() {
+        let authority = Pubkey::new_unique();
+        let data = legacy_config(authority);
+        assert!(reputation_config_needs_migration(&data));
+
+        let config = decode_reputation_config(&data).unwrap();
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.min_stake, 1_000);
+        assert_eq!(config.min_author_bond_for_free_listing, 5_000);
+        assert_eq!(config.slash_percentage, 50);
+        assert_eq!(config.longevity_bonus, 10);
+        assert_eq!(config.max_trust_depth, ReputationConfig::DEFAULT_MAX_TRUST_DEPTH);
+        // Legacy configs have no chain context until `migrate_config` supplies one.
+        assert!(config.chain_context.is_empty());
+        assert_eq!(config.bump, find_config_pda().1);
+    }
+
+    #[test]
+    fn decodes_legacy_agent_profile_like_a_migration() {
+        let authority = Pubkey::new_unique();
+        let profile = decode_agent_profile(&legacy_agent_profile(authority, 1_700_000_000), NOW).unwrap();
+        assert_eq!(profile.authority, authority);
+        assert_eq!(profile.agent_id, authority);
+        assert_eq!(profile.metadata_uri, "https://legacy.agent");
+        assert_eq!(profile.reputation_score, 42);
+        assert_eq!(profile.total_staked_for, 700);
+        assert_eq!(profile.registered_at, 1_700_000_000);
+        assert_eq!(profile.effective_vouch_weight, 20_000);
+        assert!(profile.trust_tier == TrustTier::Unverified);
+        assert_eq!(profile.bump, find_agent_pda(&authority).1);
+
+        let implausible = decode_agent_profile(&legacy_agent_profile(authority, 7), NOW).unwrap();
+        assert_eq!(implausible.registered_at, NOW);
+    }
+
+    #[test]
+    fn decodes_baseline_vouches_as_current() {
+        let (voucher, vouchee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        // The baseline layout: the fields through `bump`, 106 bytes.
+        let mut data = Vouch::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(voucher.as_ref());
+        data.extend_from_slice(vouchee.as_ref());
+        data.extend_from_slice(&5_000u64.to_le_bytes()); // stake_amount
+        data.extend_from_slice(&NOW.to_le_bytes()); // created_at
+        data.push(0); // status: Active
+        data.extend_from_slice(&0u64.to_le_bytes()); // cumulative_revenue
+        data.extend_from_slice(&NOW.to_le_bytes()); // last_payout_at
+        data.push(253); // bump
+        assert_eq!(data.len(), 106);
+        assert!(needs_migration::<Vouch>(&data));
+        assert!(decode_account::<Vouch>(&data).is_err());
+
+        let decoded: Vouch = decode_versioned(&data, NOW).unwrap();
+        assert_eq!(decoded.version, Vouch::CURRENT_VERSION);
+        assert_eq!(decoded.voucher, voucher);
+        assert_eq!(decoded.vouchee, vouchee);
+        assert_eq!(decoded.stake_amount, 5_000);
+        assert!(decoded.status == VouchStatus::Active);
+        assert_eq!(decoded.bump, 253);
+        assert_eq!(decoded.effective_weight_bps, 10_000);
+        assert_eq!(decoded.voucher_reputation_snapshot, 0);
+        assert!(!decoded.low_reputation_voucher);
+        assert_eq!(decoded.pending_dispute_links, 0);
+        assert_eq!(decoded.last_stake_increase_at, NOW);
+    }
+
+    #[test]
+    fn rejects_foreign_discriminators() {
+        let mut data = legacy_config(Pubkey::new_unique());
+        data[0] ^= 0xff;
+        assert!(decode_reputation_config(&data).is_err());
+        assert!(decode_agent_profile(&data, NOW).is_err());
+    }
+}
@@ -0,0 +1,738 @@
+//! Instruction builders for the operator and agent flows.
+//!
+//! Each builder derives every PDA itself and takes the signer wallets plus the
+//! stable `agent_id`s involved. Instructions without a builder here can still be
+//! assembled from `reputation_oracle::accounts` and `reputation_oracle::instruction`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use reputation_oracle::state::{AuthorDisputeReason, AuthorDisputeRuling, SkillListing};
+use reputation_oracle::{accounts, instruction};
+
+use crate::pda::{
+    find_agent_pda, find_author_bond_pda, find_author_dispute_pda,
+    find_author_dispute_vouch_link_pda, find_config_pda, find_purchase_pda,
+    find_session_key_pda, find_skill_listing_pda, find_vouch_pda,
+};
+use crate::PROGRAM_ID;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn config() -> Pubkey {
+    find_config_pda().0
+}
+
+fn agent(agent_id: &Pubkey) -> Pubkey {
+    find_agent_pda(agent_id).0
+}
+
+/// Content fields shared by `create_skill_listing` and `update_skill_listing`.
+#[derive(Clone, Debug)]
+pub struct SkillListingContent {
+    pub skill_uri: String,
+    pub name: String,
+    pub description: String,
+    pub price_lamports: u64,
+}
+
+/// A live vouch backing the disputed author, with the voucher's profile PDA.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BackingVouch {
+    pub vouch: Pubkey,
+    pub voucher_profile: Pubkey,
+}
+
+pub fn initialize_config(
+    authority: &Pubkey,
+    min_stake: u64,
+    dispute_bond: u64,
+    min_author_bond_for_free_listing: u64,
+    slash_percentage: u8,
+    cooldown_period: i64,
+    chain_context: String,
+) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: config(),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig {
+            min_stake,
+            dispute_bond,
+            min_author_bond_for_free_listing,
+            slash_percentage,
+            cooldown_period,
+            chain_context,
+        },
+    )
+}
+
+/// `chain_context` is required when the stored config predates it.
+pub fn migrate_config(authority: &Pubkey, chain_context: Option<String>) -> Instruction {
+    build(
+        accounts::MigrateConfig {
+            config: config(),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateConfig { chain_context },
+    )
+}
+
+/// Config-authority batch upgrade of versioned accounts. Accounts already in their
+/// current layout are skipped on chain.
+pub fn migrate_accounts(config_authority: &Pubkey, stale_accounts: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::MigrateAccounts {
+            config: config(),
+            authority: *config_authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAccounts {},
+    );
+    ix.accounts.extend(
+        stale_accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+    ix
+}
+
+pub fn update_trust_thresholds(
+    authority: &Pubkey,
+    verified_min_vouchers: u32,
+    verified_min_total_stake: u64,
+    verified_min_author_bond: u64,
+    verified_dispute_lookback: i64,
+    verified_min_account_age: i64,
+) -> Instruction {
+    build(
+        accounts::UpdateTrustThresholds {
+            config: config(),
+            authority: *authority,
+        },
+        instruction::UpdateTrustThresholds {
+            verified_min_vouchers,
+            verified_min_total_stake,
+            verified_min_author_bond,
+            verified_dispute_lookback,
+            verified_min_account_age,
+        },
+    )
+}
+
+pub fn update_vouch_weighting(
+    authority: &Pubkey,
+    min_voucher_age: i64,
+    min_voucher_reputation: u64,
+    full_weight_voucher_reputation: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateVouchWeighting {
+            config: config(),
+            authority: *authority,
+        },
+        instruction::UpdateVouchWeighting {
+            min_voucher_age,
+            min_voucher_reputation,
+            full_weight_voucher_reputation,
+        },
+    )
+}
+
+pub fn update_chain_context(authority: &Pubkey, chain_context: String) -> Instruction {
+    build(
+        accounts::UpdateChainContext {
+            config: config(),
+            authority: *authority,
+        },
+        instruction::UpdateChainContext { chain_context },
+    )
+}
+
+pub fn update_agent_registry(
+    authority: &Pubkey,
+    agent_registry_program: Pubkey,
+    agent_registry_collection: Pubkey,
+) -> Instruction {
+    build(
+        accounts::UpdateAgentRegistry {
+            config: config(),
+            authority: *authority,
+        },
+        instruction::UpdateAgentRegistry {
+            agent_registry_program,
+            agent_registry_collection,
+        },
+    )
+}
+
+pub fn register_agent(authority: &Pubkey, metadata_uri: String) -> Instruction {
+    build(
+        accounts::RegisterAgent {
+            agent_profile: agent(authority),
+            config: config(),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterAgent { metadata_uri },
+    )
+}
+
+/// Self-service migration; only valid while `authority` is still the registering wallet.
+pub fn migrate_agent(authority: &Pubkey, metadata_uri: String) -> Instruction {
+    build(
+        accounts::MigrateAgent {
+            agent_profile: agent(authority),
+            config: config(),
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAgent { metadata_uri },
+    )
+}
+
+pub fn admin_migrate_agent(config_authority: &Pubkey, agent_id: &Pubkey) -> Instruction {
+    build(
+        accounts::AdminMigrateAgent {
+            agent_profile: agent(agent_id),
+            config: config(),
+            authority: *config_authority,
+            system_program: system_program::ID,
+        },
+        instruction::AdminMigrateAgent {},
+    )
+}
+
+pub fn repair_agent_registered_at(
+    config_authority: &Pubkey,
+    agent_id: &Pubkey,
+    registered_at: i64,
+) -> Instruction {
+    build(
+        accounts::RepairAgentRegisteredAt {
+            agent_profile: agent(agent_id),
+            config: config(),
+            authority: *config_authority,
+        },
+        instruction::RepairAgentRegisteredAt { registered_at },
+    )
+}
+
+/// Config-authority counter repair. `counter_accounts` must hold every vouch the agent
+/// gave or received and every listing it authored; anything omitted drops out of the
+/// recomputed counters.
+pub fn reconcile_agent_profile(
+    config_authority: &Pubkey,
+    agent_id: &Pubkey,
+    counter_accounts: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::ReconcileAgentProfile {
+            agent_profile: agent(agent_id),
+            author_bond: find_author_bond_pda(agent_id).0,
+            config: config(),
+            authority: *config_authority,
+        },
+        instruction::ReconcileAgentProfile {},
+    );
+    ix.accounts.extend(
+        counter_accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(*account, false)),
+    );
+    ix
+}
+
+/// Permissionless counter and score recompute. `counter_accounts` must cover every live
+/// vouch the agent gave or received and every listing it has not removed, or the
+/// program rejects the set as incomplete.
+pub fn sync_agent_profile(caller: &Pubkey, agent_id: &Pubkey, counter_accounts: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::SyncAgentProfile {
+            agent_profile: agent(agent_id),
+            author_bond: find_author_bond_pda(agent_id).0,
+            config: config(),
+            caller: *caller,
+        },
+        instruction::SyncAgentProfile {},
+    );
+    ix.accounts.extend(
+        counter_accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(*account, false)),
+    );
+    ix
+}
+
+pub fn deposit_author_bond(author: &Pubkey, author_agent_id: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::DepositAuthorBond {
+            author_bond: find_author_bond_pda(author_agent_id).0,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            author: *author,
+            system_program: system_program::ID,
+        },
+        instruction::DepositAuthorBond { amount },
+    )
+}
+
+pub fn withdraw_author_bond(author: &Pubkey, author_agent_id: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawAuthorBond {
+            author_bond: find_author_bond_pda(author_agent_id).0,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            author: *author,
+        },
+        instruction::WithdrawAuthorBond { amount },
+    )
+}
+
+pub fn vouch(
+    voucher: &Pubkey,
+    voucher_agent_id: &Pubkey,
+    vouchee_agent_id: &Pubkey,
+    stake_amount: u64,
+) -> Instruction {
+    let voucher_profile = agent(voucher_agent_id);
+    let vouchee_profile = agent(vouchee_agent_id);
+    build(
+        accounts::CreateVouch {
+            vouch: find_vouch_pda(&voucher_profile, &vouchee_profile).0,
+            voucher_profile,
+            vouchee_profile,
+            config: config(),
+            voucher: *voucher,
+            system_program: system_program::ID,
+        },
+        instruction::Vouch { stake_amount },
+    )
+}
+
+pub fn revoke_vouch(
+    voucher: &Pubkey,
+    voucher_agent_id: &Pubkey,
+    vouchee_agent_id: &Pubkey,
+) -> Instruction {
+    let voucher_profile = agent(voucher_agent_id);
+    let vouchee_profile = agent(vouchee_agent_id);
+    build(
+        accounts::RevokeVouch {
+            vouch: find_vouch_pda(&voucher_profile, &vouchee_profile).0,
+            voucher_profile,
+            vouchee_profile,
+            config: config(),
+            voucher: *voucher,
+            system_program: system_program::ID,
+        },
+        instruction::RevokeVouch {},
+    )
+}
+
+pub fn decrease_vouch_stake(
+    voucher: &Pubkey,
+    voucher_agent_id: &Pubkey,
+    vouchee_agent_id: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let voucher_profile = agent(voucher_agent_id);
+    let vouchee_profile = agent(vouchee_agent_id);
+    build(
+        accounts::DecreaseVouchStake {
+            vouch: find_vouch_pda(&voucher_profile, &vouchee_profile).0,
+            voucher_profile,
+            vouchee_profile,
+            config: config(),
+            voucher: *voucher,
+        },
+        instruction::DecreaseVouchStake { amount },
+    )
+}
+
+/// Free listings must carry the author bond account; paid ones omit it.
+fn listing_author_bond(author_agent_id: &Pubkey, price_lamports: u64) -> Option<Pubkey> {
+    SkillListing::is_free_price(price_lamports).then(|| find_author_bond_pda(author_agent_id).0)
+}
+
+pub fn create_skill_listing(
+    author: &Pubkey,
+    author_agent_id: &Pubkey,
+    skill_id: String,
+    content: SkillListingContent,
+) -> Instruction {
+    build(
+        accounts::CreateSkillListing {
+            skill_listing: find_skill_listing_pda(author_agent_id, &skill_id).0,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            author_bond: listing_author_bond(author_agent_id, content.price_lamports),
+            author: *author,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSkillListing {
+            skill_id,
+            skill_uri: content.skill_uri,
+            name: content.name,
+            description: content.description,
+            price_lamports: content.price_lamports,
+        },
+    )
+}
+
+pub fn update_skill_listing(
+    author: &Pubkey,
+    author_agent_id: &Pubkey,
+    skill_id: String,
+    content: SkillListingContent,
+) -> Instruction {
+    build(
+        accounts::UpdateSkillListing {
+            skill_listing: find_skill_listing_pda(author_agent_id, &skill_id).0,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            author_bond: listing_author_bond(author_agent_id, content.price_lamports),
+            author: *author,
+        },
+        instruction::UpdateSkillListing {
+            skill_id,
+            skill_uri: content.skill_uri,
+            name: content.name,
+            description: content.description,
+            price_lamports: content.price_lamports,
+        },
+    )
+}
+
+/// `session_signer` holds a session key with the list scope and pays the listing rent.
+pub fn create_skill_listing_with_session(
+    session_signer: &Pubkey,
+    author_agent_id: &Pubkey,
+    skill_id: String,
+    content: SkillListingContent,
+) -> Instruction {
+    let author_profile = agent(author_agent_id);
+    build(
+        accounts::CreateSkillListingWithSession {
+            skill_listing: find_skill_listing_pda(author_agent_id, &skill_id).0,
+            author_profile,
+            agent_session_key: find_session_key_pda(&author_profile, session_signer).0,
+            config: config(),
+            author_bond: listing_author_bond(author_agent_id, content.price_lamports),
+            session_signer: *session_signer,
+            system_program: system_program::ID,
+        },
+        instruction::CreateSkillListingWithSession {
+            skill_id,
+            skill_uri: content.skill_uri,
+            name: content.name,
+            description: content.description,
+            price_lamports: content.price_lamports,
+        },
+    )
+}
+
+/// `session_signer` holds a session key with the update scope.
+pub fn update_skill_listing_with_session(
+    session_signer: &Pubkey,
+    author_agent_id: &Pubkey,
+    skill_id: String,
+    content: SkillListingContent,
+) -> Instruction {
+    let author_profile = agent(author_agent_id);
+    build(
+        accounts::UpdateSkillListingWithSession {
+            skill_listing: find_skill_listing_pda(author_agent_id, &skill_id).0,
+            author_profile,
+            agent_session_key: find_session_key_pda(&author_profile, session_signer).0,
+            config: config(),
+            author_bond: listing_author_bond(author_agent_id, content.price_lamports),
+            session_signer: *session_signer,
+        },
+        instruction::UpdateSkillListingWithSession {
+            skill_id,
+            skill_uri: content.skill_uri,
+            name: content.name,
+            description: content.description,
+            price_lamports: content.price_lamports,
+        },
+    )
+}
+
+pub fn remove_skill_listing(author: &Pubkey, author_agent_id: &Pubkey, skill_id: String) -> Instruction {
+    build(
+        accounts::RemoveSkillListing {
+            skill_listing: find_skill_listing_pda(author_agent_id, &skill_id).0,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            author: *author,
+        },
+        instruction::RemoveSkillListing { skill_id },
+    )
+}
+
+pub fn close_skill_listing(author: &Pubkey, author_agent_id: &Pubkey, skill_id: String) -> Instruction {
+    build(
+        accounts::CloseSkillListing {
+            skill_listing: find_skill_listing_pda(author_agent_id, &skill_id).0,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            author: *author,
+        },
+        instruction::CloseSkillListing { skill_id },
+    )
+}
+
+/// `author_authority` is the author's current wallet, which receives the author share.
+pub fn purchase_skill(
+    buyer: &Pubkey,
+    skill_listing: &Pubkey,
+    author_agent_id: &Pubkey,
+    author_authority: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::PurchaseSkill {
+            skill_listing: *skill_listing,
+            purchase: find_purchase_pda(buyer, skill_listing).0,
+            author: *author_authority,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            buyer: *buyer,
+            system_program: system_program::ID,
+        },
+        instruction::PurchaseSkill {},
+    )
+}
+
+pub fn claim_voucher_revenue(
+    voucher: &Pubkey,
+    voucher_agent_id: &Pubkey,
+    skill_listing: &Pubkey,
+    author_agent_id: &Pubkey,
+) -> Instruction {
+    let voucher_profile = agent(voucher_agent_id);
+    let author_profile = agent(author_agent_id);
+    build(
+        accounts::ClaimVoucherRevenue {
+            skill_listing: *skill_listing,
+            vouch: find_vouch_pda(&voucher_profile, &author_profile).0,
+            voucher_profile,
+            author_profile,
+            config: config(),
+            voucher: *voucher,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimVoucherRevenue {},
+    )
+}
+
+/// Opens an author dispute. `backing_vouches` must list every live vouch the author
+/// has received; each becomes a `[link, vouch]` pair in `remaining_accounts`.
+#[allow(clippy::too_many_arguments)]
+pub fn open_author_dispute(
+    challenger: &Pubkey,
+    author_agent_id: &Pubkey,
+    dispute_id: u64,
+    skill_listing: &Pubkey,
+    purchase: Option<Pubkey>,
+    reason: AuthorDisputeReason,
+    evidence_uri: String,
+    backing_vouches: &[Pubkey],
+) -> Instruction {
+    let author_dispute = find_author_dispute_pda(author_agent_id, dispute_id).0;
+    let mut ix = build(
+        accounts::OpenAuthorDispute {
+            author_dispute,
+            author_profile: agent(author_agent_id),
+            config: config(),
+            skill_listing: *skill_listing,
+            purchase,
+            challenger: *challenger,
+            system_program: system_program::ID,
+        },
+        instruction::OpenAuthorDispute {
+            dispute_id,
+            reason,
+            evidence_uri,
+        },
+    );
+    ix.accounts
+        .extend(open_author_dispute_remaining_accounts(&author_dispute, backing_vouches));
+    ix
+}
+
+pub fn open_author_dispute_remaining_accounts(
+    author_dispute: &Pubkey,
+    backing_vouches: &[Pubkey],
+) -> Vec<AccountMeta> {
+    backing_vouches
+        .iter()
+        .flat_map(|vouch| {
+            [
+                AccountMeta::new(find_author_dispute_vouch_link_pda(author_dispute, vouch).0, false),
+                AccountMeta::new(*vouch, false),
+            ]
+        })
+        .collect()
+}
+
+/// Resolves an author dispute. Pass every linked vouch in `backing_vouches` when
+/// upholding a dispute whose liability reaches vouchers; they become
+/// `[link, vouch, voucher_profile]` triples. Bond-only and dismissed rulings take none,
+/// so the slice is ignored for them.
+pub fn resolve_author_dispute(
+    config_authority: &Pubkey,
+    author_agent_id: &Pubkey,
+    dispute_id: u64,
+    challenger: &Pubkey,
+    ruling: AuthorDisputeRuling,
+    author_has_bond: bool,
+    backing_vouches: &[BackingVouch],
+) -> Instruction {
+    let author_dispute = find_author_dispute_pda(author_agent_id, dispute_id).0;
+    let mut ix = build(
+        accounts::ResolveAuthorDispute {
+            author_dispute,
+            author_profile: agent(author_agent_id),
+            author_bond: author_has_bond.then(|| find_author_bond_pda(author_agent_id).0),
+            config: config(),
+            authority: *config_authority,
+            challenger: *challenger,
+        },
+        instruction::ResolveAuthorDispute { dispute_id, ruling },
+    );
+    if ruling == AuthorDisputeRuling::Upheld {
+        ix.accounts
+            .extend(resolve_author_dispute_remaining_accounts(&author_dispute, backing_vouches));
+    }
+    ix
+}
+
+pub fn resolve_author_dispute_remaining_accounts(
+    author_dispute: &Pubkey,
+    backing_vouches: &[BackingVouch],
+) -> Vec<AccountMeta> {
+    backing_vouches
+        .iter()
+        .flat_map(|backing| {
+            [
+                AccountMeta::new(
+                    find_author_dispute_vouch_link_pda(author_dispute, &backing.vouch).0,
+                    false,
+                ),
+                AccountMeta::new(backing.vouch, false),
+                AccountMeta::new(backing.voucher_profile, false),
+            ]
+        })
+        .collect()
+}
+
+/// Permissionless crank that frees a vouch left linked by a bond-only or dismissed ruling.
+pub fn release_author_dispute_vouch_link(
+    author_dispute: &Pubkey,
+    vouch: &Pubkey,
+    challenger: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ReleaseAuthorDisputeVouchLink {
+            author_dispute: *author_dispute,
+            link: find_author_dispute_vouch_link_pda(author_dispute, vouch).0,
+            vouch: *vouch,
+            config: config(),
+            challenger: *challenger,
+        },
+        instruction::ReleaseAuthorDisputeVouchLink {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_author_dispute_appends_link_vouch_pairs() {
+        let author_id = Pubkey::new_unique();
+        let vouches = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let ix = open_author_dispute(
+            &Pubkey::new_unique(),
+            &author_id,
+            3,
+            &Pubkey::new_unique(),
+            None,
+            AuthorDisputeReason::MaliciousSkill,
+            "https://evidence.example".to_string(),
+            &vouches,
+        );
+
+        let author_dispute = find_author_dispute_pda(&author_id, 3).0;
+        let remaining = &ix.accounts[ix.accounts.len() - 4..];
+        assert_eq!(
+            remaining[0].pubkey,
+            find_author_dispute_vouch_link_pda(&author_dispute, &vouches[0]).0
+        );
+        assert_eq!(remaining[1].pubkey, vouches[0]);
+        assert_eq!(remaining[3].pubkey, vouches[1]);
+        assert!(remaining.iter().all(|meta| meta.is_writable && !meta.is_signer));
+    }
+
+    #[test]
+    fn resolve_only_attaches_triples_when_upheld() {
+        let author_id = Pubkey::new_unique();
+        let backing = [BackingVouch {
+            vouch: Pubkey::new_unique(),
+            voucher_profile: Pubkey::new_unique(),
+        }];
+        let build_with = |ruling| {
+            resolve_author_dispute(
+                &Pubkey::new_unique(),
+                &author_id,
+                1,
+                &Pubkey::new_unique(),
+                ruling,
+                false,
+                &backing,
+            )
+        };
+
+        let dismissed = build_with(AuthorDisputeRuling::Dismissed);
+        let upheld = build_with(AuthorDisputeRuling::Upheld);
+        assert_eq!(upheld.accounts.len(), dismissed.accounts.len() + 3);
+        assert_eq!(upheld.accounts.last().unwrap().pubkey, backing[0].voucher_profile);
+    }
+
+    #[test]
+    fn session_listing_builders_sign_with_the_session_key_for_the_author() {
+        let (session_signer, author_id) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let content = SkillListingContent {
+            skill_uri: "https://skill.example".to_string(),
+            name: "skill".to_string(),
+            description: String::new(),
+            price_lamports: 0,
+        };
+        let author_profile = agent(&author_id);
+        let session_key = find_session_key_pda(&author_profile, &session_signer).0;
+
+        for ix in [
+            create_skill_listing_with_session(&session_signer, &author_id, "s".into(), content.clone()),
+            update_skill_listing_with_session(&session_signer, &author_id, "s".into(), content.clone()),
+        ] {
+            assert_eq!(ix.accounts[0].pubkey, find_skill_listing_pda(&author_id, "s").0);
+            assert_eq!(ix.accounts[1].pubkey, author_profile);
+            assert_eq!(ix.accounts[2].pubkey, session_key);
+            // Free listings carry the author bond.
+            assert_eq!(ix.accounts[4].pubkey, find_author_bond_pda(&author_id).0);
+            let signers: Vec<_> = ix.accounts.iter().filter(|meta| meta.is_signer).collect();
+            assert_eq!(signers.len(), 1);
+            assert_eq!(signers[0].pubkey, session_signer);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Off-chain helpers for the reputation-oracle program.
+//!
+//! - [`pda`] derives every program address from the same seeds the program checks.
+//! - [`instructions`] builds ready-to-sign instructions, including the
+//!   `remaining_accounts` layouts author disputes expect.
+//! - [`accounts`] decodes program accounts, reading legacy `AgentProfile` and
+//!   `ReputationConfig` layouts the same way the on-chain migrations do.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use reputation_oracle::ID as PROGRAM_ID;
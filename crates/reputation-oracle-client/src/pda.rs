@@ -0,0 +1,125 @@
+//! Program-derived addresses. Every finder returns `(address, bump)` like
+//! `Pubkey::find_program_address`.
+//!
+//! Agent-scoped seeds take the stable `agent_id` (the registering wallet), not the
+//! current authority, so they keep resolving after an authority rotation.
+
+use anchor_lang::prelude::Pubkey;
+use reputation_oracle::state::{
+    AgentIdentityBinding, ForeignAgentRecord, ForeignReputationAttestation, AUTHOR_BOND_SEED,
+};
+
+use reputation_oracle::wormhole::{OUTBOUND_EMITTER_SEED, OUTBOUND_MESSAGE_SEED};
+
+use crate::PROGRAM_ID;
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &PROGRAM_ID)
+}
+
+pub fn find_config_pda() -> (Pubkey, u8) {
+    find(&[b"config"])
+}
+
+pub fn find_agent_pda(agent_id: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"agent", agent_id.as_ref()])
+}
+
+/// `vouchee` is an agent profile PDA, or a foreign agent record for cross-chain vouches.
+pub fn find_vouch_pda(voucher_profile: &Pubkey, vouchee: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"vouch", voucher_profile.as_ref(), vouchee.as_ref()])
+}
+
+pub fn find_skill_listing_pda(author_agent_id: &Pubkey, skill_id: &str) -> (Pubkey, u8) {
+    find(&[b"skill", author_agent_id.as_ref(), skill_id.as_bytes()])
+}
+
+/// `buyer` is the buyer wallet, or the buyer's `agent_id` for session-key purchases.
+pub fn find_purchase_pda(buyer: &Pubkey, skill_listing: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"purchase", buyer.as_ref(), skill_listing.as_ref()])
+}
+
+pub fn find_author_bond_pda(author_agent_id: &Pubkey) -> (Pubkey, u8) {
+    find(&[AUTHOR_BOND_SEED, author_agent_id.as_ref()])
+}
+
+pub fn find_author_dispute_pda(author_agent_id: &Pubkey, dispute_id: u64) -> (Pubkey, u8) {
+    find(&[
+        b"author_dispute",
+        author_agent_id.as_ref(),
+        &dispute_id.to_le_bytes(),
+    ])
+}
+
+pub fn find_author_dispute_vouch_link_pda(author_dispute: &Pubkey, vouch: &Pubkey) -> (Pubkey, u8) {
+    find(&[
+        b"author_dispute_vouch_link",
+        author_dispute.as_ref(),
+        vouch.as_ref(),
+    ])
+}
+
+pub fn find_session_key_pda(agent_profile: &Pubkey, session_key: &Pubkey) -> (Pubkey, u8) {
+    find(&[b"session_key", agent_profile.as_ref(), session_key.as_ref()])
+}
+
+pub fn find_identity_binding_pda(canonical_agent_id: &str) -> (Pubkey, u8) {
+    find(&[
+        b"identity_binding",
+        AgentIdentityBinding::seed_hash(canonical_agent_id).as_ref(),
+    ])
+}
+
+pub fn find_foreign_agent_pda(canonical_agent_id: &str) -> (Pubkey, u8) {
+    find(&[
+        b"foreign_agent",
+        ForeignAgentRecord::seed_hash(canonical_agent_id).as_ref(),
+    ])
+}
+
+pub fn find_foreign_reputation_pda(canonical_agent_id: &str) -> (Pubkey, u8) {
+    find(&[
+        b"foreign_reputation",
+        ForeignReputationAttestation::seed_hash(canonical_agent_id).as_ref(),
+    ])
+}
+
+pub fn find_wormhole_emitter_pda(chain: u16) -> (Pubkey, u8) {
+    find(&[b"wormhole_emitter", &chain.to_le_bytes()])
+}
+
+/// This program's own Wormhole emitter; foreign chains register it as the Solana
+/// counterpart of their `WormholeEmitter` entry.
+pub fn find_outbound_emitter_pda() -> (Pubkey, u8) {
+    find(&[OUTBOUND_EMITTER_SEED])
+}
+
+/// Core-bridge message account for the outbound emitter's `sequence`.
+pub fn find_reputation_message_pda(sequence: u64) -> (Pubkey, u8) {
+    find(&[OUTBOUND_MESSAGE_SEED, &sequence.to_le_bytes()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn author_bond_matches_program_helper() {
+        let agent_id = Pubkey::new_unique();
+        assert_eq!(
+            find_author_bond_pda(&agent_id),
+            reputation_oracle::state::find_author_bond_pda(&agent_id, &PROGRAM_ID)
+        );
+    }
+
+    #[test]
+    fn author_dispute_seed_uses_little_endian_id() {
+        let agent_id = Pubkey::new_unique();
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"author_dispute", agent_id.as_ref(), &[7, 0, 0, 0, 0, 0, 0, 0]],
+            &PROGRAM_ID,
+        );
+        assert_eq!(find_author_dispute_pda(&agent_id, 7).0, expected);
+        assert_ne!(find_author_dispute_pda(&agent_id, 8).0, expected);
+    }
+}
@@ -0,0 +1,207 @@
+//! Decodes the program's Anchor events out of transaction logs.
+//!
+//! `emit!` writes each event as a `Program data: <base64>` line holding the event
+//! discriminator followed by its Borsh fields. Only lines written while the program
+//! itself is executing are decoded, so a CPI caller's data lines are never misread.
+
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::Discriminator;
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reputation_oracle::events::*;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+macro_rules! program_events {
+    ($($event:ident),* $(,)?) => {
+        /// Every event the program emits.
+        pub enum ProgramEvent {
+            $($event($event),)*
+        }
+
+        impl ProgramEvent {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                }
+            }
+
+            /// Decodes one `Program data:` payload. Returns `None` for data that is not
+            /// one of this program's events. Trailing bytes are ignored so fields appended
+            /// by a later `event_version` do not break older indexers.
+            pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+                $(
+                    if let Some(mut fields) = data.strip_prefix($event::DISCRIMINATOR) {
+                        let event = $event::deserialize(&mut fields)
+                            .with_context(|| format!("malformed {} event", stringify!($event)))?;
+                        return Ok(Some(Self::$event(event)));
+                    }
+                )*
+                Ok(None)
+            }
+        }
+    };
+}
+
+program_events!(
+    VouchCreated,
+    VouchStakeDecreased,
+    VouchRevoked,
+    VouchClosed,
+    SlashedVouchRemainderWithdrawn,
+    VouchSlashed,
+    AuthorBondDeposited,
+    AuthorBondWithdrawn,
+    AuthorBondSlashed,
+    AuthorDisputeOpened,
+    AuthorDisputeResolved,
+    AuthorDisputeVouchLinked,
+    AuthorDisputeVouchLinkReleased,
+    SkillListingCreated,
+    SkillListingUpdated,
+    SkillListingRemoved,
+    SkillListingClosed,
+    SkillPurchased,
+    RevenueClaimed,
+    TrustDepthRefreshed,
+    PurchaseClosed,
+    AgentRegistered,
+    AgentProfileMigrated,
+    AgentRegisteredAtRepaired,
+    AgentProfileCountersRecomputed,
+    ReputationScoreChanged,
+    AgentProfileClosed,
+    AgentAuthorityRotated,
+    AgentDelegateUpdated,
+    AgentSessionKeyCreated,
+    AgentSessionKeyRevoked,
+    SessionKeySpend,
+    AgentIdentityBound,
+    AgentIdentityAttested,
+    AgentIdentityReassigned,
+    ForeignAgentRecordCreated,
+    ForeignAgentRecordClaimed,
+    WormholeEmitterRegistered,
+    ForeignReputationAttested,
+    ReputationMessagePublished,
+    ConfigInitialized,
+    ConfigMigrated,
+    AccountMigrated,
+    TrustThresholdsUpdated,
+    VouchWeightingUpdated,
+    ChainContextUpdated,
+    AgentRegistryUpdated,
+);
+
+/// Raw `Program data:` payloads written by `program_id`, in log order. Logs truncated
+/// by the runtime simply yield fewer payloads.
+pub fn program_data(logs: &[String], program_id: &Pubkey) -> Result<Vec<Vec<u8>>> {
+    let program_id = program_id.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut payloads = Vec::new();
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invocations.last() == Some(&program_id.as_str()) {
+                payloads.push(
+                    BASE64
+                        .decode(data.trim())
+                        .with_context(|| format!("invalid base64 in log line {log:?}"))?,
+                );
+            }
+            continue;
+        }
+        let mut words = log.split_whitespace();
+        if let (Some("Program"), Some(program), Some(action)) =
+            (words.next(), words.next(), words.next())
+        {
+            if action == "invoke" {
+                invocations.push(program);
+            } else if action == "success" || action.starts_with("failed") {
+                invocations.pop();
+            }
+        }
+    }
+    Ok(payloads)
+}
+
+/// Decodes every event `program_id` emitted in one transaction's logs, in order.
+pub fn decode_logs(logs: &[String], program_id: &Pubkey) -> Result<Vec<ProgramEvent>> {
+    let mut events = Vec::new();
+    for data in program_data(logs, program_id)? {
+        if let Some(event) = ProgramEvent::decode(&data)? {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use anchor_lang::Event;
+
+    /// Formats an event the way `emit!` logs it.
+    pub(crate) fn data_log<E: Event>(event: &E) -> String {
+        format!("{PROGRAM_DATA_PREFIX}{}", BASE64.encode(event.data()))
+    }
+
+    pub(crate) fn invocation(program_id: &Pubkey, lines: Vec<String>) -> Vec<String> {
+        let mut logs = vec![format!("Program {program_id} invoke [1]")];
+        logs.extend(lines);
+        logs.push(format!("Program {program_id} success"));
+        logs
+    }
+
+    fn score_changed(reputation_score: u64) -> ReputationScoreChanged {
+        ReputationScoreChanged {
+            event_version: EVENT_VERSION,
+            chain_context_id: 7,
+            agent: Pubkey::new_unique(),
+            previous_score: 0,
+            reputation_score,
+            timestamp: 1_750_000_000,
+        }
+    }
+
+    #[test]
+    fn decodes_events_emitted_by_the_program_only() {
+        let other_program = Pubkey::new_unique();
+        let mut logs = invocation(
+            &reputation_oracle::ID,
+            vec![
+                "Program log: Instruction: Vouch".to_string(),
+                data_log(&score_changed(5)),
+                format!("Program {other_program} invoke [2]"),
+                data_log(&score_changed(6)),
+                format!("Program {other_program} success"),
+                data_log(&score_changed(7)),
+            ],
+        );
+        logs.push(data_log(&score_changed(8)));
+
+        let scores: Vec<u64> = decode_logs(&logs, &reputation_oracle::ID)
+            .unwrap()
+            .into_iter()
+            .map(|event| match event {
+                ProgramEvent::ReputationScoreChanged(event) => event.reputation_score,
+                other => panic!("unexpected {}", other.name()),
+            })
+            .collect();
+        assert_eq!(scores, vec![5, 7]);
+    }
+
+    #[test]
+    fn ignores_unknown_discriminators_and_appended_fields() {
+        assert!(ProgramEvent::decode(&[0u8; 16]).unwrap().is_none());
+
+        let mut data = score_changed(9).data();
+        data.extend_from_slice(&[1, 2, 3]);
+        let event = ProgramEvent::decode(&data).unwrap().unwrap();
+        assert_eq!(event.name(), "ReputationScoreChanged");
+
+        let mut truncated = score_changed(9).data();
+        truncated.truncate(truncated.len() - 4);
+        assert!(ProgramEvent::decode(&truncated).is_err());
+    }
+}
@@ -0,0 +1,351 @@
+//! JSON-lines ingest, one record per line:
+//!
+//! ```text
+//! {"slot": 120, "signature": "5h…", "err": null, "logs": ["Program … invoke [1]", …]}
+//! {"rollback_to": 118}
+//! ```
+//!
+//! Transaction records carry the fields of `getTransaction`'s `meta` that the indexer
+//! needs; failed transactions are skipped because their events never took effect. A
+//! `rollback_to` record marks a fork switch and drops everything above that slot.
+
+use std::io::BufRead;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::store::Store;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Record {
+    Rollback {
+        rollback_to: u64,
+    },
+    Transaction {
+        slot: u64,
+        signature: String,
+        #[serde(default)]
+        err: Option<Value>,
+        #[serde(default)]
+        logs: Vec<String>,
+    },
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct IngestSummary {
+    pub transactions: u64,
+    pub failed_transactions: u64,
+    pub events: u64,
+    pub rollbacks: u64,
+    pub events_rolled_back: u64,
+}
+
+pub fn ingest_jsonl(
+    store: &mut Store,
+    reader: impl BufRead,
+    program_id: &Pubkey,
+) -> Result<IngestSummary> {
+    let mut summary = IngestSummary::default();
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record = serde_json::from_str(&line)
+            .with_context(|| format!("line {}: not a transaction or rollback record", line_number + 1))?;
+        match record {
+            Record::Rollback { rollback_to } => {
+                summary.rollbacks += 1;
+                summary.events_rolled_back += store.rollback_to(rollback_to)? as u64;
+            }
+            Record::Transaction { err: Some(_), .. } => {
+                summary.failed_transactions += 1;
+            }
+            Record::Transaction {
+                slot,
+                signature,
+                logs,
+                ..
+            } => {
+                summary.transactions += 1;
+                summary.events += store
+                    .ingest_transaction(slot, &signature, &logs, program_id)
+                    .with_context(|| format!("line {}", line_number + 1))?
+                    as u64;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::tests::{data_log, invocation};
+    use crate::PROGRAM_ID;
+    use reputation_oracle::events::*;
+    use reputation_oracle::state::{
+        AuthorDisputeLiabilityScope, AuthorDisputeReason, AuthorDisputeRuling,
+    };
+    use serde_json::json;
+
+    const CHAIN_ID: u32 = 7;
+
+    struct Fixture {
+        author: Pubkey,
+        author_profile: Pubkey,
+        voucher_profile: Pubkey,
+        vouch: Pubkey,
+        listing: Pubkey,
+        dispute: Pubkey,
+    }
+
+    fn transaction(slot: u64, signature: &str, events: Vec<String>) -> String {
+        json!({ "slot": slot, "signature": signature, "err": null, "logs": invocation(&PROGRAM_ID, events) })
+            .to_string()
+    }
+
+    fn registered(agent: Pubkey, agent_id: Pubkey) -> String {
+        data_log(&AgentRegistered {
+            event_version: EVENT_VERSION,
+            chain_context_id: CHAIN_ID,
+            agent,
+            agent_id,
+            authority: agent_id,
+            metadata_uri: "https://agent".to_string(),
+            is_new: true,
+            registered_at: 100,
+            timestamp: 100,
+        })
+    }
+
+    fn history(f: &Fixture) -> Vec<String> {
+        let buyer = Pubkey::new_unique();
+        let purchased = SkillPurchased {
+            event_version: EVENT_VERSION,
+            chain_context_id: CHAIN_ID,
+            purchase: Pubkey::new_unique(),
+            skill_listing: f.listing,
+            buyer,
+            price: 1_000,
+            author_share: 600,
+            voucher_pool: 400,
+            timestamp: 130,
+        };
+        vec![
+            transaction(
+                10,
+                "register",
+                vec![
+                    registered(f.author_profile, f.author),
+                    registered(f.voucher_profile, Pubkey::new_unique()),
+                ],
+            ),
+            transaction(
+                11,
+                "vouch",
+                vec![data_log(&VouchCreated {
+                    event_version: EVENT_VERSION,
+                    chain_context_id: CHAIN_ID,
+                    vouch: f.vouch,
+                    voucher: f.voucher_profile,
+                    vouchee: f.author_profile,
+                    stake_amount: 5_000,
+                    total_stake_amount: 5_000,
+                    effective_weight_bps: 10_000,
+                    timestamp: 110,
+                })],
+            ),
+            transaction(
+                12,
+                "list",
+                vec![data_log(&SkillListingCreated {
+                    event_version: EVENT_VERSION,
+                    chain_context_id: CHAIN_ID,
+                    skill_listing: f.listing,
+                    author: f.author,
+                    name: "skill".to_string(),
+                    price_lamports: 1_000,
+                    timestamp: 120,
+                })],
+            ),
+            transaction(13, "purchase", vec![data_log(&purchased)]),
+            json!({ "slot": 13, "signature": "failed", "err": { "InstructionError": [0, "Custom"] },
+                    "logs": invocation(&PROGRAM_ID, vec![data_log(&purchased)]) })
+            .to_string(),
+            transaction(
+                14,
+                "claim",
+                vec![data_log(&RevenueClaimed {
+                    event_version: EVENT_VERSION,
+                    chain_context_id: CHAIN_ID,
+                    skill_listing: f.listing,
+                    vouch: f.vouch,
+                    voucher: Pubkey::new_unique(),
+                    amount: 400,
+                    timestamp: 140,
+                })],
+            ),
+            transaction(
+                20,
+                "dispute",
+                vec![
+                    data_log(&AuthorDisputeVouchLinked {
+                        event_version: EVENT_VERSION,
+                        chain_context_id: CHAIN_ID,
+                        author_dispute: f.dispute,
+                        vouch: f.vouch,
+                        timestamp: 200,
+                    }),
+                    data_log(&AuthorDisputeOpened {
+                        event_version: EVENT_VERSION,
+                        chain_context_id: CHAIN_ID,
+                        author_dispute: f.dispute,
+                        author: f.author,
+                        challenger: buyer,
+                        reason: AuthorDisputeReason::MaliciousSkill,
+                        liability_scope: AuthorDisputeLiabilityScope::AuthorBondThenVouchers,
+                        skill_listing: f.listing,
+                        skill_price_lamports_snapshot: 1_000,
+                        purchase: Some(purchased.purchase),
+                        linked_vouch_count: 1,
+                        bond_amount: 500,
+                        timestamp: 200,
+                    }),
+                ],
+            ),
+            transaction(
+                21,
+                "resolve",
+                vec![
+                    data_log(&VouchSlashed {
+                        event_version: EVENT_VERSION,
+                        chain_context_id: CHAIN_ID,
+                        author_dispute: f.dispute,
+                        vouch: f.vouch,
+                        voucher: f.voucher_profile,
+                        vouchee: f.author_profile,
+                        amount: 2_500,
+                        remaining_stake_amount: 2_500,
+                        timestamp: 210,
+                    }),
+                    data_log(&AuthorDisputeResolved {
+                        event_version: EVENT_VERSION,
+                        chain_context_id: CHAIN_ID,
+                        author_dispute: f.dispute,
+                        author: f.author,
+                        ruling: AuthorDisputeRuling::Upheld,
+                        liability_scope: AuthorDisputeLiabilityScope::AuthorBondThenVouchers,
+                        linked_vouch_count: 1,
+                        author_bond_slashed_amount: 0,
+                        voucher_slashed_amount: 2_500,
+                        slashed_amount: 2_500,
+                        timestamp: 210,
+                    }),
+                ],
+            ),
+        ]
+    }
+
+    fn ingest(store: &mut Store, lines: &[String]) -> IngestSummary {
+        ingest_jsonl(store, lines.join("\n").as_bytes(), &PROGRAM_ID).unwrap()
+    }
+
+    fn fixture() -> Fixture {
+        Fixture {
+            author: Pubkey::new_unique(),
+            author_profile: Pubkey::new_unique(),
+            voucher_profile: Pubkey::new_unique(),
+            vouch: Pubkey::new_unique(),
+            listing: Pubkey::new_unique(),
+            dispute: Pubkey::new_unique(),
+        }
+    }
+
+    fn vouch_row(store: &Store, vouch: &Pubkey) -> (String, i64, i64) {
+        store
+            .connection()
+            .query_row(
+                "SELECT status, stake_amount, cumulative_revenue FROM vouches WHERE vouch = ?1",
+                [vouch.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn projects_history_into_tables_and_metrics() {
+        let f = fixture();
+        let mut store = Store::open_in_memory().unwrap();
+        let summary = ingest(&mut store, &history(&f));
+        assert_eq!(summary.transactions, 7);
+        assert_eq!(summary.failed_transactions, 1);
+        assert_eq!(summary.events, 10);
+        assert_eq!(store.cursor().unwrap(), Some(21));
+
+        assert_eq!(vouch_row(&store, &f.vouch), ("slashed".to_string(), 2_500, 400));
+        let link_slash: i64 = store
+            .connection()
+            .query_row("SELECT slashed_amount FROM dispute_vouch_links", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(link_slash, 2_500);
+
+        let metrics = store.metrics().unwrap();
+        assert_eq!(metrics.agents, 2);
+        assert_eq!(metrics.authors, 1);
+        assert_eq!(metrics.published_skills, 1);
+        assert_eq!(metrics.purchases, 1);
+        assert_eq!(metrics.total_revenue, 1_000);
+        assert_eq!(metrics.total_voucher_earnings, 400);
+        assert_eq!(metrics.active_vouches, 0);
+        assert_eq!(metrics.upheld_disputes, 1);
+        assert_eq!(metrics.total_slashed, 2_500);
+    }
+
+    #[test]
+    fn reingesting_is_a_no_op() {
+        let f = fixture();
+        let mut store = Store::open_in_memory().unwrap();
+        let lines = history(&f);
+        ingest(&mut store, &lines);
+        let metrics = store.metrics().unwrap();
+
+        let again = ingest(&mut store, &lines);
+        assert_eq!(again.events, 0);
+        assert_eq!(store.metrics().unwrap(), metrics);
+        assert_eq!(vouch_row(&store, &f.vouch).2, 400);
+    }
+
+    #[test]
+    fn rollback_rebuilds_tables_from_the_surviving_fork() {
+        let f = fixture();
+        let mut store = Store::open_in_memory().unwrap();
+        let mut lines = history(&f);
+        lines.push(json!({ "rollback_to": 14 }).to_string());
+        let summary = ingest(&mut store, &lines);
+        assert_eq!(summary.events_rolled_back, 4);
+        assert_eq!(store.cursor().unwrap(), Some(14));
+
+        assert_eq!(vouch_row(&store, &f.vouch), ("active".to_string(), 5_000, 400));
+        let metrics = store.metrics().unwrap();
+        assert_eq!(metrics.total_disputes, 0);
+        assert_eq!(metrics.total_staked, 5_000);
+        assert_eq!(metrics.total_voucher_earnings, 400);
+
+        // The dropped transactions land again on the new fork.
+        let replayed = ingest(&mut store, &history(&f)[6..]);
+        assert_eq!(replayed.events, 4);
+        assert_eq!(store.metrics().unwrap().upheld_disputes, 1);
+        assert_eq!(store.rollback_to(30).unwrap(), 0);
+    }
+
+    #[test]
+    fn rejects_unrecognized_records() {
+        let mut store = Store::open_in_memory().unwrap();
+        let err = ingest_jsonl(&mut store, "\n{\"slot\": 1}\n".as_bytes(), &PROGRAM_ID).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+}
@@ -0,0 +1,12 @@
+//! Off-chain indexer for reputation-oracle events.
+//!
+//! - [`events`] decodes `Program data:` log lines into typed program events.
+//! - [`store`] keeps the raw events in SQLite and projects them into agents, vouches,
+//!   listings, purchases, disputes, and revenue claims, with slot rollback.
+//! - [`ingest`] feeds the store from JSON-lines transaction logs.
+
+pub mod events;
+pub mod ingest;
+pub mod store;
+
+pub use reputation_oracle::ID as PROGRAM_ID;
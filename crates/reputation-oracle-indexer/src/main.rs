@@ -0,0 +1,58 @@
+//! Command-line front end for the indexer.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use reputation_oracle_indexer::ingest::ingest_jsonl;
+use reputation_oracle_indexer::store::Store;
+use reputation_oracle_indexer::PROGRAM_ID;
+
+#[derive(Parser)]
+#[command(name = "reputation-oracle-indexer", version, about)]
+struct Cli {
+    /// SQLite database, created on first use.
+    #[arg(long, global = true, default_value = "reputation-oracle.sqlite")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Index a JSON-lines file of transaction logs (`-` reads stdin).
+    Ingest { input: PathBuf },
+    /// Drop everything indexed above a slot and rebuild the tables.
+    Rollback { slot: u64 },
+    /// Print dashboard totals as JSON.
+    Metrics,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let mut store = Store::open(&cli.db)?;
+    match cli.command {
+        Command::Ingest { input } => {
+            let reader: Box<dyn BufRead> = if input.as_os_str() == "-" {
+                Box::new(io::stdin().lock())
+            } else {
+                let file = File::open(&input)
+                    .with_context(|| format!("failed to open {}", input.display()))?;
+                Box::new(BufReader::new(file))
+            };
+            let summary = ingest_jsonl(&mut store, reader, &PROGRAM_ID)?;
+            println!("{}", serde_json::to_string(&summary)?);
+        }
+        Command::Rollback { slot } => {
+            let dropped = store.rollback_to(slot)?;
+            println!("{}", serde_json::json!({ "slot": slot, "events_rolled_back": dropped }));
+        }
+        Command::Metrics => {
+            println!("{}", serde_json::to_string_pretty(&store.metrics()?)?);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,617 @@
+//! SQLite store.
+//!
+//! Every decoded event is kept verbatim in `events`, keyed by transaction signature and
+//! position, and also applied to the projection tables dashboards query. Re-ingesting a
+//! transaction is a no-op. Rolling back to a slot drops the events above it and rebuilds
+//! the projections by replaying what is left, so the tables never hold state from an
+//! abandoned fork.
+
+use std::path::Path;
+
+use anchor_lang::prelude::Pubkey;
+use anyhow::{Context, Result};
+use reputation_oracle::state::{
+    AuthorDisputeLiabilityScope, AuthorDisputeReason, AuthorDisputeRuling, VouchStatus,
+};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::Serialize;
+
+use crate::events::{program_data, ProgramEvent};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    slot INTEGER NOT NULL,
+    signature TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    data BLOB NOT NULL,
+    UNIQUE (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS events_slot ON events (slot);
+
+CREATE TABLE IF NOT EXISTS cursor (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    slot INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS agents (
+    agent TEXT PRIMARY KEY,
+    agent_id TEXT NOT NULL,
+    authority TEXT NOT NULL,
+    delegate TEXT,
+    metadata_uri TEXT NOT NULL,
+    registered_at INTEGER NOT NULL,
+    reputation_score INTEGER NOT NULL DEFAULT 0,
+    author_bond_lamports INTEGER NOT NULL DEFAULT 0,
+    trust_depth INTEGER NOT NULL DEFAULT 0,
+    closed_at INTEGER
+);
+CREATE INDEX IF NOT EXISTS agents_agent_id ON agents (agent_id);
+
+CREATE TABLE IF NOT EXISTS vouches (
+    vouch TEXT PRIMARY KEY,
+    voucher TEXT NOT NULL,
+    vouchee TEXT NOT NULL,
+    stake_amount INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    cumulative_revenue INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    closed_at INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS skill_listings (
+    skill_listing TEXT PRIMARY KEY,
+    author TEXT NOT NULL,
+    name TEXT NOT NULL,
+    price_lamports INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    total_downloads INTEGER NOT NULL DEFAULT 0,
+    total_revenue INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+    closed_at INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS purchases (
+    purchase TEXT PRIMARY KEY,
+    skill_listing TEXT NOT NULL,
+    buyer TEXT NOT NULL,
+    price INTEGER NOT NULL,
+    author_share INTEGER NOT NULL,
+    voucher_pool INTEGER NOT NULL,
+    purchased_at INTEGER NOT NULL,
+    closed_at INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS disputes (
+    author_dispute TEXT PRIMARY KEY,
+    author TEXT NOT NULL,
+    challenger TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    liability_scope TEXT NOT NULL,
+    skill_listing TEXT NOT NULL,
+    purchase TEXT,
+    linked_vouch_count INTEGER NOT NULL,
+    bond_amount INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    ruling TEXT,
+    author_bond_slashed_amount INTEGER NOT NULL DEFAULT 0,
+    voucher_slashed_amount INTEGER NOT NULL DEFAULT 0,
+    opened_at INTEGER NOT NULL,
+    resolved_at INTEGER
+);
+
+CREATE TABLE IF NOT EXISTS dispute_vouch_links (
+    author_dispute TEXT NOT NULL,
+    vouch TEXT NOT NULL,
+    slashed_amount INTEGER,
+    linked_at INTEGER NOT NULL,
+    settled_at INTEGER,
+    PRIMARY KEY (author_dispute, vouch)
+);
+
+CREATE TABLE IF NOT EXISTS revenue_claims (
+    signature TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    skill_listing TEXT NOT NULL,
+    vouch TEXT NOT NULL,
+    voucher TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    claimed_at INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+";
+
+/// Tables derived from `events`, cleared and replayed on rollback.
+const PROJECTIONS: [&str; 7] = [
+    "agents",
+    "vouches",
+    "skill_listings",
+    "purchases",
+    "disputes",
+    "dispute_vouch_links",
+    "revenue_claims",
+];
+
+/// Network-wide dashboard totals. Lamport amounts are summed over the indexed history.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Metrics {
+    pub agents: u64,
+    pub authors: u64,
+    pub published_skills: u64,
+    pub purchased_skills: u64,
+    pub purchases: u64,
+    pub active_vouches: u64,
+    pub total_staked: u64,
+    pub total_revenue: u64,
+    pub total_voucher_earnings: u64,
+    pub total_disputes: u64,
+    pub open_disputes: u64,
+    pub resolved_disputes: u64,
+    pub upheld_disputes: u64,
+    pub dismissed_disputes: u64,
+    pub total_slashed: u64,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+/// Position of an event inside the transaction that emitted it.
+struct EventContext<'a> {
+    signature: &'a str,
+    event_index: u32,
+}
+
+impl Store {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        Self::with_connection(conn)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Highest slot ingested so far, including transactions that emitted nothing.
+    pub fn cursor(&self) -> Result<Option<u64>> {
+        let slot: Option<i64> = self
+            .conn
+            .query_row("SELECT slot FROM cursor WHERE id = 0", [], |row| row.get(0))
+            .optional()?;
+        Ok(slot.map(|slot| slot as u64))
+    }
+
+    /// Indexes one successful transaction's logs. Returns the number of new events, which
+    /// is zero when the signature was already indexed.
+    pub fn ingest_transaction(
+        &mut self,
+        slot: u64,
+        signature: &str,
+        logs: &[String],
+        program_id: &Pubkey,
+    ) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let indexed: bool = tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM events WHERE signature = ?1)",
+            [signature],
+            |row| row.get(0),
+        )?;
+        let mut inserted = 0;
+        if !indexed {
+            for (event_index, data) in program_data(logs, program_id)?.into_iter().enumerate() {
+                let Some(event) = ProgramEvent::decode(&data)? else {
+                    continue;
+                };
+                let context = EventContext {
+                    signature,
+                    event_index: event_index as u32,
+                };
+                tx.execute(
+                    "INSERT INTO events (slot, signature, event_index, name, data)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![slot as i64, signature, context.event_index, event.name(), data],
+                )?;
+                apply(&tx, &context, &event)
+                    .with_context(|| format!("failed to apply {} from {signature}", event.name()))?;
+                inserted += 1;
+            }
+        }
+        tx.execute(
+            "INSERT INTO cursor (id, slot) VALUES (0, ?1)
+             ON CONFLICT (id) DO UPDATE SET slot = MAX(slot, excluded.slot)",
+            [slot as i64],
+        )?;
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Forgets everything indexed above `slot` and rebuilds the projections from the
+    /// remaining events. Returns the number of events dropped; rolling back to a slot at
+    /// or above the cursor changes nothing.
+    pub fn rollback_to(&mut self, slot: u64) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let dropped = tx.execute("DELETE FROM events WHERE slot > ?1", [slot as i64])?;
+        tx.execute(
+            "UPDATE cursor SET slot = MIN(slot, ?1) WHERE id = 0",
+            [slot as i64],
+        )?;
+        if dropped > 0 {
+            rebuild_projections(&tx)?;
+        }
+        tx.commit()?;
+        Ok(dropped)
+    }
+
+    pub fn metrics(&self) -> Result<Metrics> {
+        let count = |sql: &str| -> Result<u64> {
+            let value: i64 = self.conn.query_row(sql, [], |row| row.get(0))?;
+            Ok(value as u64)
+        };
+        Ok(Metrics {
+            agents: count("SELECT COUNT(*) FROM agents WHERE closed_at IS NULL")?,
+            authors: count("SELECT COUNT(DISTINCT author) FROM skill_listings")?,
+            published_skills: count("SELECT COUNT(*) FROM skill_listings WHERE status = 'active'")?,
+            purchased_skills: count("SELECT COUNT(DISTINCT skill_listing) FROM purchases")?,
+            purchases: count("SELECT COUNT(*) FROM purchases")?,
+            active_vouches: count("SELECT COUNT(*) FROM vouches WHERE status = 'active'")?,
+            total_staked: count(
+                "SELECT COALESCE(SUM(stake_amount), 0) FROM vouches WHERE status = 'active'",
+            )?,
+            total_revenue: count("SELECT COALESCE(SUM(price), 0) FROM purchases")?,
+            total_voucher_earnings: count("SELECT COALESCE(SUM(amount), 0) FROM revenue_claims")?,
+            total_disputes: count("SELECT COUNT(*) FROM disputes")?,
+            open_disputes: count("SELECT COUNT(*) FROM disputes WHERE status = 'open'")?,
+            resolved_disputes: count("SELECT COUNT(*) FROM disputes WHERE status = 'resolved'")?,
+            upheld_disputes: count("SELECT COUNT(*) FROM disputes WHERE ruling = 'upheld'")?,
+            dismissed_disputes: count("SELECT COUNT(*) FROM disputes WHERE ruling = 'dismissed'")?,
+            total_slashed: count(
+                "SELECT COALESCE(SUM(author_bond_slashed_amount + voucher_slashed_amount), 0)
+                 FROM disputes",
+            )?,
+        })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+fn rebuild_projections(tx: &Transaction) -> Result<()> {
+    for table in PROJECTIONS {
+        tx.execute(&format!("DELETE FROM {table}"), [])?;
+    }
+    let mut statement =
+        tx.prepare("SELECT signature, event_index, data FROM events ORDER BY slot, seq")?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let signature: String = row.get(0)?;
+        let context = EventContext {
+            signature: &signature,
+            event_index: row.get(1)?,
+        };
+        let data: Vec<u8> = row.get(2)?;
+        if let Some(event) = ProgramEvent::decode(&data)? {
+            apply(tx, &context, &event)?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies one event to the projection tables. Events the dashboards do not track, such
+/// as config and cross-chain updates, only live in `events`.
+fn apply(tx: &Transaction, context: &EventContext, event: &ProgramEvent) -> Result<()> {
+    match event {
+        ProgramEvent::AgentRegistered(e) if e.is_new => {
+            tx.execute(
+                "INSERT OR REPLACE INTO agents (agent, agent_id, authority, metadata_uri, registered_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![key(&e.agent), key(&e.agent_id), key(&e.authority), e.metadata_uri, e.registered_at],
+            )?;
+        }
+        ProgramEvent::AgentRegistered(e) => {
+            tx.execute(
+                "UPDATE agents SET metadata_uri = ?2 WHERE agent = ?1",
+                params![key(&e.agent), e.metadata_uri],
+            )?;
+        }
+        ProgramEvent::AgentProfileMigrated(e) => {
+            tx.execute(
+                "UPDATE agents SET metadata_uri = ?2 WHERE agent = ?1",
+                params![key(&e.agent), e.metadata_uri],
+            )?;
+        }
+        ProgramEvent::AgentRegisteredAtRepaired(e) => {
+            tx.execute(
+                "UPDATE agents SET registered_at = ?2 WHERE agent = ?1",
+                params![key(&e.agent), e.registered_at],
+            )?;
+        }
+        ProgramEvent::ReputationScoreChanged(e) => {
+            tx.execute(
+                "UPDATE agents SET reputation_score = ?2 WHERE agent = ?1",
+                params![key(&e.agent), e.reputation_score as i64],
+            )?;
+        }
+        ProgramEvent::TrustDepthRefreshed(e) => {
+            tx.execute(
+                "UPDATE agents SET trust_depth = ?2 WHERE agent = ?1",
+                params![key(&e.agent), e.trust_depth],
+            )?;
+        }
+        ProgramEvent::AgentAuthorityRotated(e) => {
+            tx.execute(
+                "UPDATE agents SET authority = ?2 WHERE agent = ?1",
+                params![key(&e.agent), key(&e.new_authority)],
+            )?;
+        }
+        ProgramEvent::AgentDelegateUpdated(e) => {
+            let delegate = (e.delegate != Pubkey::default()).then(|| key(&e.delegate));
+            tx.execute(
+                "UPDATE agents SET delegate = ?2 WHERE agent = ?1",
+                params![key(&e.agent), delegate],
+            )?;
+        }
+        ProgramEvent::AgentProfileClosed(e) => {
+            tx.execute(
+                "UPDATE agents SET reputation_score = ?2, closed_at = ?3 WHERE agent = ?1",
+                params![key(&e.agent), e.reputation_score as i64, e.timestamp],
+            )?;
+        }
+        ProgramEvent::AuthorBondDeposited(e) => {
+            set_author_bond(tx, &e.author, e.total_bond_amount)?;
+        }
+        ProgramEvent::AuthorBondWithdrawn(e) => {
+            set_author_bond(tx, &e.author, e.total_bond_amount)?;
+        }
+        ProgramEvent::AuthorBondSlashed(e) => {
+            set_author_bond(tx, &e.author, e.remaining_bond_amount)?;
+        }
+        ProgramEvent::VouchCreated(e) => {
+            // A vouch PDA closed earlier and opened again starts a fresh history.
+            tx.execute(
+                "INSERT INTO vouches (vouch, voucher, vouchee, stake_amount, status, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, 'active', ?5, ?5)
+                 ON CONFLICT (vouch) DO UPDATE SET
+                     stake_amount = excluded.stake_amount,
+                     status = 'active',
+                     created_at = CASE WHEN closed_at IS NULL THEN created_at ELSE excluded.created_at END,
+                     cumulative_revenue = CASE WHEN closed_at IS NULL THEN cumulative_revenue ELSE 0 END,
+                     updated_at = excluded.updated_at,
+                     closed_at = NULL",
+                params![
+                    key(&e.vouch),
+                    key(&e.voucher),
+                    key(&e.vouchee),
+                    e.total_stake_amount as i64,
+                    e.timestamp
+                ],
+            )?;
+        }
+        ProgramEvent::VouchStakeDecreased(e) => {
+            tx.execute(
+                "UPDATE vouches SET stake_amount = ?2, updated_at = ?3 WHERE vouch = ?1",
+                params![key(&e.vouch), e.total_stake_amount as i64, e.timestamp],
+            )?;
+        }
+        ProgramEvent::VouchRevoked(e) => {
+            tx.execute(
+                "UPDATE vouches SET status = 'revoked', stake_amount = 0, updated_at = ?2 WHERE vouch = ?1",
+                params![key(&e.vouch), e.timestamp],
+            )?;
+        }
+        ProgramEvent::VouchSlashed(e) => {
+            tx.execute(
+                "UPDATE vouches SET status = 'slashed', stake_amount = ?2, updated_at = ?3 WHERE vouch = ?1",
+                params![key(&e.vouch), e.remaining_stake_amount as i64, e.timestamp],
+            )?;
+            tx.execute(
+                "UPDATE dispute_vouch_links SET slashed_amount = ?3, settled_at = ?4
+                 WHERE author_dispute = ?1 AND vouch = ?2",
+                params![key(&e.author_dispute), key(&e.vouch), e.amount as i64, e.timestamp],
+            )?;
+        }
+        ProgramEvent::SlashedVouchRemainderWithdrawn(e) => {
+            tx.execute(
+                "UPDATE vouches SET stake_amount = MAX(stake_amount - ?2, 0), updated_at = ?3
+                 WHERE vouch = ?1",
+                params![key(&e.vouch), e.stake_returned as i64, e.timestamp],
+            )?;
+        }
+        ProgramEvent::VouchClosed(e) => {
+            tx.execute(
+                "UPDATE vouches SET status = ?2, stake_amount = 0, cumulative_revenue = ?3,
+                     updated_at = ?4, closed_at = ?4
+                 WHERE vouch = ?1",
+                params![
+                    key(&e.vouch),
+                    vouch_status(e.final_status),
+                    e.cumulative_revenue as i64,
+                    e.timestamp
+                ],
+            )?;
+        }
+        ProgramEvent::SkillListingCreated(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO skill_listings
+                     (skill_listing, author, name, price_lamports, status, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, 'active', ?5, ?5)",
+                params![
+                    key(&e.skill_listing),
+                    key(&e.author),
+                    e.name,
+                    e.price_lamports as i64,
+                    e.timestamp
+                ],
+            )?;
+        }
+        ProgramEvent::SkillListingUpdated(e) => {
+            tx.execute(
+                "UPDATE skill_listings SET name = ?2, price_lamports = ?3, updated_at = ?4
+                 WHERE skill_listing = ?1",
+                params![key(&e.skill_listing), e.name, e.price_lamports as i64, e.timestamp],
+            )?;
+        }
+        ProgramEvent::SkillListingRemoved(e) => {
+            tx.execute(
+                "UPDATE skill_listings SET status = 'removed', updated_at = ?2 WHERE skill_listing = ?1",
+                params![key(&e.skill_listing), e.timestamp],
+            )?;
+        }
+        ProgramEvent::SkillListingClosed(e) => {
+            tx.execute(
+                "UPDATE skill_listings SET closed_at = ?2 WHERE skill_listing = ?1",
+                params![key(&e.skill_listing), e.timestamp],
+            )?;
+        }
+        ProgramEvent::SkillPurchased(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO purchases
+                     (purchase, skill_listing, buyer, price, author_share, voucher_pool, purchased_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    key(&e.purchase),
+                    key(&e.skill_listing),
+                    key(&e.buyer),
+                    e.price as i64,
+                    e.author_share as i64,
+                    e.voucher_pool as i64,
+                    e.timestamp
+                ],
+            )?;
+            tx.execute(
+                "UPDATE skill_listings
+                 SET total_downloads = total_downloads + 1, total_revenue = total_revenue + ?2
+                 WHERE skill_listing = ?1",
+                params![key(&e.skill_listing), e.price as i64],
+            )?;
+        }
+        ProgramEvent::PurchaseClosed(e) => {
+            tx.execute(
+                "UPDATE purchases SET closed_at = ?2 WHERE purchase = ?1",
+                params![key(&e.purchase), e.timestamp],
+            )?;
+        }
+        ProgramEvent::RevenueClaimed(e) => {
+            tx.execute(
+                "INSERT OR IGNORE INTO revenue_claims
+                     (signature, event_index, skill_listing, vouch, voucher, amount, claimed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    context.signature,
+                    context.event_index,
+                    key(&e.skill_listing),
+                    key(&e.vouch),
+                    key(&e.voucher),
+                    e.amount as i64,
+                    e.timestamp
+                ],
+            )?;
+            tx.execute(
+                "UPDATE vouches SET cumulative_revenue = cumulative_revenue + ?2 WHERE vouch = ?1",
+                params![key(&e.vouch), e.amount as i64],
+            )?;
+        }
+        ProgramEvent::AuthorDisputeOpened(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO disputes
+                     (author_dispute, author, challenger, reason, liability_scope, skill_listing,
+                      purchase, linked_vouch_count, bond_amount, status, opened_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 'open', ?10)",
+                params![
+                    key(&e.author_dispute),
+                    key(&e.author),
+                    key(&e.challenger),
+                    dispute_reason(e.reason),
+                    liability_scope(e.liability_scope),
+                    key(&e.skill_listing),
+                    e.purchase.as_ref().map(key),
+                    e.linked_vouch_count,
+                    e.bond_amount as i64,
+                    e.timestamp
+                ],
+            )?;
+        }
+        ProgramEvent::AuthorDisputeVouchLinked(e) => {
+            tx.execute(
+                "INSERT OR REPLACE INTO dispute_vouch_links (author_dispute, vouch, linked_at)
+                 VALUES (?1, ?2, ?3)",
+                params![key(&e.author_dispute), key(&e.vouch), e.timestamp],
+            )?;
+        }
+        ProgramEvent::AuthorDisputeVouchLinkReleased(e) => {
+            tx.execute(
+                "UPDATE dispute_vouch_links SET settled_at = ?3 WHERE author_dispute = ?1 AND vouch = ?2",
+                params![key(&e.author_dispute), key(&e.vouch), e.timestamp],
+            )?;
+        }
+        ProgramEvent::AuthorDisputeResolved(e) => {
+            tx.execute(
+                "UPDATE disputes SET status = 'resolved', ruling = ?2, author_bond_slashed_amount = ?3,
+                     voucher_slashed_amount = ?4, resolved_at = ?5
+                 WHERE author_dispute = ?1",
+                params![
+                    key(&e.author_dispute),
+                    dispute_ruling(e.ruling),
+                    e.author_bond_slashed_amount as i64,
+                    e.voucher_slashed_amount as i64,
+                    e.timestamp
+                ],
+            )?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn set_author_bond(tx: &Transaction, author_agent_id: &Pubkey, amount: u64) -> Result<()> {
+    tx.execute(
+        "UPDATE agents SET author_bond_lamports = ?2 WHERE agent_id = ?1 AND closed_at IS NULL",
+        params![key(author_agent_id), amount as i64],
+    )?;
+    Ok(())
+}
+
+fn key(pubkey: &Pubkey) -> String {
+    pubkey.to_string()
+}
+
+fn vouch_status(status: VouchStatus) -> &'static str {
+    match status {
+        VouchStatus::Active => "active",
+        VouchStatus::Revoked => "revoked",
+        VouchStatus::Slashed => "slashed",
+    }
+}
+
+fn dispute_reason(reason: AuthorDisputeReason) -> &'static str {
+    match reason {
+        AuthorDisputeReason::MaliciousSkill => "malicious_skill",
+        AuthorDisputeReason::FraudulentClaims => "fraudulent_claims",
+        AuthorDisputeReason::FailedDelivery => "failed_delivery",
+        AuthorDisputeReason::Other => "other",
+    }
+}
+
+fn liability_scope(scope: AuthorDisputeLiabilityScope) -> &'static str {
+    match scope {
+        AuthorDisputeLiabilityScope::AuthorBondOnly => "author_bond_only",
+        AuthorDisputeLiabilityScope::AuthorBondThenVouchers => "author_bond_then_vouchers",
+    }
+}
+
+fn dispute_ruling(ruling: AuthorDisputeRuling) -> &'static str {
+    match ruling {
+        AuthorDisputeRuling::Upheld => "upheld",
+        AuthorDisputeRuling::Dismissed => "dismissed",
+    }
+}
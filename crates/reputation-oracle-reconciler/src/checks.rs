@@ -0,0 +1,336 @@
+//! Invariant checks over a snapshot.
+//!
+//! Agent profile counters are compared against the program's own tally of the
+//! profile's vouch accounts, unremoved listings, and author bond, so a clean report means
+//! `reconcile_agent_profile` would change nothing. Listings must also hold exactly their
+//! unclaimed voucher revenue above rent.
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use reputation_oracle::instructions::agent_profile_counters::AgentProfileCounters;
+use serde::Serialize;
+
+use crate::snapshot::Snapshot;
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub account: String,
+    pub invariant: &'static str,
+    /// Value derived from the accounts the stored field summarizes.
+    pub expected: u64,
+    /// Value stored on chain.
+    pub actual: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub agent_profiles: usize,
+    pub vouches: usize,
+    pub skill_listings: usize,
+    pub author_bonds: usize,
+    pub other_accounts: usize,
+    pub violations: Vec<Violation>,
+    /// Agent IDs whose profile counters drifted, ready for `reconcile_agent_profile`.
+    pub agents_to_reconcile: Vec<String>,
+}
+
+pub fn check(snapshot: &Snapshot) -> Report {
+    let mut tallies: HashMap<Pubkey, AgentProfileCounters> = snapshot
+        .agent_profiles
+        .iter()
+        .map(|profile| (profile.address, AgentProfileCounters::default()))
+        .collect();
+    let profiles_by_agent_id: HashMap<Pubkey, Pubkey> = snapshot
+        .agent_profiles
+        .iter()
+        .map(|profile| (profile.account.agent_id, profile.address))
+        .collect();
+
+    for vouch in &snapshot.vouches {
+        for profile in [vouch.account.voucher, vouch.account.vouchee] {
+            if let Some(tally) = tallies.get_mut(&profile) {
+                tally.add_vouch(&profile, &vouch.account);
+            }
+        }
+    }
+    for listing in &snapshot.skill_listings {
+        if let Some(tally) = profiles_by_agent_id
+            .get(&listing.account.author)
+            .and_then(|profile| tallies.get_mut(profile))
+        {
+            tally.add_skill_listing(&listing.account);
+        }
+    }
+    for bond in &snapshot.author_bonds {
+        if let Some(tally) = profiles_by_agent_id
+            .get(&bond.account.author)
+            .and_then(|profile| tallies.get_mut(profile))
+        {
+            tally.author_bond_lamports = bond.account.amount;
+        }
+    }
+
+    let mut violations = Vec::new();
+    let mut agents_to_reconcile = Vec::new();
+    for profile in &snapshot.agent_profiles {
+        let expected = tallies[&profile.address];
+        let actual = AgentProfileCounters::from_profile(&profile.account);
+        if expected == actual {
+            continue;
+        }
+        agents_to_reconcile.push(profile.account.agent_id.to_string());
+        for (invariant, expected, actual) in counter_fields(&expected, &actual) {
+            if expected != actual {
+                violations.push(Violation {
+                    account: profile.address.to_string(),
+                    invariant,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+
+    let rent = Rent::default();
+    for listing in &snapshot.skill_listings {
+        let above_rent = listing
+            .lamports
+            .saturating_sub(rent.minimum_balance(listing.data_len));
+        if above_rent != listing.account.unclaimed_voucher_revenue {
+            violations.push(Violation {
+                account: listing.address.to_string(),
+                invariant: "skill_listing.unclaimed_voucher_revenue",
+                expected: above_rent,
+                actual: listing.account.unclaimed_voucher_revenue,
+            });
+        }
+    }
+
+    Report {
+        agent_profiles: snapshot.agent_profiles.len(),
+        vouches: snapshot.vouches.len(),
+        skill_listings: snapshot.skill_listings.len(),
+        author_bonds: snapshot.author_bonds.len(),
+        other_accounts: snapshot.other_accounts,
+        violations,
+        agents_to_reconcile,
+    }
+}
+
+fn counter_fields(
+    expected: &AgentProfileCounters,
+    actual: &AgentProfileCounters,
+) -> [(&'static str, u64, u64); 11] {
+    [
+        (
+            "agent_profile.total_vouches_received",
+            expected.total_vouches_received as u64,
+            actual.total_vouches_received as u64,
+        ),
+        (
+            "agent_profile.total_vouches_given",
+            expected.total_vouches_given as u64,
+            actual.total_vouches_given as u64,
+        ),
+        (
+            "agent_profile.total_staked_for",
+            expected.total_staked_for,
+            actual.total_staked_for,
+        ),
+        (
+            "agent_profile.vouched_reputation_total",
+            expected.vouched_reputation_total,
+            actual.vouched_reputation_total,
+        ),
+        (
+            "agent_profile.effective_vouch_weight",
+            expected.effective_vouch_weight,
+            actual.effective_vouch_weight,
+        ),
+        (
+            "agent_profile.low_reputation_vouches_received",
+            expected.low_reputation_vouches_received as u64,
+            actual.low_reputation_vouches_received as u64,
+        ),
+        (
+            "agent_profile.active_skill_listings",
+            expected.active_skill_listings as u64,
+            actual.active_skill_listings as u64,
+        ),
+        (
+            "agent_profile.active_free_skill_listings",
+            expected.active_free_skill_listings as u64,
+            actual.active_free_skill_listings as u64,
+        ),
+        (
+            "agent_profile.author_bond_lamports",
+            expected.author_bond_lamports,
+            actual.author_bond_lamports,
+        ),
+        (
+            "agent_profile.vouch_accounts_received",
+            expected.vouch_accounts_received as u64,
+            actual.vouch_accounts_received as u64,
+        ),
+        (
+            "agent_profile.vouch_accounts_given",
+            expected.vouch_accounts_given as u64,
+            actual.vouch_accounts_given as u64,
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use reputation_oracle::state::{
+        AgentProfile, AuthorBond, SkillListing, SkillStatus, TrustTier, Vouch, VouchStatus,
+    };
+    use reputation_oracle_client::pda::{find_agent_pda, find_author_bond_pda};
+    use serde_json::json;
+
+    fn profile(agent_id: Pubkey) -> AgentProfile {
+        AgentProfile {
+            authority: agent_id,
+            metadata_uri: String::new(),
+            reputation_score: 0,
+            total_vouches_received: 0,
+            total_vouches_given: 0,
+            total_staked_for: 0,
+            author_bond_lamports: 0,
+            active_free_skill_listings: 0,
+            open_author_disputes: 0,
+            registered_at: 1_700_000_000,
+            vouched_reputation_total: 0,
+            trust_depth: 0,
+            trust_depth_updated_at: 0,
+            trust_tier: TrustTier::Unverified,
+            last_upheld_dispute_at: 0,
+            effective_vouch_weight: 0,
+            low_reputation_vouches_received: 0,
+            active_skill_listings: 0,
+            agent_id,
+            delegate: Pubkey::default(),
+            bump: find_agent_pda(&agent_id).1,
+            version: AgentProfile::CURRENT_VERSION,
+            counters_verified: true,
+            claimed_foreign_staked_for: 0,
+            claimed_foreign_vouch_weight: 0,
+            claimed_foreign_reputation_total: 0,
+            vouch_accounts_received: 0,
+            vouch_accounts_given: 0,
+        }
+    }
+
+    fn vouch(voucher: Pubkey, vouchee: Pubkey, stake_amount: u64) -> Vouch {
+        Vouch {
+            voucher,
+            vouchee,
+            stake_amount,
+            created_at: 0,
+            status: VouchStatus::Active,
+            cumulative_revenue: 0,
+            last_payout_at: 0,
+            voucher_reputation_snapshot: 0,
+            effective_weight_bps: 10_000,
+            low_reputation_voucher: false,
+            pending_dispute_links: 0,
+            bump: 0,
+            version: Vouch::CURRENT_VERSION,
+            last_stake_increase_at: 0,
+        }
+    }
+
+    fn listing(author: Pubkey, price_lamports: u64, unclaimed: u64) -> SkillListing {
+        SkillListing {
+            author,
+            skill_uri: String::new(),
+            name: String::new(),
+            description: String::new(),
+            price_lamports,
+            total_downloads: 0,
+            total_revenue: 0,
+            unclaimed_voucher_revenue: unclaimed,
+            created_at: 0,
+            updated_at: 0,
+            status: SkillStatus::Active,
+            bump: 0,
+            version: SkillListing::CURRENT_VERSION,
+        }
+    }
+
+    fn entry<T: AccountSerialize>(address: Pubkey, account: &T, space: usize, extra: u64) -> serde_json::Value {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data.resize(space.max(data.len()), 0);
+        let lamports = Rent::default().minimum_balance(data.len()) + extra;
+        json!({
+            "pubkey": address.to_string(),
+            "account": { "lamports": lamports, "data": [BASE64.encode(&data), "base64"], "owner": "x" }
+        })
+    }
+
+    #[test]
+    fn reports_counter_drift_and_listing_shortfalls() {
+        let author_id = Pubkey::new_unique();
+        let voucher_id = Pubkey::new_unique();
+        let author = find_agent_pda(&author_id).0;
+        let voucher = find_agent_pda(&voucher_id).0;
+
+        let mut author_profile = profile(author_id);
+        author_profile.total_vouches_received = 1;
+        author_profile.total_staked_for = 9_999; // drifted: the vouch stakes 5_000
+        author_profile.effective_vouch_weight = 10_000;
+        author_profile.active_skill_listings = 1;
+        author_profile.active_free_skill_listings = 1;
+        author_profile.vouch_accounts_received = 1;
+        let mut voucher_profile = profile(voucher_id);
+        voucher_profile.total_vouches_given = 1;
+        voucher_profile.vouch_accounts_given = 1;
+        let bond = AuthorBond {
+            author: author_id,
+            amount: 700,
+            created_at: 0,
+            updated_at: 0,
+            bump: 0,
+        };
+        author_profile.author_bond_lamports = 700;
+
+        let dump = json!({ "jsonrpc": "2.0", "id": 1, "result": [
+            entry(author, &author_profile, AgentProfile::LEN, 0),
+            entry(voucher, &voucher_profile, AgentProfile::LEN, 0),
+            entry(Pubkey::new_unique(), &vouch(voucher, author, 5_000), Vouch::LEN, 5_000),
+            entry(Pubkey::new_unique(), &listing(author_id, 0, 0), SkillListing::SPACE, 0),
+            entry(Pubkey::new_unique(), &listing(author_id, 100, 40), SkillListing::SPACE, 30),
+            entry(find_author_bond_pda(&author_id).0, &bond, AuthorBond::LEN, 700),
+        ]});
+        let snapshot = Snapshot::from_json(&dump.to_string(), 1_750_000_000).unwrap();
+        let report = check(&snapshot);
+
+        assert_eq!(report.agent_profiles, 2);
+        assert_eq!(report.agents_to_reconcile, vec![author_id.to_string()]);
+        assert_eq!(
+            report
+                .violations
+                .iter()
+                .map(|violation| (violation.invariant, violation.expected, violation.actual))
+                .collect::<Vec<_>>(),
+            vec![
+                ("agent_profile.total_staked_for", 5_000, 9_999),
+                ("agent_profile.active_skill_listings", 2, 1),
+                ("skill_listing.unclaimed_voucher_revenue", 30, 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_non_base64_dumps() {
+        let dump = json!([{ "pubkey": Pubkey::new_unique().to_string(),
+                            "account": { "lamports": 1, "data": ["abc", "base58"] } }]);
+        assert!(Snapshot::from_json(&dump.to_string(), 0).is_err());
+    }
+}
@@ -0,0 +1,7 @@
+//! Cross-checks the program's denormalized counters against a snapshot of its accounts.
+//!
+//! - [`snapshot`] loads a `getProgramAccounts` dump.
+//! - [`checks`] reports every invariant violation in it.
+
+pub mod checks;
+pub mod snapshot;
@@ -0,0 +1,58 @@
+//! Reports counter drift in a program account dump. Exits non-zero when any invariant
+//! is violated, so it can gate scripts.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use reputation_oracle_reconciler::checks::check;
+use reputation_oracle_reconciler::snapshot::Snapshot;
+
+#[derive(Parser)]
+#[command(name = "reputation-oracle-reconciler", version, about)]
+struct Cli {
+    /// `getProgramAccounts` dump with base64 account data.
+    snapshot: PathBuf,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+    let json = fs::read_to_string(&cli.snapshot)
+        .with_context(|| format!("failed to read {}", cli.snapshot.display()))?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    let report = check(&Snapshot::from_json(&json, now)?);
+
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "checked {} agent profiles, {} vouches, {} skill listings, {} author bonds",
+            report.agent_profiles, report.vouches, report.skill_listings, report.author_bonds
+        );
+        for violation in &report.violations {
+            println!(
+                "{} {}: expected {}, found {}",
+                violation.account, violation.invariant, violation.expected, violation.actual
+            );
+        }
+        for agent_id in &report.agents_to_reconcile {
+            println!("reconcile with: reputation-oracle-cli agent reconcile {agent_id}");
+        }
+    }
+
+    Ok(if report.violations.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
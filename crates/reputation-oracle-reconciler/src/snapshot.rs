@@ -0,0 +1,110 @@
+//! Program account snapshots.
+//!
+//! A snapshot is the `getProgramAccounts` result for the program with base64 data:
+//! either the bare `[{ "pubkey", "account": { "lamports", "data": [b64, "base64"] } }]`
+//! list or the whole JSON-RPC response around it. `reputation-oracle-cli snapshot`
+//! writes the bare list.
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::{AccountDeserialize, Pubkey};
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reputation_oracle::state::{AgentProfile, AuthorBond, SkillListing, Vouch};
+use reputation_oracle_client::accounts::{decode_agent_profile, decode_versioned};
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct RawAccount {
+    pubkey: String,
+    account: RawAccountInfo,
+}
+
+#[derive(Deserialize)]
+struct RawAccountInfo {
+    lamports: u64,
+    data: (String, String),
+}
+
+pub struct Decoded<T> {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub data_len: usize,
+    pub account: T,
+}
+
+/// The account types the reconciler checks, decoded. Other program accounts are only
+/// counted.
+#[derive(Default)]
+pub struct Snapshot {
+    pub agent_profiles: Vec<Decoded<AgentProfile>>,
+    pub vouches: Vec<Decoded<Vouch>>,
+    pub skill_listings: Vec<Decoded<SkillListing>>,
+    pub author_bonds: Vec<Decoded<AuthorBond>>,
+    pub other_accounts: usize,
+}
+
+impl Snapshot {
+    /// Parses a dump, decoding accounts not yet migrated as their current layout. `now`
+    /// stands in for implausible legacy `registered_at` values while decoding old agent
+    /// profile layouts; counters do not depend on it.
+    pub fn from_json(json: &str, now: i64) -> Result<Self> {
+        let value: Value = serde_json::from_str(json).context("snapshot is not valid JSON")?;
+        let list = match value {
+            Value::Object(mut response) => response
+                .remove("result")
+                .ok_or_else(|| anyhow!("snapshot object has no `result` list"))?,
+            list => list,
+        };
+        let raw: Vec<RawAccount> =
+            serde_json::from_value(list).context("snapshot is not a getProgramAccounts list")?;
+
+        let mut snapshot = Snapshot::default();
+        for entry in raw {
+            let address = Pubkey::from_str(&entry.pubkey)
+                .map_err(|err| anyhow!("invalid pubkey {}: {err}", entry.pubkey))?;
+            let (data, encoding) = &entry.account.data;
+            if encoding != "base64" {
+                bail!("account {address} uses {encoding} encoding; dump with base64");
+            }
+            let data = BASE64
+                .decode(data)
+                .with_context(|| format!("account {address} has invalid base64 data"))?;
+            snapshot
+                .insert(address, entry.account.lamports, &data, now)
+                .with_context(|| format!("failed to decode account {address}"))?;
+        }
+        Ok(snapshot)
+    }
+
+    fn insert(&mut self, address: Pubkey, lamports: u64, data: &[u8], now: i64) -> Result<()> {
+        fn decoded<T>(address: Pubkey, lamports: u64, data: &[u8], account: T) -> Decoded<T> {
+            Decoded {
+                address,
+                lamports,
+                data_len: data.len(),
+                account,
+            }
+        }
+
+        if data.starts_with(AgentProfile::DISCRIMINATOR) {
+            let profile = decode_agent_profile(data, now)?;
+            self.agent_profiles.push(decoded(address, lamports, data, profile));
+        } else if data.starts_with(Vouch::DISCRIMINATOR) {
+            let vouch: Vouch = decode_versioned(data, now)?;
+            self.vouches.push(decoded(address, lamports, data, vouch));
+        } else if data.starts_with(SkillListing::DISCRIMINATOR) {
+            let listing: SkillListing = decode_versioned(data, now)?;
+            self.skill_listings.push(decoded(address, lamports, data, listing));
+        } else if data.starts_with(AuthorBond::DISCRIMINATOR) {
+            let bond = AuthorBond::try_deserialize(&mut &data[..])?;
+            self.author_bonds.push(decoded(address, lamports, data, bond));
+        } else {
+            self.other_accounts += 1;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+//! Minimal reader for Solana Agent Registry identities.
+//!
+//! Registry records are Metaplex Core assets, so a `solana:` canonical agent ID names
+//! the registry program and the core asset pubkey. Only the fields needed to prove
+//! control are decoded: the base asset owner and the collection the asset belongs to.
+//! Plugins are never parsed, so transfer delegates do not count as controllers.
+
+use anchor_lang::prelude::*;
+
+use crate::bytes::Reader;
+
+pub const MPL_CORE_PROGRAM_ID: Pubkey = pubkey!("CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7d");
+
+const KEY_ASSET_V1: u8 = 1;
+
+const UPDATE_AUTHORITY_NONE: u8 = 0;
+const UPDATE_AUTHORITY_ADDRESS: u8 = 1;
+const UPDATE_AUTHORITY_COLLECTION: u8 = 2;
+
+/// Control facts read from a Metaplex Core asset account.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoreAssetControl {
+    pub owner: Pubkey,
+    pub collection: Option<Pubkey>,
+}
+
+impl CoreAssetControl {
+    pub fn is_controlled_by(&self, authority: &Pubkey) -> bool {
+        self.owner == *authority
+    }
+
+    pub fn is_in_collection(&self, collection: &Pubkey) -> bool {
+        self.collection.as_ref() == Some(collection)
+    }
+}
+
+/// Decodes the base fields of an `AssetV1`. Returns `None` for anything that is not a
+/// well-formed core asset.
+pub fn parse_core_asset(data: &[u8]) -> Option<CoreAssetControl> {
+    let mut reader = Reader::at(data, 0);
+    if reader.u8()? != KEY_ASSET_V1 {
+        return None;
+    }
+    let owner = reader.pubkey()?;
+    let collection = match reader.u8()? {
+        UPDATE_AUTHORITY_NONE => None,
+        UPDATE_AUTHORITY_ADDRESS => {
+            reader.pubkey()?;
+            None
+        }
+        UPDATE_AUTHORITY_COLLECTION => Some(reader.pubkey()?),
+        _ => return None,
+    };
+    reader.borsh_bytes()?; // name
+    reader.borsh_bytes()?; // uri
+    match reader.u8()? {
+        0 => {}
+        1 => {
+            reader.u64_le()?;
+        }
+        _ => return None,
+    }
+
+    Some(CoreAssetControl { owner, collection })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(owner: Pubkey, update_authority: (u8, Option<Pubkey>), seq: Option<u64>) -> Vec<u8> {
+        let mut data = vec![KEY_ASSET_V1];
+        data.extend_from_slice(owner.as_ref());
+        data.push(update_authority.0);
+        if let Some(address) = update_authority.1 {
+            data.extend_from_slice(address.as_ref());
+        }
+        for value in ["Agent", "https://agent.example/card.json"] {
+            data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            data.extend_from_slice(value.as_bytes());
+        }
+        match seq {
+            Some(seq) => {
+                data.push(1);
+                data.extend_from_slice(&seq.to_le_bytes());
+            }
+            None => data.push(0),
+        }
+        data
+    }
+
+    #[test]
+    fn reads_owner_and_collection() {
+        let owner = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let data = asset(owner, (UPDATE_AUTHORITY_COLLECTION, Some(collection)), Some(7));
+
+        let control = parse_core_asset(&data).unwrap();
+        assert_eq!(control.owner, owner);
+        assert!(control.is_controlled_by(&owner));
+        assert!(!control.is_controlled_by(&collection));
+        assert!(control.is_in_collection(&collection));
+        assert!(!control.is_in_collection(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn address_update_authority_is_neither_collection_nor_controller() {
+        let owner = Pubkey::new_unique();
+        let update_authority = Pubkey::new_unique();
+        let data = asset(owner, (UPDATE_AUTHORITY_ADDRESS, Some(update_authority)), None);
+
+        let control = parse_core_asset(&data).unwrap();
+        assert_eq!(control.collection, None);
+        assert!(!control.is_in_collection(&update_authority));
+        assert!(!control.is_controlled_by(&update_authority));
+    }
+
+    #[test]
+    fn ignores_trailing_plugin_data() {
+        let owner = Pubkey::new_unique();
+        let mut data = asset(owner, (UPDATE_AUTHORITY_NONE, None), None);
+        data.extend_from_slice(&[3, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+
+        let control = parse_core_asset(&data).unwrap();
+        assert_eq!(control.owner, owner);
+        assert_eq!(control.collection, None);
+    }
+
+    #[test]
+    fn rejects_non_asset_and_truncated_data() {
+        let owner = Pubkey::new_unique();
+        let mut wrong_key = asset(owner, (UPDATE_AUTHORITY_NONE, None), None);
+        wrong_key[0] = 5; // CollectionV1
+        assert!(parse_core_asset(&wrong_key).is_none());
+
+        let full = asset(owner, (UPDATE_AUTHORITY_NONE, None), Some(1));
+        assert!(parse_core_asset(&full[..full.len() - 3]).is_none());
+        assert!(parse_core_asset(&[]).is_none());
+    }
+}
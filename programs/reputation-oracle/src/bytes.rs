@@ -0,0 +1,107 @@
+//! Bounds-checked cursor for decoding foreign account layouts by hand.
+//!
+//! Metaplex Core and Wormhole accounts are borsh (little-endian), while Wormhole
+//! payloads are big-endian, so both flavours live here. Layouts of our own accounts
+//! that Borsh can no longer decode are read with it too.
+
+use anchor_lang::prelude::*;
+
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn at(data: &'a [u8], offset: usize) -> Self {
+        Self { data, offset }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    pub(crate) fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.offset.checked_add(len)?;
+        let bytes = self.data.get(self.offset..end)?;
+        self.offset = end;
+        Some(bytes)
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N).map(|bytes| bytes.try_into().unwrap())
+    }
+
+    pub(crate) fn u8(&mut self) -> Option<u8> {
+        self.array::<1>().map(|bytes| bytes[0])
+    }
+
+    pub(crate) fn u16_le(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    pub(crate) fn u32_le(&mut self) -> Option<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    pub(crate) fn u64_le(&mut self) -> Option<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    pub(crate) fn i64_le(&mut self) -> Option<i64> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    pub(crate) fn u16_be(&mut self) -> Option<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(crate) fn u32_be(&mut self) -> Option<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(crate) fn u64_be(&mut self) -> Option<u64> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    pub(crate) fn pubkey(&mut self) -> Option<Pubkey> {
+        self.array().map(Pubkey::new_from_array)
+    }
+
+    /// Borsh `Vec<u8>` / `String`: u32 little-endian length followed by the bytes.
+    pub(crate) fn borsh_bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32_le()? as usize;
+        self.take(len)
+    }
+
+    /// Runs `read` against the cursor for decoders that return `Result`, reporting a
+    /// short read as the caller's layout error.
+    pub(crate) fn decode<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Option<T>,
+        invalid_layout: fn() -> Error,
+    ) -> Result<T> {
+        read(self).ok_or_else(invalid_layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::account_migration::AccountMigrationError;
+
+    fn invalid_layout() -> Error {
+        error!(AccountMigrationError::InvalidAccountLayout)
+    }
+
+    #[test]
+    fn decode_reports_short_reads_as_the_callers_error() {
+        let mut reader = Reader::at(&[1, 0, 0, 0, 9], 0);
+        assert_eq!(reader.decode(Reader::u32_le, invalid_layout).unwrap(), 1);
+        assert_eq!(reader.decode(Reader::u8, invalid_layout).unwrap(), 9);
+        assert!(reader.decode(Reader::u8, invalid_layout).is_err());
+        assert!(Reader::at(&[0; 4], 0).decode(Reader::u64_le, invalid_layout).is_err());
+        assert!(Reader::at(&[0; 4], usize::MAX)
+            .decode(|reader| reader.take(2), invalid_layout)
+            .is_err());
+    }
+}
@@ -1,16 +1,46 @@
 use anchor_lang::prelude::*;
 
+use crate::instructions::account_migration::MigratableAccountKind;
+use crate::state::{
+    AuthorDisputeLiabilityScope, AuthorDisputeReason, AuthorDisputeRuling, ReputationConfig,
+    VouchStatus,
+};
+
+/// Bumped whenever an event's field layout changes. Every event leads with
+/// `event_version` and the deployment's `chain_context_id`, a compact hash of its CAIP-2
+/// chain ID, so indexers can route and decode logs from several deployments without
+/// guessing. The events that set the chain context also carry it in full.
+pub const EVENT_VERSION: u8 = 1;
+
 #[event]
 pub struct VouchCreated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub vouch: Pubkey,
     pub voucher: Pubkey,
     pub vouchee: Pubkey,
     pub stake_amount: u64,
+    pub total_stake_amount: u64,
+    pub effective_weight_bps: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchStakeDecreased {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub vouch: Pubkey,
+    pub voucher: Pubkey,
+    pub vouchee: Pubkey,
+    pub amount: u64,
+    pub total_stake_amount: u64,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct VouchRevoked {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub vouch: Pubkey,
     pub voucher: Pubkey,
     pub vouchee: Pubkey,
@@ -18,8 +48,53 @@ pub struct VouchRevoked {
     pub timestamp: i64,
 }
 
+/// Final snapshot of a vouch relationship, emitted right before the account is
+/// closed so indexers keep its revenue history after the PDA disappears.
+#[event]
+pub struct VouchClosed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub vouch: Pubkey,
+    pub voucher: Pubkey,
+    pub vouchee: Pubkey,
+    pub final_status: VouchStatus,
+    pub cumulative_revenue: u64,
+    pub created_at: i64,
+    pub last_payout_at: i64,
+    pub rent_refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlashedVouchRemainderWithdrawn {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub vouch: Pubkey,
+    pub voucher: Pubkey,
+    pub vouchee: Pubkey,
+    pub stake_returned: u64,
+    pub timestamp: i64,
+}
+
+/// One backing vouch settled by an upheld author dispute. Emitted even when the
+/// prorated amount rounds to zero, since the vouch still leaves the author's backing.
+#[event]
+pub struct VouchSlashed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub author_dispute: Pubkey,
+    pub vouch: Pubkey,
+    pub voucher: Pubkey,
+    pub vouchee: Pubkey,
+    pub amount: u64,
+    pub remaining_stake_amount: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AuthorBondDeposited {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub author_bond: Pubkey,
     pub author: Pubkey,
     pub amount: u64,
@@ -29,6 +104,8 @@ pub struct AuthorBondDeposited {
 
 #[event]
 pub struct AuthorBondWithdrawn {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub author_bond: Pubkey,
     pub author: Pubkey,
     pub amount: u64,
@@ -38,6 +115,8 @@ pub struct AuthorBondWithdrawn {
 
 #[event]
 pub struct AuthorBondSlashed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub author_bond: Pubkey,
     pub author: Pubkey,
     pub amount: u64,
@@ -47,11 +126,13 @@ pub struct AuthorBondSlashed {
 
 #[event]
 pub struct AuthorDisputeOpened {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub author_dispute: Pubkey,
     pub author: Pubkey,
     pub challenger: Pubkey,
-    pub reason: String,
-    pub liability_scope: String,
+    pub reason: AuthorDisputeReason,
+    pub liability_scope: AuthorDisputeLiabilityScope,
     pub skill_listing: Pubkey,
     pub skill_price_lamports_snapshot: u64,
     pub purchase: Option<Pubkey>,
@@ -62,10 +143,12 @@ pub struct AuthorDisputeOpened {
 
 #[event]
 pub struct AuthorDisputeResolved {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub author_dispute: Pubkey,
     pub author: Pubkey,
-    pub ruling: String,
-    pub liability_scope: String,
+    pub ruling: AuthorDisputeRuling,
+    pub liability_scope: AuthorDisputeLiabilityScope,
     pub linked_vouch_count: u32,
     pub author_bond_slashed_amount: u64,
     pub voucher_slashed_amount: u64,
@@ -75,13 +158,27 @@ pub struct AuthorDisputeResolved {
 
 #[event]
 pub struct AuthorDisputeVouchLinked {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub author_dispute: Pubkey,
+    pub vouch: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorDisputeVouchLinkReleased {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub author_dispute: Pubkey,
     pub vouch: Pubkey,
+    pub pending_dispute_links: u32,
     pub timestamp: i64,
 }
 
 #[event]
 pub struct SkillListingCreated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub skill_listing: Pubkey,
     pub author: Pubkey,
     pub name: String,
@@ -91,6 +188,8 @@ pub struct SkillListingCreated {
 
 #[event]
 pub struct SkillListingUpdated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub skill_listing: Pubkey,
     pub author: Pubkey,
     pub name: String,
@@ -98,8 +197,33 @@ pub struct SkillListingUpdated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct SkillListingRemoved {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub skill_listing: Pubkey,
+    pub author: Pubkey,
+    pub operator: Pubkey,
+    pub price_lamports: u64,
+    pub active_skill_listings: u32,
+    pub active_free_skill_listings: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SkillListingClosed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub skill_listing: Pubkey,
+    pub author: Pubkey,
+    pub rent_refunded: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct SkillPurchased {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub purchase: Pubkey,
     pub skill_listing: Pubkey,
     pub buyer: Pubkey,
@@ -111,9 +235,386 @@ pub struct SkillPurchased {
 
 #[event]
 pub struct RevenueClaimed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
     pub skill_listing: Pubkey,
     pub vouch: Pubkey,
     pub voucher: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct TrustDepthRefreshed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub previous_trust_depth: u8,
+    pub trust_depth: u8,
+    pub reachable_agent_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PurchaseClosed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub purchase: Pubkey,
+    pub buyer: Pubkey,
+    pub skill_listing: Pubkey,
+    pub price_paid: u64,
+    pub purchased_at: i64,
+    pub rent_refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentRegistered {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub agent_id: Pubkey,
+    pub authority: Pubkey,
+    pub metadata_uri: String,
+    /// False when an existing profile re-registered to update its metadata URI.
+    pub is_new: bool,
+    pub registered_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentProfileMigrated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub agent_id: Pubkey,
+    /// Agent authority for self-service migrations, config authority for admin ones.
+    pub migrated_by: Pubkey,
+    pub admin_migration: bool,
+    pub previous_data_len: u32,
+    pub previous_metadata_uri: String,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentRegisteredAtRepaired {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub previous_registered_at: i64,
+    pub registered_at: i64,
+    pub timestamp: i64,
+}
+
+/// Counters after a recompute from the agent's vouch and listing accounts. `changed` is
+/// false when the stored counters already matched.
+#[event]
+pub struct AgentProfileCountersRecomputed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub recomputed_by: Pubkey,
+    pub admin_reconcile: bool,
+    pub changed: bool,
+    pub total_vouches_received: u32,
+    pub total_vouches_given: u32,
+    pub total_staked_for: u64,
+    pub author_bond_lamports: u64,
+    pub active_skill_listings: u32,
+    pub active_free_skill_listings: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted whenever a recompute moves `reputation_score`, for agent profiles and
+/// foreign agent records alike, so clients never have to rerun the formula.
+#[event]
+pub struct ReputationScoreChanged {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub previous_score: u64,
+    pub reputation_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentProfileClosed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub authority: Pubkey,
+    pub reputation_score: u64,
+    pub registered_at: i64,
+    pub last_upheld_dispute_at: i64,
+    pub rent_refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentAuthorityRotated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub agent_id: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentDelegateUpdated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub previous_delegate: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentSessionKeyCreated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub session_key: Pubkey,
+    pub scopes: u8,
+    pub spend_cap_lamports: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentSessionKeyRevoked {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub session_key: Pubkey,
+    pub spent_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionKeySpend {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub session_key: Pubkey,
+    pub amount: u64,
+    pub spent_lamports: u64,
+    pub spend_cap_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentIdentityBound {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub binding: Pubkey,
+    pub canonical_agent_id: String,
+    pub agent_chain_context: String,
+    pub raw_upstream_label: String,
+    pub registry_asset: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentIdentityAttested {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub binding: Pubkey,
+    pub canonical_agent_id: String,
+    pub attested_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentIdentityReassigned {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub binding: Pubkey,
+    pub canonical_agent_id: String,
+    pub previous_agent: Pubkey,
+    pub agent: Pubkey,
+    pub reassigned_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForeignAgentRecordCreated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub record: Pubkey,
+    pub canonical_agent_id: String,
+    pub agent_chain_context: String,
+    pub created_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForeignAgentRecordClaimed {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub record: Pubkey,
+    pub agent: Pubkey,
+    pub binding: Pubkey,
+    pub canonical_agent_id: String,
+    pub total_vouches_received: u32,
+    pub total_staked_for: u64,
+    pub reputation_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WormholeEmitterRegistered {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub emitter: Pubkey,
+    pub chain: u16,
+    pub address: [u8; 32],
+    pub emitter_chain_context: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ForeignReputationAttested {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub attestation: Pubkey,
+    pub canonical_agent_id: String,
+    pub agent_chain_context: String,
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    pub reputation_score: u64,
+    pub total_vouches_received: u32,
+    pub feedback_count: u32,
+    pub observed_at: i64,
+    pub timestamp: i64,
+}
+
+/// Outbound reputation summary. `payload` is the encoded `ReputationSummaryPayload`
+/// that relayers post through the Wormhole core bridge unchanged.
+#[event]
+pub struct ReputationMessagePublished {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub agent: Pubkey,
+    pub canonical_agent_id: String,
+    pub emitter_chain: u16,
+    pub emitter: Pubkey,
+    pub sequence: u64,
+    pub message: Pubkey,
+    pub payload: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigInitialized {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub config: Pubkey,
+    pub chain_context: String,
+    pub authority: Pubkey,
+    pub min_stake: u64,
+    pub dispute_bond: u64,
+    pub min_author_bond_for_free_listing: u64,
+    pub slash_percentage: u8,
+    pub cooldown_period: i64,
+    pub timestamp: i64,
+}
+
+/// An account rewritten in its current layout by `migrate_accounts`. Accounts that were
+/// already current emit nothing.
+#[event]
+pub struct AccountMigrated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub account: Pubkey,
+    pub kind: MigratableAccountKind,
+    pub migrated_by: Pubkey,
+    pub previous_data_len: u32,
+    pub previous_version: u8,
+    pub data_len: u32,
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub config: Pubkey,
+    pub chain_context: String,
+    pub authority: Pubkey,
+    pub previous_data_len: u32,
+    pub data_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrustThresholdsUpdated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub config: Pubkey,
+    pub verified_min_vouchers: u32,
+    pub verified_min_total_stake: u64,
+    pub verified_min_author_bond: u64,
+    pub verified_dispute_lookback: i64,
+    pub verified_min_account_age: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VouchWeightingUpdated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub config: Pubkey,
+    pub min_voucher_age: i64,
+    pub min_voucher_reputation: u64,
+    pub full_weight_voucher_reputation: u64,
+    pub timestamp: i64,
+}
+
+/// `chain_context_id` already carries the new value; the old one is kept for routing.
+#[event]
+pub struct ChainContextUpdated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub config: Pubkey,
+    pub chain_context: String,
+    pub previous_chain_context_id: u32,
+    pub previous_chain_context: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentRegistryUpdated {
+    pub event_version: u8,
+    pub chain_context_id: u32,
+    pub config: Pubkey,
+    pub agent_registry_program: Pubkey,
+    pub agent_registry_collection: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emits `ReputationScoreChanged` if a recompute actually moved the score.
+pub(crate) fn emit_reputation_score_changed(
+    config: &ReputationConfig,
+    agent: Pubkey,
+    previous_score: u64,
+    reputation_score: u64,
+) -> Result<()> {
+    if previous_score != reputation_score {
+        emit!(ReputationScoreChanged {
+            event_version: EVENT_VERSION,
+            chain_context_id: config.chain_context_id,
+            agent,
+            previous_score,
+            reputation_score,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//! CAIP-2 chain IDs and AgentVouch canonical agent IDs.
+//!
+//! A canonical agent ID has the shape `<caip2-chain-id>:<registryOrProgram>#<recordId>`,
+//! for example `solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp:<agentRegistryProgram>#<coreAsset>`
+//! or `eip155:8453:<identityRegistry>#<tokenId>`. Legacy aliases such as `solana` or
+//! `solana:mainnet` are normalized off-chain; the program only accepts strict CAIP-2.
+
+/// CAIP-2 namespace whose registry records can be verified directly on-chain.
+pub const SOLANA_NAMESPACE: &str = "solana";
+
+pub const MAX_CAIP2_NAMESPACE_LEN: usize = 8;
+pub const MIN_CAIP2_NAMESPACE_LEN: usize = 3;
+pub const MAX_CAIP2_REFERENCE_LEN: usize = 32;
+pub const MAX_REGISTRY_LEN: usize = 64;
+pub const MAX_RECORD_ID_LEN: usize = 64;
+pub const MAX_CAIP2_CHAIN_ID_LEN: usize = MAX_CAIP2_NAMESPACE_LEN + 1 + MAX_CAIP2_REFERENCE_LEN;
+pub const MAX_CANONICAL_AGENT_ID_LEN: usize =
+    MAX_CAIP2_CHAIN_ID_LEN + 1 + MAX_REGISTRY_LEN + 1 + MAX_RECORD_ID_LEN;
+
+/// Borrowed view of a validated canonical agent ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CanonicalAgentId<'a> {
+    pub chain_id: &'a str,
+    pub namespace: &'a str,
+    pub reference: &'a str,
+    pub registry: &'a str,
+    pub record_id: &'a str,
+}
+
+fn is_namespace_char(c: char) -> bool {
+    c == '-' || c.is_ascii_lowercase() || c.is_ascii_digit()
+}
+
+fn is_reference_char(c: char) -> bool {
+    c == '-' || c == '_' || c.is_ascii_alphanumeric()
+}
+
+fn is_segment_char(c: char) -> bool {
+    c == '-' || c == '_' || c == '.' || c.is_ascii_alphanumeric()
+}
+
+fn is_valid_segment(value: &str, max_len: usize, valid_char: fn(char) -> bool) -> bool {
+    !value.is_empty() && value.len() <= max_len && value.chars().all(valid_char)
+}
+
+/// Returns `(namespace, reference)` when `chain_id` is a strict CAIP-2 identifier.
+pub fn parse_caip2_chain_id(chain_id: &str) -> Option<(&str, &str)> {
+    let (namespace, reference) = chain_id.split_once(':')?;
+    let namespace_ok = namespace.len() >= MIN_CAIP2_NAMESPACE_LEN
+        && is_valid_segment(namespace, MAX_CAIP2_NAMESPACE_LEN, is_namespace_char);
+    let reference_ok = is_valid_segment(reference, MAX_CAIP2_REFERENCE_LEN, is_reference_char);
+    (namespace_ok && reference_ok).then_some((namespace, reference))
+}
+
+pub fn is_valid_caip2_chain_id(chain_id: &str) -> bool {
+    parse_caip2_chain_id(chain_id).is_some()
+}
+
+/// A deployment's own chain context must be a strict `solana:` CAIP-2 chain ID.
+pub fn is_solana_caip2_chain_id(chain_id: &str) -> bool {
+    matches!(parse_caip2_chain_id(chain_id), Some((SOLANA_NAMESPACE, _)))
+}
+
+pub fn parse_canonical_agent_id(value: &str) -> Option<CanonicalAgentId<'_>> {
+    if value.len() > MAX_CANONICAL_AGENT_ID_LEN {
+        return None;
+    }
+
+    let (locator, record_id) = value.split_once('#')?;
+    let (chain_id, registry) = locator.rsplit_once(':')?;
+    let (namespace, reference) = parse_caip2_chain_id(chain_id)?;
+    if !is_valid_segment(registry, MAX_REGISTRY_LEN, is_segment_char)
+        || !is_valid_segment(record_id, MAX_RECORD_ID_LEN, is_segment_char)
+    {
+        return None;
+    }
+
+    Some(CanonicalAgentId {
+        chain_id,
+        namespace,
+        reference,
+        registry,
+        record_id,
+    })
+}
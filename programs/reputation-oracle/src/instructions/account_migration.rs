@@ -0,0 +1,432 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::bytes::Reader;
+use crate::instructions::agent_profile_migration::decode_agent_profile;
+use crate::instructions::migrate_config::{decode_reputation_config, MigrateConfigError};
+use crate::state::{
+    AgentProfile, AuthorDispute, ReputationConfig, SkillListing, Vouch, VouchStatus,
+    BPS_DENOMINATOR,
+};
+
+/// Account types `migrate_accounts` can rewrite in their current layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MigratableAccountKind {
+    ReputationConfig,
+    AgentProfile,
+    Vouch,
+    SkillListing,
+    AuthorDispute,
+}
+
+impl MigratableAccountKind {
+    /// Identifies an account by its discriminator alone; every layout of a type shares it.
+    pub fn from_data(raw: &[u8]) -> Option<Self> {
+        if raw.starts_with(ReputationConfig::DISCRIMINATOR) {
+            Some(Self::ReputationConfig)
+        } else if raw.starts_with(AgentProfile::DISCRIMINATOR) {
+            Some(Self::AgentProfile)
+        } else if raw.starts_with(Vouch::DISCRIMINATOR) {
+            Some(Self::Vouch)
+        } else if raw.starts_with(SkillListing::DISCRIMINATOR) {
+            Some(Self::SkillListing)
+        } else if raw.starts_with(AuthorDispute::DISCRIMINATOR) {
+            Some(Self::AuthorDispute)
+        } else {
+            None
+        }
+    }
+}
+
+/// An account type whose older layouts can be decoded and rewritten in place.
+///
+/// New fields are appended after `version`, so the layout a type has shipped is picked
+/// by data length for layouts that predate versioning and by the stored `version`
+/// after that. `upgrade_from` fills fields a version lacked where zero is not the
+/// right default.
+pub trait MigratableAccount: AccountSerialize + AccountDeserialize + Discriminator + Sized {
+    const KIND: MigratableAccountKind;
+    const LAYOUT_LEN: usize;
+    const LAYOUT_VERSION: u8;
+
+    fn version(&self) -> u8;
+
+    fn set_version(&mut self, version: u8);
+
+    fn upgrade_from(&mut self, _version: u8) {}
+
+    /// Decodes any shipped layout into the current one, returning the stored layout
+    /// version alongside (0 for every layout from before versioning). `now` stands in
+    /// for timestamps an old layout could not record, as a migration at that time would.
+    fn parse_layout(raw: &[u8], now: i64) -> Result<(Self, u8)>;
+}
+
+/// Decodes a layout that only ever grew by appending fields. The data is zero-extended
+/// to the current length, so fields an older layout lacked read as zero and an account
+/// written before versioning reads as version 0.
+pub fn decode_appended_layout<T: MigratableAccount>(raw: &[u8]) -> Result<(T, u8)> {
+    require!(
+        raw.starts_with(T::DISCRIMINATOR),
+        AccountMigrationError::InvalidAccountDiscriminator
+    );
+    require!(
+        raw.len() <= T::LAYOUT_LEN,
+        AccountMigrationError::UnsupportedAccountLayout
+    );
+
+    let mut data = raw.to_vec();
+    data.resize(T::LAYOUT_LEN, 0);
+    let mut account = T::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(AccountMigrationError::InvalidAccountLayout))?;
+    let version = account.version();
+    require!(
+        version <= T::LAYOUT_VERSION,
+        AccountMigrationError::UnsupportedAccountVersion
+    );
+    account.upgrade_from(version);
+    account.set_version(T::LAYOUT_VERSION);
+    Ok((account, version))
+}
+
+/// True when the account is stored at the current length and version.
+pub fn is_current_layout<T: MigratableAccount>(raw: &[u8]) -> bool {
+    raw.len() == T::LAYOUT_LEN
+        && T::try_deserialize(&mut &raw[..])
+            .map(|account| account.version() == T::LAYOUT_VERSION)
+            .unwrap_or(false)
+}
+
+impl MigratableAccount for ReputationConfig {
+    const KIND: MigratableAccountKind = MigratableAccountKind::ReputationConfig;
+    const LAYOUT_LEN: usize = ReputationConfig::LEN;
+    const LAYOUT_VERSION: u8 = ReputationConfig::CURRENT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn parse_layout(raw: &[u8], _now: i64) -> Result<(Self, u8)> {
+        let (_, bump) = Pubkey::find_program_address(&[b"config"], &crate::ID);
+        let parsed = decode_reputation_config(raw, bump)?;
+        require!(
+            parsed.chain_context.is_some(),
+            MigrateConfigError::MissingChainContext
+        );
+        let version = parsed.version;
+        Ok((parsed.into_reputation_config(), version))
+    }
+}
+
+impl MigratableAccount for AgentProfile {
+    const KIND: MigratableAccountKind = MigratableAccountKind::AgentProfile;
+    const LAYOUT_LEN: usize = AgentProfile::LEN;
+    const LAYOUT_VERSION: u8 = AgentProfile::CURRENT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn parse_layout(raw: &[u8], now: i64) -> Result<(Self, u8)> {
+        let parsed = decode_agent_profile(raw, now)?;
+        let (_, bump) =
+            Pubkey::find_program_address(&[b"agent", parsed.agent_id.as_ref()], &crate::ID);
+        let version = parsed.version;
+        Ok((parsed.into_agent_profile(bump), version))
+    }
+}
+
+impl MigratableAccount for Vouch {
+    const KIND: MigratableAccountKind = MigratableAccountKind::Vouch;
+    const LAYOUT_LEN: usize = Vouch::LEN;
+    const LAYOUT_VERSION: u8 = Vouch::CURRENT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn parse_layout(raw: &[u8], _now: i64) -> Result<(Self, u8)> {
+        if raw.len() == BASELINE_VOUCH_LEN {
+            return Ok((parse_baseline_vouch(raw)?, 0));
+        }
+        decode_appended_layout(raw)
+    }
+}
+
+/// The `Vouch` layout shipped before sybil weighting and versioning, ending at `bump`.
+const BASELINE_VOUCH_LEN: usize = 106;
+
+/// Reads a baseline vouch field by field. Zero-extending it would weigh it at nothing;
+/// baseline vouches predate weighting and count at full weight, matching the weight
+/// `agent_profile_migration` grandfathers onto the vouchee's profile.
+fn parse_baseline_vouch(raw: &[u8]) -> Result<Vouch> {
+    require!(
+        raw.starts_with(Vouch::DISCRIMINATOR),
+        AccountMigrationError::InvalidAccountDiscriminator
+    );
+    Reader::at(raw, 8).decode(
+        |reader| {
+            let voucher = reader.pubkey()?;
+            let vouchee = reader.pubkey()?;
+            let stake_amount = reader.u64_le()?;
+            let created_at = reader.i64_le()?;
+            let status = VouchStatus::try_from_slice(reader.take(1)?).ok()?;
+
+            Some(Vouch {
+                voucher,
+                vouchee,
+                stake_amount,
+                created_at,
+                status,
+                cumulative_revenue: reader.u64_le()?,
+                last_payout_at: reader.i64_le()?,
+                bump: reader.u8()?,
+                voucher_reputation_snapshot: 0,
+                effective_weight_bps: BPS_DENOMINATOR as u32,
+                low_reputation_voucher: false,
+                pending_dispute_links: 0,
+                version: Vouch::CURRENT_VERSION,
+                // Baseline top-ups left no timestamp, so the cooldown falls back to creation.
+                last_stake_increase_at: created_at,
+            })
+        },
+        || error!(AccountMigrationError::InvalidAccountLayout),
+    )
+}
+
+impl MigratableAccount for SkillListing {
+    const KIND: MigratableAccountKind = MigratableAccountKind::SkillListing;
+    const LAYOUT_LEN: usize = SkillListing::SPACE;
+    const LAYOUT_VERSION: u8 = SkillListing::CURRENT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn parse_layout(raw: &[u8], _now: i64) -> Result<(Self, u8)> {
+        decode_appended_layout(raw)
+    }
+}
+
+impl MigratableAccount for AuthorDispute {
+    const KIND: MigratableAccountKind = MigratableAccountKind::AuthorDispute;
+    const LAYOUT_LEN: usize = AuthorDispute::LEN;
+    const LAYOUT_VERSION: u8 = AuthorDispute::CURRENT_VERSION;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    fn parse_layout(raw: &[u8], _now: i64) -> Result<(Self, u8)> {
+        decode_appended_layout(raw)
+    }
+}
+
+/// Reallocates `account` to `len`, topping its rent up from `payer` or refunding the
+/// surplus to it.
+pub(crate) fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    len: usize,
+) -> Result<()> {
+    if account.data_len() == len {
+        return Ok(());
+    }
+
+    account.resize(len)?;
+
+    let target_rent = Rent::get()?.minimum_balance(len);
+    let current_lamports = account.lamports();
+
+    if target_rent > current_lamports {
+        let cpi = system_program::Transfer {
+            from: payer.clone(),
+            to: account.clone(),
+        };
+        system_program::transfer(
+            CpiContext::new(system_program.clone(), cpi),
+            target_rent - current_lamports,
+        )?;
+    } else if current_lamports > target_rent {
+        let diff = current_lamports - target_rent;
+        **account.try_borrow_mut_lamports()? -= diff;
+        **payer.try_borrow_mut_lamports()? += diff;
+    }
+
+    Ok(())
+}
+
+/// Overwrites the account data with `value` in its current layout. Bytes past the
+/// serialized value are zeroed so no field of an older layout lingers in the padding.
+pub(crate) fn write_account<T: AccountSerialize>(account: &AccountInfo, value: &T) -> Result<()> {
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    let mut writer = &mut data[..];
+    value.try_serialize(&mut writer)
+}
+
+/// Rewrites one account in its current layout, resizing it first. Returns the version
+/// it was stored in, or `None` when it was already current and left untouched.
+pub(crate) fn migrate_account<'info, T: MigratableAccount>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    now: i64,
+) -> Result<Option<u8>> {
+    let raw = account.try_borrow_data()?.to_vec();
+    if is_current_layout::<T>(&raw) {
+        return Ok(None);
+    }
+
+    let (value, previous_version) = T::parse_layout(&raw, now)?;
+    resize_account(account, payer, system_program, T::LAYOUT_LEN)?;
+    write_account(account, &value)?;
+    Ok(Some(previous_version))
+}
+
+#[error_code]
+pub enum AccountMigrationError {
+    #[msg("The account discriminator does not match the expected account type")]
+    InvalidAccountDiscriminator,
+    #[msg("The account uses an unsupported layout")]
+    UnsupportedAccountLayout,
+    #[msg("The account version is newer than this program supports")]
+    UnsupportedAccountVersion,
+    #[msg("The account data could not be parsed")]
+    InvalidAccountLayout,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures;
+
+    fn vouch() -> Vouch {
+        Vouch {
+            voucher: Pubkey::new_unique(),
+            vouchee: Pubkey::new_unique(),
+            stake_amount: 1_000,
+            created_at: 1_700_000_000,
+            status: VouchStatus::Active,
+            cumulative_revenue: 40,
+            last_payout_at: 1_700_000_000,
+            voucher_reputation_snapshot: 50,
+            effective_weight_bps: 10_000,
+            low_reputation_voucher: false,
+            pending_dispute_links: 0,
+            bump: 254,
+            version: Vouch::CURRENT_VERSION,
+            last_stake_increase_at: 1_700_000_000,
+        }
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T, len: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data.resize(len, 0);
+        data
+    }
+
+    /// A baseline vouch as it was stored: the fields through `bump`, 106 bytes.
+    fn baseline_vouch(voucher: Pubkey, vouchee: Pubkey) -> Vec<u8> {
+        let mut data = Vouch::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(voucher.as_ref());
+        data.extend_from_slice(vouchee.as_ref());
+        data.extend_from_slice(&1_000u64.to_le_bytes()); // stake_amount
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // created_at
+        data.push(1); // status: Revoked
+        data.extend_from_slice(&40u64.to_le_bytes()); // cumulative_revenue
+        data.extend_from_slice(&1_700_000_100i64.to_le_bytes()); // last_payout_at
+        data.push(254); // bump
+        data
+    }
+
+    #[test]
+    fn baseline_vouches_read_at_full_weight_as_version_zero() {
+        let (voucher, vouchee) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let baseline = baseline_vouch(voucher, vouchee);
+        assert_eq!(baseline.len(), 106);
+        assert!(!is_current_layout::<Vouch>(&baseline));
+
+        let (upgraded, previous_version) = Vouch::parse_layout(&baseline, 0).unwrap();
+        assert_eq!(previous_version, 0);
+        assert_eq!(upgraded.version, Vouch::CURRENT_VERSION);
+        assert_eq!(upgraded.voucher, voucher);
+        assert_eq!(upgraded.vouchee, vouchee);
+        assert_eq!(upgraded.stake_amount, 1_000);
+        assert_eq!(upgraded.created_at, 1_700_000_000);
+        assert!(upgraded.status == VouchStatus::Revoked);
+        assert_eq!(upgraded.cumulative_revenue, 40);
+        assert_eq!(upgraded.last_payout_at, 1_700_000_100);
+        assert_eq!(upgraded.bump, 254);
+        assert_eq!(upgraded.effective_weight_bps, BPS_DENOMINATOR as u32);
+        assert_eq!(upgraded.voucher_reputation_snapshot, 0);
+        assert!(!upgraded.low_reputation_voucher);
+        assert_eq!(upgraded.pending_dispute_links, 0);
+        assert_eq!(upgraded.last_stake_increase_at, 1_700_000_000);
+
+        let mut unknown_status = baseline.clone();
+        unknown_status[88] = 3;
+        assert!(Vouch::parse_layout(&unknown_status, 0).is_err());
+        let mut foreign = baseline;
+        foreign[0] ^= 0xff;
+        assert!(Vouch::parse_layout(&foreign, 0).is_err());
+    }
+
+    #[test]
+    fn current_layouts_are_left_alone_and_newer_versions_rejected() {
+        let mut current = vouch();
+        assert!(is_current_layout::<Vouch>(&serialize(&current, Vouch::LEN)));
+
+        current.version = Vouch::CURRENT_VERSION + 1;
+        let newer = serialize(&current, Vouch::LEN);
+        assert!(!is_current_layout::<Vouch>(&newer));
+        assert!(Vouch::parse_layout(&newer, 0).is_err());
+        assert!(AuthorDispute::parse_layout(&newer, 0).is_err());
+        assert_eq!(
+            MigratableAccountKind::from_data(&newer),
+            Some(MigratableAccountKind::Vouch)
+        );
+    }
+
+    #[test]
+    fn configs_without_a_chain_context_wait_for_migrate_config() {
+        // The bonded layout (86 bytes) predates the chain context.
+        let bonded = serialize(&fixtures::config(), 86);
+        assert!(ReputationConfig::parse_layout(&bonded, 0).is_err());
+
+        let mut current = fixtures::config();
+        current.chain_context_id = 0;
+        let (upgraded, _) =
+            ReputationConfig::parse_layout(&serialize(&current, ReputationConfig::LEN), 0).unwrap();
+        assert_eq!(upgraded.chain_context, fixtures::DEVNET);
+        assert_eq!(
+            upgraded.chain_context_id,
+            ReputationConfig::compact_chain_id(fixtures::DEVNET)
+        );
+        assert_ne!(
+            upgraded.chain_context_id,
+            ReputationConfig::compact_chain_id("solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp")
+        );
+    }
+}
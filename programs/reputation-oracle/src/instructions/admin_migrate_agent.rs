@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::events::{emit_reputation_score_changed, AgentProfileMigrated, EVENT_VERSION};
 use crate::state::{AgentProfile, ReputationConfig};
 
+use crate::instructions::account_migration::{resize_account, write_account};
 use crate::instructions::agent_profile_migration::{
-    derive_canonical_agent_pda, parse_agent_profile_for_migration,
-    resize_agent_profile_account_if_needed, serialize_migrated_agent_profile,
-    AgentProfileMigrationError,
+    derive_canonical_agent_pda, parse_agent_profile_for_migration, AgentProfileMigrationError,
 };
 
 #[derive(Accounts)]
@@ -28,7 +28,7 @@ pub struct AdminMigrateAgent<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<AdminMigrateAgent>) -> Result<()> {
+pub(crate) fn handler(ctx: Context<AdminMigrateAgent>) -> Result<()> {
     let profile_info = &ctx.accounts.agent_profile;
     let raw = profile_info.try_borrow_data()?.to_vec();
     let parsed = parse_agent_profile_for_migration(&raw)?;
@@ -39,28 +39,38 @@ pub fn handler(ctx: Context<AdminMigrateAgent>) -> Result<()> {
         AgentProfileMigrationError::InvalidAgentProfilePda
     );
 
-    resize_agent_profile_account_if_needed(
+    resize_account(
         &profile_info.to_account_info(),
         &ctx.accounts.authority.to_account_info(),
         &ctx.accounts.system_program.to_account_info(),
+        AgentProfile::LEN,
     )?;
 
-    let mut profile = AgentProfile {
-        authority: parsed.authority,
-        metadata_uri: parsed.metadata_uri,
-        reputation_score: parsed.reputation_score,
-        total_vouches_received: parsed.total_vouches_received,
-        total_vouches_given: parsed.total_vouches_given,
-        total_staked_for: parsed.total_staked_for,
-        author_bond_lamports: parsed.author_bond_lamports,
-        active_free_skill_listings: parsed.active_free_skill_listings,
-        open_author_disputes: parsed.open_author_disputes,
-        registered_at: parsed.registered_at,
-        bump: canonical_bump,
-    };
+    let mut profile = parsed.into_agent_profile(canonical_bump);
+    let previous_score = profile.reputation_score;
     profile.reputation_score = profile.compute_reputation(&ctx.accounts.config);
+    profile.trust_tier = profile.compute_trust_tier(&ctx.accounts.config);
 
-    serialize_migrated_agent_profile(&profile_info.to_account_info(), &profile)?;
+    write_account(&profile_info.to_account_info(), &profile)?;
+
+    emit!(AgentProfileMigrated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: profile_info.key(),
+        agent_id: profile.agent_id,
+        migrated_by: ctx.accounts.authority.key(),
+        admin_migration: true,
+        previous_data_len: raw.len() as u32,
+        previous_metadata_uri: profile.metadata_uri.clone(),
+        metadata_uri: profile.metadata_uri.clone(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    emit_reputation_score_changed(
+        &ctx.accounts.config,
+        profile_info.key(),
+        previous_score,
+        profile.reputation_score,
+    )?;
     Ok(())
 }
 
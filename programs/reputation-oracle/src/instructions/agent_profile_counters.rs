@@ -0,0 +1,385 @@
+use anchor_lang::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::events::{emit_reputation_score_changed, AgentProfileCountersRecomputed, EVENT_VERSION};
+use crate::state::{
+    AgentProfile, AuthorBond, ReputationConfig, SkillListing, SkillStatus, Vouch,
+};
+
+/// The denormalized `AgentProfile` counters, tallied from the accounts they summarize.
+///
+/// The same tally backs the on-chain recompute instructions and off-chain reconcilers,
+/// so both agree on which vouches and listings each counter covers. The vouch account
+/// counts include settled vouches and change only when a vouch account is created or
+/// closed, so unlike the live totals they stay a completeness check for a tally.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AgentProfileCounters {
+    pub total_vouches_received: u32,
+    pub total_vouches_given: u32,
+    pub total_staked_for: u64,
+    pub vouched_reputation_total: u64,
+    pub effective_vouch_weight: u64,
+    pub low_reputation_vouches_received: u32,
+    pub active_skill_listings: u32,
+    pub active_free_skill_listings: u32,
+    pub author_bond_lamports: u64,
+    pub vouch_accounts_received: u32,
+    pub vouch_accounts_given: u32,
+}
+
+impl AgentProfileCounters {
+    /// Counts a vouch the agent gave or received. Only live vouches count toward the
+    /// totals; every vouch counts as an account.
+    pub fn add_vouch(&mut self, agent_profile: &Pubkey, vouch: &Vouch) {
+        if vouch.vouchee == *agent_profile {
+            self.vouch_accounts_received = self.vouch_accounts_received.saturating_add(1);
+        }
+        if vouch.voucher == *agent_profile {
+            self.vouch_accounts_given = self.vouch_accounts_given.saturating_add(1);
+        }
+        if !vouch.status.is_live() {
+            return;
+        }
+        if vouch.vouchee == *agent_profile {
+            self.total_vouches_received = self.total_vouches_received.saturating_add(1);
+            self.total_staked_for = self.total_staked_for.saturating_add(vouch.stake_amount);
+            self.vouched_reputation_total = self
+                .vouched_reputation_total
+                .saturating_add(vouch.voucher_reputation_snapshot);
+            self.effective_vouch_weight = self
+                .effective_vouch_weight
+                .saturating_add(vouch.effective_weight_bps as u64);
+            if vouch.low_reputation_voucher {
+                self.low_reputation_vouches_received =
+                    self.low_reputation_vouches_received.saturating_add(1);
+            }
+        }
+        if vouch.voucher == *agent_profile {
+            self.total_vouches_given = self.total_vouches_given.saturating_add(1);
+        }
+    }
+
+    /// Counts one of the agent's listings. Removed listings no longer count.
+    pub fn add_skill_listing(&mut self, listing: &SkillListing) {
+        if listing.status == SkillStatus::Removed {
+            return;
+        }
+        self.active_skill_listings = self.active_skill_listings.saturating_add(1);
+        if SkillListing::is_free_price(listing.price_lamports) {
+            self.active_free_skill_listings = self.active_free_skill_listings.saturating_add(1);
+        }
+    }
+
+    /// The profile's stored values, for comparison against a fresh tally.
+    pub fn from_profile(profile: &AgentProfile) -> Self {
+        Self {
+            total_vouches_received: profile.total_vouches_received,
+            total_vouches_given: profile.total_vouches_given,
+            total_staked_for: profile.total_staked_for,
+            vouched_reputation_total: profile.vouched_reputation_total,
+            effective_vouch_weight: profile.effective_vouch_weight,
+            low_reputation_vouches_received: profile.low_reputation_vouches_received,
+            active_skill_listings: profile.active_skill_listings,
+            active_free_skill_listings: profile.active_free_skill_listings,
+            author_bond_lamports: profile.author_bond_lamports,
+            vouch_accounts_received: profile.vouch_accounts_received,
+            vouch_accounts_given: profile.vouch_accounts_given,
+        }
+    }
+
+    pub fn apply_to(&self, profile: &mut AgentProfile) {
+        profile.total_vouches_received = self.total_vouches_received;
+        profile.total_vouches_given = self.total_vouches_given;
+        profile.total_staked_for = self.total_staked_for;
+        profile.vouched_reputation_total = self.vouched_reputation_total;
+        profile.effective_vouch_weight = self.effective_vouch_weight;
+        profile.low_reputation_vouches_received = self.low_reputation_vouches_received;
+        profile.active_skill_listings = self.active_skill_listings;
+        profile.active_free_skill_listings = self.active_free_skill_listings;
+        profile.author_bond_lamports = self.author_bond_lamports;
+        profile.vouch_accounts_received = self.vouch_accounts_received;
+        profile.vouch_accounts_given = self.vouch_accounts_given;
+    }
+
+    /// Whether the tally saw every vouch account and unremoved listing the profile
+    /// records. Each supplied account is distinct and tied to the agent, so matching
+    /// counts mean none were left out, whatever the vouch totals being repaired say.
+    /// `active_skill_listings` qualifies because only creating and removing a listing
+    /// change it.
+    pub fn covers_accounts(&self, stored: &Self) -> bool {
+        self.vouch_accounts_received == stored.vouch_accounts_received
+            && self.vouch_accounts_given == stored.vouch_accounts_given
+            && self.active_skill_listings == stored.active_skill_listings
+    }
+}
+
+/// Writes a fresh tally to the profile, recomputes score and tier, and emits the
+/// recompute events.
+pub(crate) fn recompute_agent_profile(
+    config: &ReputationConfig,
+    agent_profile: &mut Account<AgentProfile>,
+    counters: AgentProfileCounters,
+    recomputed_by: Pubkey,
+    admin_reconcile: bool,
+) -> Result<()> {
+    let agent = agent_profile.key();
+    let previous_counters = AgentProfileCounters::from_profile(agent_profile);
+    counters.apply_to(agent_profile);
+    let previous_score = agent_profile.reputation_score;
+    agent_profile.reputation_score = agent_profile.compute_reputation(config);
+    agent_profile.trust_tier = agent_profile.compute_trust_tier(config);
+    emit_reputation_score_changed(config, agent, previous_score, agent_profile.reputation_score)?;
+
+    emit!(AgentProfileCountersRecomputed {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        agent,
+        recomputed_by,
+        admin_reconcile,
+        changed: counters != previous_counters,
+        total_vouches_received: counters.total_vouches_received,
+        total_vouches_given: counters.total_vouches_given,
+        total_staked_for: counters.total_staked_for,
+        author_bond_lamports: counters.author_bond_lamports,
+        active_skill_listings: counters.active_skill_listings,
+        active_free_skill_listings: counters.active_free_skill_listings,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Tallies supplied `Vouch` and `SkillListing` accounts into counters for one agent.
+///
+/// Every account must be a program-owned vouch the agent gave or received, or one of
+/// the agent's listings, and may appear once. The bond comes from the agent's author
+/// bond PDA, which counts as zero while it does not exist.
+pub(crate) fn tally_agent_accounts(
+    accounts: &[AccountInfo],
+    program_id: &Pubkey,
+    agent_profile_key: &Pubkey,
+    agent_id: &Pubkey,
+    author_bond: &AccountInfo,
+) -> Result<AgentProfileCounters> {
+    let mut counters = AgentProfileCounters {
+        author_bond_lamports: read_author_bond_amount(author_bond, program_id, agent_id)?,
+        ..Default::default()
+    };
+    let mut seen = BTreeSet::new();
+
+    for account in accounts {
+        require!(
+            seen.insert(account.key()),
+            AgentProfileCountersError::DuplicateCounterAccount
+        );
+        require_keys_eq!(
+            *account.owner,
+            *program_id,
+            AgentProfileCountersError::CounterAccountNotOwnedByProgram
+        );
+        let data = account.try_borrow_data()?;
+        if data.starts_with(Vouch::DISCRIMINATOR) {
+            let vouch = Vouch::try_deserialize(&mut &data[..])?;
+            require!(
+                vouch.voucher == *agent_profile_key || vouch.vouchee == *agent_profile_key,
+                AgentProfileCountersError::VouchNotForAgent
+            );
+            counters.add_vouch(agent_profile_key, &vouch);
+        } else if data.starts_with(SkillListing::DISCRIMINATOR) {
+            let listing = SkillListing::try_deserialize(&mut &data[..])?;
+            require_keys_eq!(
+                listing.author,
+                *agent_id,
+                AgentProfileCountersError::SkillListingNotForAgent
+            );
+            counters.add_skill_listing(&listing);
+        } else {
+            return err!(AgentProfileCountersError::UnsupportedCounterAccount);
+        }
+    }
+
+    Ok(counters)
+}
+
+fn read_author_bond_amount(
+    author_bond: &AccountInfo,
+    program_id: &Pubkey,
+    agent_id: &Pubkey,
+) -> Result<u64> {
+    if author_bond.owner != program_id {
+        require!(
+            author_bond.data_is_empty(),
+            AgentProfileCountersError::InvalidAuthorBondAccount
+        );
+        return Ok(0);
+    }
+    let bond = AuthorBond::try_deserialize(&mut &author_bond.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        bond.author,
+        *agent_id,
+        AgentProfileCountersError::InvalidAuthorBondAccount
+    );
+    Ok(bond.amount)
+}
+
+#[error_code]
+pub enum AgentProfileCountersError {
+    #[msg("Counter accounts must be owned by the reputation program")]
+    CounterAccountNotOwnedByProgram,
+    #[msg("Counter accounts must be Vouch or SkillListing accounts")]
+    UnsupportedCounterAccount,
+    #[msg("Each counter account may only be supplied once")]
+    DuplicateCounterAccount,
+    #[msg("Supplied vouch was neither given nor received by this agent")]
+    VouchNotForAgent,
+    #[msg("Supplied skill listing belongs to another author")]
+    SkillListingNotForAgent,
+    #[msg("Author bond account does not hold this agent's bond")]
+    InvalidAuthorBondAccount,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{fixtures, VouchStatus};
+
+    fn vouch(voucher: Pubkey, vouchee: Pubkey, status: VouchStatus, low: bool) -> Vouch {
+        Vouch {
+            voucher,
+            vouchee,
+            stake_amount: 1_000,
+            created_at: 0,
+            status,
+            cumulative_revenue: 0,
+            last_payout_at: 0,
+            voucher_reputation_snapshot: 50,
+            effective_weight_bps: if low { 5_000 } else { 10_000 },
+            low_reputation_voucher: low,
+            pending_dispute_links: 0,
+            bump: 0,
+            version: Vouch::CURRENT_VERSION,
+            last_stake_increase_at: 0,
+        }
+    }
+
+    fn listing(price_lamports: u64, status: SkillStatus) -> SkillListing {
+        SkillListing {
+            author: Pubkey::new_unique(),
+            skill_uri: String::new(),
+            name: String::new(),
+            description: String::new(),
+            price_lamports,
+            total_downloads: 0,
+            total_revenue: 0,
+            unclaimed_voucher_revenue: 0,
+            created_at: 0,
+            updated_at: 0,
+            status,
+            bump: 0,
+            version: SkillListing::CURRENT_VERSION,
+        }
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn counts_only_live_vouches_and_unremoved_listings() {
+        let agent = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mut counters = AgentProfileCounters::default();
+        counters.add_vouch(&agent, &vouch(other, agent, VouchStatus::Active, false));
+        counters.add_vouch(&agent, &vouch(other, agent, VouchStatus::Active, true));
+        counters.add_vouch(&agent, &vouch(other, agent, VouchStatus::Revoked, false));
+        counters.add_vouch(&agent, &vouch(agent, other, VouchStatus::Active, false));
+        counters.add_vouch(&agent, &vouch(agent, other, VouchStatus::Slashed, false));
+        counters.add_skill_listing(&listing(0, SkillStatus::Active));
+        counters.add_skill_listing(&listing(10, SkillStatus::Suspended));
+        counters.add_skill_listing(&listing(0, SkillStatus::Removed));
+
+        assert_eq!(
+            counters,
+            AgentProfileCounters {
+                total_vouches_received: 2,
+                total_vouches_given: 1,
+                total_staked_for: 2_000,
+                vouched_reputation_total: 100,
+                effective_vouch_weight: 15_000,
+                low_reputation_vouches_received: 1,
+                active_skill_listings: 2,
+                active_free_skill_listings: 1,
+                author_bond_lamports: 0,
+                vouch_accounts_received: 3,
+                vouch_accounts_given: 2,
+            }
+        );
+
+        let mut stored = counters;
+        stored.total_vouches_given = 7;
+        stored.total_staked_for = 0;
+        assert!(counters.covers_accounts(&stored));
+        stored.vouch_accounts_given += 1;
+        assert!(!counters.covers_accounts(&stored));
+        stored.vouch_accounts_given -= 1;
+        stored.active_skill_listings += 1;
+        assert!(!counters.covers_accounts(&stored));
+    }
+
+    #[test]
+    fn a_complete_tally_repairs_a_skewed_received_count() {
+        let mut profile = fixtures::profile(0, fixtures::NOW);
+        let agent = Pubkey::new_unique();
+        let voucher = Pubkey::new_unique();
+        // Two vouch accounts exist, one of them revoked, but a bug left the live count at 5.
+        profile.vouch_accounts_received = 2;
+        profile.total_vouches_received = 5;
+        profile.total_staked_for = 5_000;
+        profile.effective_vouch_weight = 50_000;
+        let stored = AgentProfileCounters::from_profile(&profile);
+
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (mut live, mut revoked) = (0u64, 0u64);
+        let (mut bond_lamports, mut bond_data) = (0u64, Vec::new());
+        let mut live_data = serialize(&vouch(voucher, agent, VouchStatus::Active, false));
+        let mut revoked_data = serialize(&vouch(voucher, agent, VouchStatus::Revoked, false));
+        let system_program = Pubkey::default();
+        let live_vouch = AccountInfo::new(
+            &keys[0], false, false, &mut live, &mut live_data, &crate::ID, false, 0,
+        );
+        let revoked_vouch = AccountInfo::new(
+            &keys[1], false, false, &mut revoked, &mut revoked_data, &crate::ID, false, 0,
+        );
+        let author_bond = AccountInfo::new(
+            &keys[2], false, false, &mut bond_lamports, &mut bond_data, &system_program, false, 0,
+        );
+
+        // A set missing the revoked vouch falls short of the account count.
+        let partial = tally_agent_accounts(
+            std::slice::from_ref(&live_vouch),
+            &crate::ID,
+            &agent,
+            &profile.agent_id,
+            &author_bond,
+        )
+        .unwrap();
+        assert!(!partial.covers_accounts(&stored));
+
+        let complete = tally_agent_accounts(
+            &[live_vouch, revoked_vouch],
+            &crate::ID,
+            &agent,
+            &profile.agent_id,
+            &author_bond,
+        )
+        .unwrap();
+        assert!(complete.covers_accounts(&stored));
+        complete.apply_to(&mut profile);
+        assert_eq!(profile.total_vouches_received, 1);
+        assert_eq!(profile.total_staked_for, 1_000);
+        assert_eq!(profile.effective_vouch_weight, 10_000);
+        assert_eq!(profile.vouch_accounts_received, 2);
+    }
+}
@@ -1,16 +1,22 @@
-use std::convert::TryInto;
-
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 
-use crate::state::AgentProfile;
+use crate::bytes::Reader;
+use crate::instructions::account_migration::decode_appended_layout;
+use crate::state::{AgentProfile, TrustTier, BPS_DENOMINATOR};
 
 pub(crate) const MIN_PLAUSIBLE_REGISTERED_AT: i64 = 946_684_800; // 2000-01-01T00:00:00Z
 pub(crate) const MAX_FUTURE_SKEW_SECONDS: i64 = 366 * 24 * 60 * 60;
-const LEGACY_AGENT_PROFILE_TRAILING_LEN: usize = 41;
+const BONDED_AGENT_PROFILE_LEN: usize = 293;
 
+/// An agent profile read from the legacy, bonded, or current layout, normalized to the
+/// current field set. Off-chain clients use it to decode accounts that have not been
+/// migrated yet. `version` is the stored layout version, 0 for layouts from before
+/// versioning. `counters_verified` is only carried over from layouts that record it,
+/// since older layouts seeded counters they could not know. Older layouts could not
+/// claim foreign records, so their claimed backing is zero, and their vouch account
+/// counts stay zero until `reconcile_agent_profile` tallies them.
 #[derive(Clone)]
-pub(crate) struct ParsedAgentProfile {
+pub struct ParsedAgentProfile {
     pub authority: Pubkey,
     pub metadata_uri: String,
     pub reputation_score: u64,
@@ -21,6 +27,58 @@ pub(crate) struct ParsedAgentProfile {
     pub active_free_skill_listings: u32,
     pub open_author_disputes: u32,
     pub registered_at: i64,
+    pub vouched_reputation_total: u64,
+    pub trust_depth: u8,
+    pub trust_depth_updated_at: i64,
+    pub trust_tier: TrustTier,
+    pub last_upheld_dispute_at: i64,
+    pub effective_vouch_weight: u64,
+    pub low_reputation_vouches_received: u32,
+    pub active_skill_listings: u32,
+    pub agent_id: Pubkey,
+    pub delegate: Pubkey,
+    pub version: u8,
+    pub counters_verified: bool,
+    pub claimed_foreign_staked_for: u64,
+    pub claimed_foreign_vouch_weight: u64,
+    pub claimed_foreign_reputation_total: u64,
+    pub vouch_accounts_received: u32,
+    pub vouch_accounts_given: u32,
+}
+
+impl ParsedAgentProfile {
+    pub fn into_agent_profile(self, bump: u8) -> AgentProfile {
+        AgentProfile {
+            authority: self.authority,
+            metadata_uri: self.metadata_uri,
+            reputation_score: self.reputation_score,
+            total_vouches_received: self.total_vouches_received,
+            total_vouches_given: self.total_vouches_given,
+            total_staked_for: self.total_staked_for,
+            author_bond_lamports: self.author_bond_lamports,
+            active_free_skill_listings: self.active_free_skill_listings,
+            open_author_disputes: self.open_author_disputes,
+            registered_at: self.registered_at,
+            vouched_reputation_total: self.vouched_reputation_total,
+            trust_depth: self.trust_depth,
+            trust_depth_updated_at: self.trust_depth_updated_at,
+            trust_tier: self.trust_tier,
+            last_upheld_dispute_at: self.last_upheld_dispute_at,
+            effective_vouch_weight: self.effective_vouch_weight,
+            low_reputation_vouches_received: self.low_reputation_vouches_received,
+            active_skill_listings: self.active_skill_listings,
+            agent_id: self.agent_id,
+            delegate: self.delegate,
+            bump,
+            version: AgentProfile::CURRENT_VERSION,
+            counters_verified: self.counters_verified,
+            claimed_foreign_staked_for: self.claimed_foreign_staked_for,
+            claimed_foreign_vouch_weight: self.claimed_foreign_vouch_weight,
+            claimed_foreign_reputation_total: self.claimed_foreign_reputation_total,
+            vouch_accounts_received: self.vouch_accounts_received,
+            vouch_accounts_given: self.vouch_accounts_given,
+        }
+    }
 }
 
 pub(crate) fn is_plausible_registered_at(timestamp: i64, now: i64) -> bool {
@@ -28,64 +86,34 @@ pub(crate) fn is_plausible_registered_at(timestamp: i64, now: i64) -> bool {
         && timestamp <= now.saturating_add(MAX_FUTURE_SKEW_SECONDS)
 }
 
-fn read_pubkey(raw: &[u8], start: usize) -> Result<Pubkey> {
-    let bytes: [u8; 32] = raw
-        .get(start..start + 32)
-        .ok_or(error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?
-        .try_into()
-        .map_err(|_| error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?;
-    Ok(Pubkey::new_from_array(bytes))
-}
-
-fn read_u64(raw: &[u8], start: usize) -> Result<u64> {
-    let bytes: [u8; 8] = raw
-        .get(start..start + 8)
-        .ok_or(error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?
-        .try_into()
-        .map_err(|_| error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?;
-    Ok(u64::from_le_bytes(bytes))
+/// Vouches recorded before sybil weighting existed are grandfathered at full weight.
+fn full_vouch_weight(total_vouches_received: u32) -> u64 {
+    (total_vouches_received as u64).saturating_mul(BPS_DENOMINATOR)
 }
 
-fn read_i64(raw: &[u8], start: usize) -> Result<i64> {
-    let bytes: [u8; 8] = raw
-        .get(start..start + 8)
-        .ok_or(error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?
-        .try_into()
-        .map_err(|_| error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?;
-    Ok(i64::from_le_bytes(bytes))
+fn invalid_agent_profile_layout() -> Error {
+    error!(AgentProfileMigrationError::InvalidAgentProfileLayout)
 }
 
-fn read_u32(raw: &[u8], start: usize) -> Result<u32> {
-    let bytes: [u8; 4] = raw
-        .get(start..start + 4)
-        .ok_or(error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?
-        .try_into()
-        .map_err(|_| error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?;
-    Ok(u32::from_le_bytes(bytes))
-}
-
-fn read_metadata_uri(raw: &[u8]) -> Result<(String, usize)> {
-    let uri_len = read_u32(raw, 40)? as usize;
+/// Reads the authority and metadata URI that open every profile layout, leaving
+/// `reader` at the first field after the URI.
+fn read_profile_head(reader: &mut Reader) -> Result<(Pubkey, String)> {
+    let authority = reader.decode(Reader::pubkey, invalid_agent_profile_layout)?;
+    let uri_len = reader.decode(Reader::u32_le, invalid_agent_profile_layout)? as usize;
     require!(
         uri_len <= AgentProfile::MAX_URI_LENGTH,
         AgentProfileMigrationError::MetadataUriTooLong
     );
 
-    let start = 44;
-    let end = start + uri_len;
-    let bytes = raw
-        .get(start..end)
-        .ok_or(error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?;
+    let bytes = reader.decode(|reader| reader.take(uri_len), invalid_agent_profile_layout)?;
     let metadata_uri = String::from_utf8(bytes.to_vec())
         .map_err(|_| error!(AgentProfileMigrationError::InvalidMetadataUriEncoding))?;
 
-    Ok((metadata_uri, end))
+    Ok((authority, metadata_uri))
 }
 
-fn parse_current_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile> {
-    let mut slice = raw;
-    let profile = AgentProfile::try_deserialize(&mut slice)
-        .map_err(|_| error!(AgentProfileMigrationError::InvalidAgentProfileLayout))?;
+fn parse_versioned_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile> {
+    let (profile, version) = decode_appended_layout::<AgentProfile>(raw)?;
     let registered_at = if is_plausible_registered_at(profile.registered_at, now) {
         profile.registered_at
     } else {
@@ -103,28 +131,118 @@ fn parse_current_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfil
         active_free_skill_listings: profile.active_free_skill_listings,
         open_author_disputes: profile.open_author_disputes,
         registered_at,
+        vouched_reputation_total: profile.vouched_reputation_total,
+        trust_depth: profile.trust_depth,
+        trust_depth_updated_at: profile.trust_depth_updated_at,
+        trust_tier: profile.trust_tier,
+        last_upheld_dispute_at: profile.last_upheld_dispute_at,
+        effective_vouch_weight: profile.effective_vouch_weight,
+        low_reputation_vouches_received: profile.low_reputation_vouches_received,
+        active_skill_listings: profile.active_skill_listings,
+        agent_id: profile.agent_id,
+        delegate: profile.delegate,
+        version,
+        counters_verified: profile.counters_verified,
+        claimed_foreign_staked_for: profile.claimed_foreign_staked_for,
+        claimed_foreign_vouch_weight: profile.claimed_foreign_vouch_weight,
+        claimed_foreign_reputation_total: profile.claimed_foreign_reputation_total,
+        vouch_accounts_received: profile.vouch_accounts_received,
+        vouch_accounts_given: profile.vouch_accounts_given,
     })
 }
 
-fn parse_legacy_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile> {
-    require!(
-        raw.len() >= 8 + 32 + 4 + LEGACY_AGENT_PROFILE_TRAILING_LEN,
-        AgentProfileMigrationError::InvalidAgentProfileLayout
-    );
+fn parse_bonded_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile> {
+    let mut reader = Reader::at(raw, 8);
+    let (authority, metadata_uri) = read_profile_head(&mut reader)?;
+    let (
+        reputation_score,
+        total_vouches_received,
+        total_vouches_given,
+        total_staked_for,
+        author_bond_lamports,
+        active_free_skill_listings,
+        open_author_disputes,
+        stored_registered_at,
+    ) = reader.decode(
+        |reader| {
+            let fields = (
+                reader.u64_le()?,
+                reader.u32_le()?,
+                reader.u32_le()?,
+                reader.u64_le()?,
+                reader.u64_le()?,
+                reader.u32_le()?,
+                reader.u32_le()?,
+                reader.i64_le()?,
+            );
+            reader.u8()?; // bump
+            Some(fields)
+        },
+        invalid_agent_profile_layout,
+    )?;
+    let registered_at = if is_plausible_registered_at(stored_registered_at, now) {
+        stored_registered_at
+    } else {
+        now
+    };
 
-    let authority = read_pubkey(raw, 8)?;
-    let (metadata_uri, base) = read_metadata_uri(raw)?;
-    require!(
-        raw.len() >= base + LEGACY_AGENT_PROFILE_TRAILING_LEN,
-        AgentProfileMigrationError::InvalidAgentProfileLayout
-    );
+    Ok(ParsedAgentProfile {
+        authority,
+        metadata_uri,
+        reputation_score,
+        total_vouches_received,
+        total_vouches_given,
+        total_staked_for,
+        author_bond_lamports,
+        active_free_skill_listings,
+        open_author_disputes,
+        registered_at,
+        vouched_reputation_total: 0,
+        trust_depth: 0,
+        trust_depth_updated_at: 0,
+        trust_tier: TrustTier::Unverified,
+        last_upheld_dispute_at: 0,
+        effective_vouch_weight: full_vouch_weight(total_vouches_received),
+        low_reputation_vouches_received: 0,
+        // Paid listings were never counted; free ones are the only known lower bound.
+        active_skill_listings: active_free_skill_listings,
+        agent_id: authority,
+        delegate: Pubkey::default(),
+        version: 0,
+        counters_verified: false,
+        claimed_foreign_staked_for: 0,
+        claimed_foreign_vouch_weight: 0,
+        claimed_foreign_reputation_total: 0,
+        vouch_accounts_received: 0,
+        vouch_accounts_given: 0,
+    })
+}
 
-    let reputation_score = read_u64(raw, base)?;
-    let total_vouches_received = read_u32(raw, base + 8)?;
-    let total_vouches_given = read_u32(raw, base + 12)?;
-    let total_staked_for = read_u64(raw, base + 16)?;
-    let candidate_registered_at = read_i64(raw, base + 24)?;
-    let legacy_registered_at = read_i64(raw, base + 32)?;
+fn parse_legacy_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile> {
+    let mut reader = Reader::at(raw, 8);
+    let (authority, metadata_uri) = read_profile_head(&mut reader)?;
+    let (
+        reputation_score,
+        total_vouches_received,
+        total_vouches_given,
+        total_staked_for,
+        candidate_registered_at,
+        legacy_registered_at,
+    ) = reader.decode(
+        |reader| {
+            let fields = (
+                reader.u64_le()?,
+                reader.u32_le()?,
+                reader.u32_le()?,
+                reader.u64_le()?,
+                reader.i64_le()?,
+                reader.i64_le()?,
+            );
+            reader.u8()?; // bump
+            Some(fields)
+        },
+        invalid_agent_profile_layout,
+    )?;
     let registered_at = if is_plausible_registered_at(candidate_registered_at, now) {
         candidate_registered_at
     } else if is_plausible_registered_at(legacy_registered_at, now) {
@@ -144,20 +262,42 @@ fn parse_legacy_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile
         active_free_skill_listings: 0,
         open_author_disputes: 0,
         registered_at,
+        vouched_reputation_total: 0,
+        trust_depth: 0,
+        trust_depth_updated_at: 0,
+        trust_tier: TrustTier::Unverified,
+        last_upheld_dispute_at: 0,
+        effective_vouch_weight: full_vouch_weight(total_vouches_received),
+        low_reputation_vouches_received: 0,
+        active_skill_listings: 0,
+        agent_id: authority,
+        delegate: Pubkey::default(),
+        version: 0,
+        counters_verified: false,
+        claimed_foreign_staked_for: 0,
+        claimed_foreign_vouch_weight: 0,
+        claimed_foreign_reputation_total: 0,
+        vouch_accounts_received: 0,
+        vouch_accounts_given: 0,
     })
 }
 
 pub(crate) fn parse_agent_profile_for_migration(raw: &[u8]) -> Result<ParsedAgentProfile> {
+    decode_agent_profile(raw, Clock::get()?.unix_timestamp)
+}
+
+/// Decodes current, bonded, and legacy profile layouts. `now` stands in for an
+/// implausible stored `registered_at`, exactly as a migration at that time would.
+pub fn decode_agent_profile(raw: &[u8], now: i64) -> Result<ParsedAgentProfile> {
     require!(
-        raw.starts_with(&AgentProfile::DISCRIMINATOR),
+        raw.starts_with(AgentProfile::DISCRIMINATOR),
         AgentProfileMigrationError::InvalidAgentProfileDiscriminator
     );
 
-    let now = Clock::get()?.unix_timestamp;
-    if raw.len() == AgentProfile::LEN {
-        parse_current_agent_profile(raw, now)
-    } else {
-        parse_legacy_agent_profile(raw, now)
+    match raw.len() {
+        AgentProfile::LEN => parse_versioned_agent_profile(raw, now),
+        BONDED_AGENT_PROFILE_LEN => parse_bonded_agent_profile(raw, now),
+        _ => parse_legacy_agent_profile(raw, now),
     }
 }
 
@@ -165,52 +305,6 @@ pub(crate) fn derive_canonical_agent_pda(authority: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(&[b"agent", authority.as_ref()], &crate::ID)
 }
 
-pub(crate) fn resize_agent_profile_account_if_needed<'info>(
-    profile_info: &AccountInfo<'info>,
-    payer_info: &AccountInfo<'info>,
-    system_program_info: &AccountInfo<'info>,
-) -> Result<()> {
-    let target_len = AgentProfile::LEN;
-    if profile_info.data_len() == target_len {
-        return Ok(());
-    }
-
-    profile_info.resize(target_len)?;
-
-    let rent = Rent::get()?;
-    let target_rent = rent.minimum_balance(target_len);
-    let current_lamports = profile_info.lamports();
-
-    if target_rent > current_lamports {
-        let diff = target_rent - current_lamports;
-        let cpi = system_program::Transfer {
-            from: payer_info.clone(),
-            to: profile_info.clone(),
-        };
-        system_program::transfer(
-            CpiContext::new(system_program_info.clone(), cpi),
-            diff,
-        )?;
-    } else if current_lamports > target_rent {
-        let diff = current_lamports - target_rent;
-        **profile_info.try_borrow_mut_lamports()? -= diff;
-        **payer_info.try_borrow_mut_lamports()? += diff;
-    }
-
-    Ok(())
-}
-
-pub(crate) fn serialize_migrated_agent_profile<'info>(
-    profile_info: &AccountInfo<'info>,
-    profile: &AgentProfile,
-) -> Result<()> {
-    let mut data = profile_info.try_borrow_mut_data()?;
-    data[..8].copy_from_slice(&AgentProfile::DISCRIMINATOR);
-    let mut writer = &mut data[8..];
-    AnchorSerialize::serialize(profile, &mut writer)?;
-    Ok(())
-}
-
 #[error_code]
 pub enum AgentProfileMigrationError {
     #[msg("The agent profile discriminator does not match AgentProfile")]
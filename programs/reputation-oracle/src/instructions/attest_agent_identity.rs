@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentIdentityAttested, EVENT_VERSION};
+use crate::state::{AgentIdentityBinding, ReputationConfig};
+
+/// Config-authority attestation for bindings whose registry lives on another chain and
+/// therefore cannot be checked by `bind_agent_identity` itself.
+#[derive(Accounts)]
+pub struct AttestAgentIdentity<'info> {
+    #[account(
+        mut,
+        seeds = [b"identity_binding", AgentIdentityBinding::seed_hash(&identity_binding.canonical_agent_id).as_ref()],
+        bump = identity_binding.bump,
+        constraint = !identity_binding.is_registry_verified() @ AttestAgentIdentityError::BindingAlreadyVerified
+    )]
+    pub identity_binding: Account<'info, AgentIdentityBinding>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ AttestAgentIdentityError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<AttestAgentIdentity>) -> Result<()> {
+    let clock = Clock::get()?;
+    let identity_binding = &mut ctx.accounts.identity_binding;
+    identity_binding.attested_by = ctx.accounts.authority.key();
+    identity_binding.attested_at = clock.unix_timestamp;
+
+    emit!(AgentIdentityAttested {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: identity_binding.agent,
+        binding: identity_binding.key(),
+        canonical_agent_id: identity_binding.canonical_agent_id.clone(),
+        attested_by: identity_binding.attested_by,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum AttestAgentIdentityError {
+    #[msg("Only the config authority can attest identity bindings")]
+    UnauthorizedConfigAuthority,
+    #[msg("Solana registry bindings are verified on-chain and need no attestation")]
+    BindingAlreadyVerified,
+}
@@ -0,0 +1,175 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+
+use crate::agent_registry::{parse_core_asset, MPL_CORE_PROGRAM_ID};
+use crate::events::{AgentIdentityBound, EVENT_VERSION};
+use crate::identity::{parse_canonical_agent_id, SOLANA_NAMESPACE};
+use crate::state::{AgentIdentityBinding, AgentProfile, ReputationConfig};
+
+#[derive(Accounts)]
+#[instruction(canonical_agent_id: String)]
+pub struct BindAgentIdentity<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = AgentIdentityBinding::LEN,
+        seeds = [b"identity_binding", AgentIdentityBinding::seed_hash(&canonical_agent_id).as_ref()],
+        bump
+    )]
+    pub identity_binding: Account<'info, AgentIdentityBinding>,
+
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ BindAgentIdentityError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: Solana Agent Registry core asset named by the canonical ID. Required for
+    /// `solana:` bindings; the handler checks its key, owner program, layout, and
+    /// collection against the configured registry.
+    pub registry_asset: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<BindAgentIdentity>,
+    canonical_agent_id: String,
+    raw_upstream_label: String,
+) -> Result<()> {
+    let parsed = parse_canonical_agent_id(&canonical_agent_id)
+        .ok_or(BindAgentIdentityError::InvalidCanonicalAgentId)?;
+    require!(
+        raw_upstream_label.len() <= AgentIdentityBinding::MAX_RAW_UPSTREAM_LABEL_LEN,
+        BindAgentIdentityError::RawUpstreamLabelTooLong
+    );
+    let chain_context = parsed.chain_id.to_string();
+
+    let registry_asset = if parsed.namespace == SOLANA_NAMESPACE {
+        let config = &ctx.accounts.config;
+        require!(
+            parsed.chain_id == config.chain_context,
+            BindAgentIdentityError::RegistryChainMismatch
+        );
+        require!(
+            config.has_agent_registry(),
+            BindAgentIdentityError::AgentRegistryNotConfigured
+        );
+        require!(
+            parsed.registry == config.agent_registry_program.to_string(),
+            BindAgentIdentityError::UnknownAgentRegistry
+        );
+        let asset_key = Pubkey::from_str(parsed.record_id)
+            .map_err(|_| BindAgentIdentityError::InvalidRegistryAssetId)?;
+        let asset = ctx
+            .accounts
+            .registry_asset
+            .as_ref()
+            .ok_or(BindAgentIdentityError::MissingRegistryAsset)?;
+        require_keys_eq!(
+            asset.key(),
+            asset_key,
+            BindAgentIdentityError::RegistryAssetMismatch
+        );
+        require_keys_eq!(
+            *asset.owner,
+            MPL_CORE_PROGRAM_ID,
+            BindAgentIdentityError::InvalidRegistryAsset
+        );
+        let control = parse_core_asset(&asset.try_borrow_data()?)
+            .ok_or(BindAgentIdentityError::InvalidRegistryAsset)?;
+        require!(
+            control.is_in_collection(&config.agent_registry_collection),
+            BindAgentIdentityError::RegistryAssetOutsideCollection
+        );
+        require!(
+            control.is_controlled_by(&ctx.accounts.authority.key()),
+            BindAgentIdentityError::RegistryAssetNotControlled
+        );
+        asset_key
+    } else {
+        Pubkey::default()
+    };
+
+    let clock = Clock::get()?;
+    let agent = ctx.accounts.agent_profile.key();
+    let identity_binding = &mut ctx.accounts.identity_binding;
+    // A verified asset follows its current controller, so a transferred registry
+    // record can be re-bound by the new owner. Unverified IDs stay first-come until
+    // the config authority reassigns them.
+    let rebind_to_new_agent =
+        identity_binding.agent != agent && registry_asset != Pubkey::default();
+    if identity_binding.agent == Pubkey::default() || rebind_to_new_agent {
+        identity_binding.agent = agent;
+        identity_binding.canonical_agent_id = canonical_agent_id;
+        identity_binding.chain_context = chain_context;
+        identity_binding.bound_at = clock.unix_timestamp;
+        identity_binding.attested_by = Pubkey::default();
+        identity_binding.attested_at = 0;
+        identity_binding.bump = ctx.bumps.identity_binding;
+    } else {
+        require_keys_eq!(
+            identity_binding.agent,
+            agent,
+            BindAgentIdentityError::IdentityBoundToAnotherAgent
+        );
+    }
+    identity_binding.raw_upstream_label = raw_upstream_label;
+    identity_binding.registry_asset = registry_asset;
+    identity_binding.updated_at = clock.unix_timestamp;
+
+    emit!(AgentIdentityBound {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent,
+        binding: identity_binding.key(),
+        canonical_agent_id: identity_binding.canonical_agent_id.clone(),
+        agent_chain_context: identity_binding.chain_context.clone(),
+        raw_upstream_label: identity_binding.raw_upstream_label.clone(),
+        registry_asset: identity_binding.is_registry_verified().then_some(registry_asset),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum BindAgentIdentityError {
+    #[msg("Only the agent authority can bind registry identities")]
+    UnauthorizedAgentAuthority,
+    #[msg("Canonical agent ID must look like <caip2-chain-id>:<registry>#<recordId>")]
+    InvalidCanonicalAgentId,
+    #[msg("Raw upstream label is too long")]
+    RawUpstreamLabelTooLong,
+    #[msg("This registry identity is already bound to a different agent")]
+    IdentityBoundToAnotherAgent,
+    #[msg("Solana registry bindings must pass the core asset account")]
+    MissingRegistryAsset,
+    #[msg("Solana registry record ID must be a core asset pubkey")]
+    InvalidRegistryAssetId,
+    #[msg("Registry asset account does not match the canonical agent ID")]
+    RegistryAssetMismatch,
+    #[msg("Registry asset account is not a Metaplex Core asset")]
+    InvalidRegistryAsset,
+    #[msg("Agent authority does not own the registry asset")]
+    RegistryAssetNotControlled,
+    #[msg("Solana registry bindings must name this deployment's chain")]
+    RegistryChainMismatch,
+    #[msg("No Solana Agent Registry is configured for identity bindings")]
+    AgentRegistryNotConfigured,
+    #[msg("Canonical agent ID names a registry other than the configured one")]
+    UnknownAgentRegistry,
+    #[msg("Registry asset is not in the configured registry collection")]
+    RegistryAssetOutsideCollection,
+}
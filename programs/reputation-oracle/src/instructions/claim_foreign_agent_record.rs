@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{emit_reputation_score_changed, ForeignAgentRecordClaimed, EVENT_VERSION};
+use crate::instructions::account_migration::MigratableAccount;
+use crate::state::{
+    AgentIdentityBinding, AgentProfile, ForeignAgentRecord, ReputationConfig, Vouch,
+};
+
+/// Links a foreign record to the Solana profile that proved control of the same
+/// canonical ID. The record's existing backing moves into the profile's score and
+/// leaves it again as those vouchers revoke; new backing goes to the profile directly.
+/// A profile cannot claim a record it still backs itself: its own stake would start
+/// counting toward its own score.
+#[derive(Accounts)]
+pub struct ClaimForeignAgentRecord<'info> {
+    #[account(
+        mut,
+        seeds = [b"foreign_agent", ForeignAgentRecord::seed_hash(&foreign_agent.canonical_agent_id).as_ref()],
+        bump = foreign_agent.bump,
+        constraint = !foreign_agent.is_claimed() @ ClaimForeignAgentRecordError::ForeignAgentAlreadyClaimed
+    )]
+    pub foreign_agent: Account<'info, ForeignAgentRecord>,
+
+    #[account(
+        seeds = [b"identity_binding", ForeignAgentRecord::seed_hash(&foreign_agent.canonical_agent_id).as_ref()],
+        bump = identity_binding.bump,
+        constraint = identity_binding.agent == agent_profile.key() @ ClaimForeignAgentRecordError::BindingAgentMismatch,
+        constraint = identity_binding.is_attested() @ ClaimForeignAgentRecordError::BindingNotAttested
+    )]
+    pub identity_binding: Account<'info, AgentIdentityBinding>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ ClaimForeignAgentRecordError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: The claimant's own vouch on the record, which usually does not exist.
+    /// Deriving it here keeps a caller from hiding a live one.
+    #[account(
+        seeds = [b"vouch", agent_profile.key().as_ref(), foreign_agent.key().as_ref()],
+        bump
+    )]
+    pub claimant_vouch: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ClaimForeignAgentRecord>) -> Result<()> {
+    let clock = Clock::get()?;
+    let claimant_vouch = ctx.accounts.claimant_vouch.to_account_info();
+    if claimant_vouch.owner == ctx.program_id && !claimant_vouch.data_is_empty() {
+        let (vouch, _) = Vouch::parse_layout(&claimant_vouch.try_borrow_data()?, clock.unix_timestamp)?;
+        require!(
+            !vouch.status.is_live(),
+            ClaimForeignAgentRecordError::ClaimantVouchLive
+        );
+    }
+
+    let foreign_agent = &mut ctx.accounts.foreign_agent;
+    foreign_agent.claimed_by = ctx.accounts.agent_profile.key();
+    foreign_agent.claimed_at = clock.unix_timestamp;
+
+    let config = &ctx.accounts.config;
+    let agent_profile = &mut ctx.accounts.agent_profile;
+    agent_profile.absorb_claimed_foreign_record(foreign_agent);
+    let previous_score = agent_profile.reputation_score;
+    agent_profile.reputation_score = agent_profile.compute_reputation(config);
+    emit_reputation_score_changed(
+        config,
+        agent_profile.key(),
+        previous_score,
+        agent_profile.reputation_score,
+    )?;
+
+    emit!(ForeignAgentRecordClaimed {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        record: foreign_agent.key(),
+        agent: foreign_agent.claimed_by,
+        binding: ctx.accounts.identity_binding.key(),
+        canonical_agent_id: foreign_agent.canonical_agent_id.clone(),
+        total_vouches_received: foreign_agent.total_vouches_received,
+        total_staked_for: foreign_agent.total_staked_for,
+        reputation_score: foreign_agent.reputation_score,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ClaimForeignAgentRecordError {
+    #[msg("Foreign agent record has already been claimed")]
+    ForeignAgentAlreadyClaimed,
+    #[msg("Identity binding belongs to a different agent")]
+    BindingAgentMismatch,
+    #[msg("Identity binding must be verified or attested before it can claim a foreign record")]
+    BindingNotAttested,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+    #[msg("Revoke the agent's own vouch on the record before claiming it")]
+    ClaimantVouchLive,
+}
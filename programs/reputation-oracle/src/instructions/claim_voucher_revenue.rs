@@ -1,12 +1,12 @@
 use anchor_lang::prelude::*;
-use crate::state::{SkillListing, Vouch, VouchStatus, AgentProfile};
-use crate::events::RevenueClaimed;
+use crate::state::{SkillListing, Vouch, VouchStatus, AgentProfile, ReputationConfig};
+use crate::events::{RevenueClaimed, EVENT_VERSION};
 
 #[derive(Accounts)]
 pub struct ClaimVoucherRevenue<'info> {
     #[account(
         mut,
-        constraint = skill_listing.author == author_profile.authority @ ClaimError::AuthorMismatch,
+        constraint = skill_listing.author == author_profile.agent_id @ ClaimError::AuthorMismatch,
     )]
     pub skill_listing: Account<'info, SkillListing>,
 
@@ -20,8 +20,9 @@ pub struct ClaimVoucherRevenue<'info> {
     pub vouch: Account<'info, Vouch>,
 
     #[account(
-        seeds = [b"agent", voucher.key().as_ref()],
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
         bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ ClaimError::UnauthorizedAgentAuthority,
     )]
     pub voucher_profile: Account<'info, AgentProfile>,
 
@@ -31,24 +32,46 @@ pub struct ClaimVoucherRevenue<'info> {
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
     #[account(mut)]
     pub voucher: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ClaimVoucherRevenue>) -> Result<()> {
-    let vouch = &ctx.accounts.vouch;
+pub(crate) fn handler(ctx: Context<ClaimVoucherRevenue>) -> Result<()> {
+    settle_voucher_claim(
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.vouch,
+        &ctx.accounts.author_profile,
+        &ctx.accounts.voucher.to_account_info(),
+        &ctx.accounts.config,
+    )
+}
 
+/// Pays a vouch its proportional share of the listing's voucher pool. Shared by
+/// wallet claims and session-key claims.
+pub(crate) fn settle_voucher_claim<'info>(
+    skill_listing: &mut Account<'info, SkillListing>,
+    vouch: &mut Account<'info, Vouch>,
+    author_profile: &Account<'info, AgentProfile>,
+    recipient: &AccountInfo<'info>,
+    config: &Account<'info, ReputationConfig>,
+) -> Result<()> {
     require!(
         vouch.status == VouchStatus::Active,
         ClaimError::VouchNotEligible
     );
 
-    let unclaimed = ctx.accounts.skill_listing.unclaimed_voucher_revenue;
+    let unclaimed = skill_listing.unclaimed_voucher_revenue;
     require!(unclaimed > 0, ClaimError::NothingToClaim);
 
-    let total_staked = ctx.accounts.author_profile.total_staked_for;
+    let total_staked = author_profile.total_staked_for;
     require!(total_staked > 0, ClaimError::NoStakeForAuthor);
 
     // Proportional share: unclaimed * stake_amount / total_staked_for
@@ -60,30 +83,30 @@ pub fn handler(ctx: Context<ClaimVoucherRevenue>) -> Result<()> {
 
     require!(share > 0, ClaimError::ShareTooSmall);
 
-    // Transfer lamports from skill_listing PDA to voucher
-    **ctx.accounts.skill_listing.to_account_info().try_borrow_mut_lamports()? = ctx
-        .accounts.skill_listing.to_account_info().lamports()
+    // Transfer lamports from skill_listing PDA to the recipient
+    **skill_listing.to_account_info().try_borrow_mut_lamports()? = skill_listing
+        .to_account_info().lamports()
         .checked_sub(share)
         .ok_or(ClaimError::InsufficientFunds)?;
-    **ctx.accounts.voucher.to_account_info().try_borrow_mut_lamports()? = ctx
-        .accounts.voucher.to_account_info().lamports()
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
         .checked_add(share)
         .ok_or(ClaimError::InsufficientFunds)?;
 
     // Update tracking
-    let skill_listing = &mut ctx.accounts.skill_listing;
     skill_listing.unclaimed_voucher_revenue = skill_listing.unclaimed_voucher_revenue
         .checked_sub(share).unwrap();
 
     let clock = Clock::get()?;
-    let vouch = &mut ctx.accounts.vouch;
     vouch.cumulative_revenue = vouch.cumulative_revenue.checked_add(share).unwrap();
     vouch.last_payout_at = clock.unix_timestamp;
 
     emit!(RevenueClaimed {
-        skill_listing: ctx.accounts.skill_listing.key(),
-        vouch: ctx.accounts.vouch.key(),
-        voucher: ctx.accounts.voucher.key(),
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        skill_listing: skill_listing.key(),
+        vouch: vouch.key(),
+        voucher: recipient.key(),
         amount: share,
         timestamp: clock.unix_timestamp,
     });
@@ -107,4 +130,6 @@ pub enum ClaimError {
     ShareTooSmall,
     #[msg("Insufficient funds in skill listing")]
     InsufficientFunds,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
 }
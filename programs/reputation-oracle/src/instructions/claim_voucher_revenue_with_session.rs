@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::claim_voucher_revenue::{settle_voucher_claim, ClaimError};
+use crate::state::{
+    AgentProfile, AgentSessionKey, ReputationConfig, SkillListing, Vouch, SESSION_SCOPE_CLAIM,
+};
+
+/// Claims voucher revenue for an agent using a scoped session key. Proceeds always
+/// go to the agent's current authority wallet, never to the session signer.
+#[derive(Accounts)]
+pub struct ClaimVoucherRevenueWithSession<'info> {
+    #[account(
+        mut,
+        constraint = skill_listing.author == author_profile.agent_id @ ClaimError::AuthorMismatch,
+    )]
+    pub skill_listing: Account<'info, SkillListing>,
+
+    #[account(
+        mut,
+        seeds = [b"vouch", voucher_profile.key().as_ref(), author_profile.key().as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.voucher == voucher_profile.key() @ ClaimError::VouchMismatch,
+        constraint = vouch.vouchee == author_profile.key() @ ClaimError::VouchMismatch,
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+    )]
+    pub voucher_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"agent", skill_listing.author.as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"session_key", voucher_profile.key().as_ref(), session_signer.key().as_ref()],
+        bump = agent_session_key.bump,
+        constraint = agent_session_key.is_issued_by_current_authority(&voucher_profile) @ ClaimVoucherRevenueWithSessionError::SessionKeyAuthorityRotated,
+    )]
+    pub agent_session_key: Account<'info, AgentSessionKey>,
+
+    pub session_signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    /// CHECK: Revenue recipient, pinned to the voucher profile's current authority.
+    #[account(mut, address = voucher_profile.authority @ ClaimVoucherRevenueWithSessionError::RecipientNotAuthority)]
+    pub voucher_authority: UncheckedAccount<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ClaimVoucherRevenueWithSession>) -> Result<()> {
+    require!(
+        ctx.accounts
+            .agent_session_key
+            .allows(SESSION_SCOPE_CLAIM, Clock::get()?.unix_timestamp),
+        ClaimVoucherRevenueWithSessionError::SessionKeyNotAuthorized
+    );
+
+    settle_voucher_claim(
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.vouch,
+        &ctx.accounts.author_profile,
+        &ctx.accounts.voucher_authority.to_account_info(),
+        &ctx.accounts.config,
+    )
+}
+
+#[error_code]
+pub enum ClaimVoucherRevenueWithSessionError {
+    #[msg("Session key is expired or lacks the claim scope")]
+    SessionKeyNotAuthorized,
+    #[msg("Revenue recipient must be the voucher's authority wallet")]
+    RecipientNotAuthority,
+    #[msg("Session key was issued by an authority the agent has since rotated away from")]
+    SessionKeyAuthorityRotated,
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentProfileClosed, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig};
+
+/// Deregisters an agent and refunds the profile rent. Only allowed once nothing on
+/// chain still depends on the profile's counters, and only once those counters are
+/// verified: a profile migrated from an older layout may undercount its paid listings
+/// until `reconcile_agent_profile` tallies them.
+#[derive(Accounts)]
+pub struct CloseAgentProfile<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ CloseAgentProfileError::NotAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseAgentProfile>) -> Result<()> {
+    let agent_profile = &ctx.accounts.agent_profile;
+
+    require!(
+        agent_profile.counters_verified,
+        CloseAgentProfileError::CountersNotVerified
+    );
+    require!(
+        agent_profile.total_vouches_given == 0,
+        CloseAgentProfileError::LiveVouchesGiven
+    );
+    require!(
+        agent_profile.total_vouches_received == 0,
+        CloseAgentProfileError::LiveVouchesReceived
+    );
+    require!(
+        agent_profile.author_bond_lamports == 0,
+        CloseAgentProfileError::AuthorBondOutstanding
+    );
+    require!(
+        agent_profile.active_skill_listings == 0 && agent_profile.active_free_skill_listings == 0,
+        CloseAgentProfileError::ActiveSkillListings
+    );
+    require!(
+        agent_profile.open_author_disputes == 0,
+        CloseAgentProfileError::AuthorDisputesOpen
+    );
+    // Settled vouches still name the profile's PDA; a profile re-registered there
+    // would start with account counts that leave them out.
+    require!(
+        agent_profile.vouch_accounts_given == 0 && agent_profile.vouch_accounts_received == 0,
+        CloseAgentProfileError::VouchAccountsOpen
+    );
+    // Revoking a vouch on a claimed foreign record updates the claimant, so the
+    // profile has to stay until every such vouch is gone.
+    require!(
+        !agent_profile.holds_claimed_foreign_backing(),
+        CloseAgentProfileError::ClaimedForeignBackingOpen
+    );
+
+    emit!(AgentProfileClosed {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_profile.key(),
+        authority: agent_profile.authority,
+        reputation_score: agent_profile.reputation_score,
+        registered_at: agent_profile.registered_at,
+        last_upheld_dispute_at: agent_profile.last_upheld_dispute_at,
+        rent_refunded: agent_profile.to_account_info().lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum CloseAgentProfileError {
+    #[msg("Only the profile authority can close this agent profile")]
+    NotAuthority,
+    #[msg("Agent still has live vouches given")]
+    LiveVouchesGiven,
+    #[msg("Agent still has live vouches received")]
+    LiveVouchesReceived,
+    #[msg("Withdraw the author bond before closing the profile")]
+    AuthorBondOutstanding,
+    #[msg("Remove every skill listing before closing the profile")]
+    ActiveSkillListings,
+    #[msg("Agent profile cannot be closed while author disputes are open")]
+    AuthorDisputesOpen,
+    #[msg("Reconcile the migrated profile's counters before closing it")]
+    CountersNotVerified,
+    #[msg("Every vouch account the agent gave or received must be closed first")]
+    VouchAccountsOpen,
+    #[msg("Vouches on a claimed foreign record must be revoked before closing the profile")]
+    ClaimedForeignBackingOpen,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::close_purchase::settle_receipt_close;
+use crate::state::{AgentProfile, Purchase, ReputationConfig};
+
+/// Closes a receipt recorded under an agent's `agent_id` by a session-key purchase.
+/// Once the agent rotates its authority no wallet signs as `agent_id` any more, so
+/// the current owner closes it instead and receives the rent, under the same dispute
+/// checks as `close_purchase`.
+#[derive(Accounts)]
+pub struct CloseAgentPurchase<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"purchase", agent_profile.agent_id.as_ref(), purchase.skill_listing.as_ref()],
+        bump = purchase.bump,
+        constraint = purchase.buyer == agent_profile.agent_id @ CloseAgentPurchaseError::NotAgentReceipt
+    )]
+    pub purchase: Account<'info, Purchase>,
+
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ CloseAgentPurchaseError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: Matched against the receipt and checked like in `close_purchase`.
+    #[account(address = purchase.skill_listing @ CloseAgentPurchaseError::SkillListingMismatch)]
+    pub skill_listing: UncheckedAccount<'info>,
+
+    pub author_profile: Option<Account<'info, AgentProfile>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseAgentPurchase>) -> Result<()> {
+    settle_receipt_close(
+        ctx.program_id,
+        &ctx.accounts.purchase,
+        &ctx.accounts.skill_listing,
+        ctx.accounts.author_profile.as_ref(),
+        &ctx.accounts.config,
+    )
+}
+
+#[error_code]
+pub enum CloseAgentPurchaseError {
+    #[msg("Purchase was not recorded under this agent's ID")]
+    NotAgentReceipt,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+    #[msg("Skill listing does not match the purchase")]
+    SkillListingMismatch,
+}
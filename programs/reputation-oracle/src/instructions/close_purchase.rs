@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{PurchaseClosed, EVENT_VERSION};
+use crate::state::{AgentProfile, Purchase, SkillListing, ReputationConfig};
+
+/// Closes a purchase receipt and refunds its rent to the buyer once the dispute
+/// window has passed and the author has no open disputes that could cite it.
+#[derive(Accounts)]
+pub struct ClosePurchase<'info> {
+    #[account(
+        mut,
+        close = buyer,
+        seeds = [b"purchase", buyer.key().as_ref(), purchase.skill_listing.as_ref()],
+        bump = purchase.bump,
+        constraint = purchase.buyer == buyer.key() @ ClosePurchaseError::NotBuyer
+    )]
+    pub purchase: Account<'info, Purchase>,
+
+    /// CHECK: Matched against the receipt. A closed listing can no longer be disputed,
+    /// so the author check below only runs while the listing still exists.
+    #[account(address = purchase.skill_listing @ ClosePurchaseError::SkillListingMismatch)]
+    pub skill_listing: UncheckedAccount<'info>,
+
+    pub author_profile: Option<Account<'info, AgentProfile>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ClosePurchase>) -> Result<()> {
+    settle_receipt_close(
+        ctx.program_id,
+        &ctx.accounts.purchase,
+        &ctx.accounts.skill_listing,
+        ctx.accounts.author_profile.as_ref(),
+        &ctx.accounts.config,
+    )
+}
+
+/// Checks that no dispute can still cite the receipt and emits `PurchaseClosed`; the
+/// caller's `close` constraint refunds the rent. Shared with `close_agent_purchase`.
+pub(crate) fn settle_receipt_close<'info>(
+    program_id: &Pubkey,
+    purchase: &Account<'info, Purchase>,
+    skill_listing: &UncheckedAccount<'info>,
+    author_profile: Option<&Account<'info, AgentProfile>>,
+    config: &Account<'info, ReputationConfig>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp
+            >= purchase
+                .purchased_at
+                .saturating_add(Purchase::DISPUTE_WINDOW_SECONDS),
+        ClosePurchaseError::DisputeWindowOpen
+    );
+
+    let skill_listing_info = skill_listing.to_account_info();
+    if !skill_listing_info.data_is_empty() {
+        require_keys_eq!(
+            *skill_listing_info.owner,
+            crate::ID,
+            ClosePurchaseError::SkillListingMismatch
+        );
+        let skill_listing =
+            SkillListing::try_deserialize(&mut &skill_listing_info.try_borrow_data()?[..])?;
+        let author_profile = author_profile.ok_or(ClosePurchaseError::MissingAuthorProfile)?;
+        let (expected_author_profile, _) = Pubkey::find_program_address(
+            &[b"agent", skill_listing.author.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(
+            author_profile.key(),
+            expected_author_profile,
+            ClosePurchaseError::AuthorProfileMismatch
+        );
+        // Disputes are author-wide, so any open one may still reference this receipt.
+        require!(
+            author_profile.open_author_disputes == 0,
+            ClosePurchaseError::AuthorDisputesOpen
+        );
+    }
+
+    emit!(PurchaseClosed {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        purchase: purchase.key(),
+        buyer: purchase.buyer,
+        skill_listing: purchase.skill_listing,
+        price_paid: purchase.price_paid,
+        purchased_at: purchase.purchased_at,
+        rent_refunded: purchase.to_account_info().lamports(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ClosePurchaseError {
+    #[msg("Only the buyer can close this purchase")]
+    NotBuyer,
+    #[msg("Skill listing does not match the purchase")]
+    SkillListingMismatch,
+    #[msg("Purchase is still inside its dispute window")]
+    DisputeWindowOpen,
+    #[msg("The author's profile is required while the skill listing exists")]
+    MissingAuthorProfile,
+    #[msg("Author profile does not match the skill listing author")]
+    AuthorProfileMismatch,
+    #[msg("Purchase cannot be closed while the author has open disputes")]
+    AuthorDisputesOpen,
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::{SkillListing, SkillStatus, AgentProfile};
+use crate::events::{SkillListingClosed, EVENT_VERSION};
+use crate::state::{SkillListing, SkillStatus, AgentProfile, ReputationConfig};
 
 /// Permanently closes a skill listing PDA and reclaims rent lamports.
 /// Requires the listing is already Removed and has no pending voucher revenue.
@@ -8,9 +9,9 @@ use crate::state::{SkillListing, SkillStatus, AgentProfile};
 pub struct CloseSkillListing<'info> {
     #[account(
         mut,
-        seeds = [b"skill", author.key().as_ref(), skill_id.as_bytes()],
+        seeds = [b"skill", author_profile.agent_id.as_ref(), skill_id.as_bytes()],
         bump = skill_listing.bump,
-        constraint = skill_listing.author == author.key() @ CloseSkillError::NotAuthor,
+        constraint = skill_listing.author == author_profile.agent_id @ CloseSkillError::NotAuthor,
         constraint = skill_listing.status == SkillStatus::Removed @ CloseSkillError::NotRemoved,
         constraint = skill_listing.unclaimed_voucher_revenue == 0 @ CloseSkillError::UnclaimedRevenue,
         close = author,
@@ -18,16 +19,32 @@ pub struct CloseSkillListing<'info> {
     pub skill_listing: Account<'info, SkillListing>,
 
     #[account(
-        seeds = [b"agent", author.key().as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump,
+        constraint = author_profile.is_owner(&author.key()) @ CloseSkillError::UnauthorizedAgentAuthority,
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
     #[account(mut)]
     pub author: Signer<'info>,
 }
 
-pub fn handler(_ctx: Context<CloseSkillListing>, _skill_id: String) -> Result<()> {
+pub(crate) fn handler(ctx: Context<CloseSkillListing>, _skill_id: String) -> Result<()> {
+    emit!(SkillListingClosed {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        skill_listing: ctx.accounts.skill_listing.key(),
+        author: ctx.accounts.author_profile.agent_id,
+        rent_refunded: ctx.accounts.skill_listing.to_account_info().lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     Ok(())
 }
 
@@ -39,4 +56,6 @@ pub enum CloseSkillError {
     NotRemoved,
     #[msg("Listing has unclaimed voucher revenue; claim it before closing")]
     UnclaimedRevenue,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
 }
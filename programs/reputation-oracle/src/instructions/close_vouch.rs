@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{VouchClosed, EVENT_VERSION};
+use crate::instructions::account_migration::{is_current_layout, write_account};
+use crate::state::{AgentProfile, Vouch, ReputationConfig};
+
+#[derive(Accounts)]
+pub struct CloseVouch<'info> {
+    #[account(
+        mut,
+        close = voucher,
+        seeds = [b"vouch", voucher_profile.key().as_ref(), vouch.vouchee.as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.voucher == voucher_profile.key() @ CloseVouchError::UnauthorizedVouchClose,
+        constraint = vouch.is_closable() @ CloseVouchError::VouchNotClosable
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ CloseVouchError::UnauthorizedAgentAuthority
+    )]
+    pub voucher_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: The vouchee, an agent profile or a foreign agent record. Only a profile in
+    /// the current layout tracks its vouch accounts, so only such a profile is written;
+    /// older profiles get their counts from `reconcile_agent_profile` after migrating.
+    #[account(mut, address = vouch.vouchee)]
+    pub vouchee: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<CloseVouch>) -> Result<()> {
+    let vouch = &ctx.accounts.vouch;
+
+    // Live counters already excluded this vouch when it was revoked or slashed; only
+    // the account counts that prove a sync complete change on close.
+    let voucher_profile = &mut ctx.accounts.voucher_profile;
+    voucher_profile.vouch_accounts_given = voucher_profile.vouch_accounts_given.saturating_sub(1);
+    let vouchee = ctx.accounts.vouchee.to_account_info();
+    if vouchee.owner == ctx.program_id
+        && is_current_layout::<AgentProfile>(&vouchee.try_borrow_data()?)
+    {
+        let mut vouchee_profile = AgentProfile::try_deserialize(&mut &vouchee.try_borrow_data()?[..])?;
+        vouchee_profile.vouch_accounts_received =
+            vouchee_profile.vouch_accounts_received.saturating_sub(1);
+        write_account(&vouchee, &vouchee_profile)?;
+    }
+
+    emit!(VouchClosed {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        vouch: vouch.key(),
+        voucher: vouch.voucher,
+        vouchee: vouch.vouchee,
+        final_status: vouch.status,
+        cumulative_revenue: vouch.cumulative_revenue,
+        created_at: vouch.created_at,
+        last_payout_at: vouch.last_payout_at,
+        rent_refunded: vouch.to_account_info().lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum CloseVouchError {
+    #[msg("Only the original voucher can close a vouch")]
+    UnauthorizedVouchClose,
+    #[msg("Vouch must be revoked, or slashed with its remainder withdrawn, and free of pending dispute links")]
+    VouchNotClosable,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+}
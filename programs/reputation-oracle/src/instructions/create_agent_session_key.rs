@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentSessionKeyCreated, EVENT_VERSION};
+use crate::state::{AgentProfile, AgentSessionKey, ReputationConfig};
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateAgentSessionKey<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = AgentSessionKey::LEN,
+        seeds = [b"session_key", agent_profile.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub agent_session_key: Account<'info, AgentSessionKey>,
+
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ CreateAgentSessionKeyError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<CreateAgentSessionKey>,
+    session_key: Pubkey,
+    scopes: u8,
+    spend_cap_lamports: u64,
+    expires_at: i64,
+) -> Result<()> {
+    require!(
+        AgentSessionKey::is_valid_scope_mask(scopes),
+        CreateAgentSessionKeyError::InvalidScopes
+    );
+    require_keys_neq!(
+        session_key,
+        ctx.accounts.authority.key(),
+        CreateAgentSessionKeyError::SessionKeyIsAuthority
+    );
+
+    let clock = Clock::get()?;
+    require!(
+        expires_at > clock.unix_timestamp,
+        CreateAgentSessionKeyError::ExpiryInPast
+    );
+
+    let agent_session_key = &mut ctx.accounts.agent_session_key;
+    agent_session_key.agent = ctx.accounts.agent_profile.key();
+    agent_session_key.session_key = session_key;
+    agent_session_key.scopes = scopes;
+    agent_session_key.spend_cap_lamports = spend_cap_lamports;
+    agent_session_key.spent_lamports = 0;
+    agent_session_key.expires_at = expires_at;
+    agent_session_key.created_at = clock.unix_timestamp;
+    agent_session_key.bump = ctx.bumps.agent_session_key;
+    agent_session_key.authority = ctx.accounts.authority.key();
+
+    emit!(AgentSessionKeyCreated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_session_key.agent,
+        session_key,
+        scopes,
+        spend_cap_lamports,
+        expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum CreateAgentSessionKeyError {
+    #[msg("Only the agent authority can create session keys")]
+    UnauthorizedAgentAuthority,
+    #[msg("Session key scopes must be a non-empty combination of known scopes")]
+    InvalidScopes,
+    #[msg("The agent authority cannot be its own session key")]
+    SessionKeyIsAuthority,
+    #[msg("Session key expiry must be in the future")]
+    ExpiryInPast,
+}
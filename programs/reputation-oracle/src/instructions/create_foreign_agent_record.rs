@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{ForeignAgentRecordCreated, EVENT_VERSION};
+use crate::identity::{parse_canonical_agent_id, SOLANA_NAMESPACE};
+use crate::state::{ForeignAgentRecord, ReputationConfig};
+
+#[derive(Accounts)]
+#[instruction(canonical_agent_id: String)]
+pub struct CreateForeignAgentRecord<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = ForeignAgentRecord::LEN,
+        seeds = [b"foreign_agent", ForeignAgentRecord::seed_hash(&canonical_agent_id).as_ref()],
+        bump
+    )]
+    pub foreign_agent: Account<'info, ForeignAgentRecord>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<CreateForeignAgentRecord>, canonical_agent_id: String) -> Result<()> {
+    let parsed = parse_canonical_agent_id(&canonical_agent_id)
+        .ok_or(CreateForeignAgentRecordError::InvalidCanonicalAgentId)?;
+    // Solana agents register a profile of their own instead of a placeholder.
+    require!(
+        parsed.namespace != SOLANA_NAMESPACE,
+        CreateForeignAgentRecordError::SolanaAgentNotForeign
+    );
+    let chain_context = parsed.chain_id.to_string();
+
+    let clock = Clock::get()?;
+    let foreign_agent = &mut ctx.accounts.foreign_agent;
+    foreign_agent.canonical_agent_id = canonical_agent_id;
+    foreign_agent.chain_context = chain_context;
+    foreign_agent.created_by = ctx.accounts.payer.key();
+    foreign_agent.created_at = clock.unix_timestamp;
+    foreign_agent.reputation_score = 0;
+    foreign_agent.total_vouches_received = 0;
+    foreign_agent.total_staked_for = 0;
+    foreign_agent.vouched_reputation_total = 0;
+    foreign_agent.effective_vouch_weight = 0;
+    foreign_agent.low_reputation_vouches_received = 0;
+    foreign_agent.claimed_by = Pubkey::default();
+    foreign_agent.claimed_at = 0;
+    foreign_agent.bump = ctx.bumps.foreign_agent;
+
+    emit!(ForeignAgentRecordCreated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        record: foreign_agent.key(),
+        canonical_agent_id: foreign_agent.canonical_agent_id.clone(),
+        agent_chain_context: foreign_agent.chain_context.clone(),
+        created_by: foreign_agent.created_by,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum CreateForeignAgentRecordError {
+    #[msg("Canonical agent ID must look like <caip2-chain-id>:<registry>#<recordId>")]
+    InvalidCanonicalAgentId,
+    #[msg("Solana agents must register an AgentProfile instead of a foreign record")]
+    SolanaAgentNotForeign,
+}
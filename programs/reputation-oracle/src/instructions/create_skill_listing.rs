@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::state::{
     find_author_bond_pda, AgentProfile, AuthorBond, ReputationConfig, SkillListing, SkillStatus,
 };
-use crate::events::SkillListingCreated;
+use crate::events::{SkillListingCreated, EVENT_VERSION};
 
 #[derive(Accounts)]
 #[instruction(skill_id: String)]
@@ -11,15 +11,16 @@ pub struct CreateSkillListing<'info> {
         init,
         payer = author,
         space = SkillListing::SPACE,
-        seeds = [b"skill", author.key().as_ref(), skill_id.as_bytes()],
+        seeds = [b"skill", author_profile.agent_id.as_ref(), skill_id.as_bytes()],
         bump
     )]
     pub skill_listing: Account<'info, SkillListing>,
     
     #[account(
         mut,
-        seeds = [b"agent", author.key().as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump,
+        constraint = author_profile.can_operate(&author.key()) @ CreateSkillError::UnauthorizedAgentOperator,
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
@@ -37,13 +38,42 @@ pub struct CreateSkillListing<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<CreateSkillListing>,
     _skill_id: String,
     skill_uri: String,
     name: String,
     description: String,
     price_lamports: u64,
+) -> Result<()> {
+    open_listing(
+        ctx.program_id,
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.author_profile,
+        &ctx.accounts.config,
+        ctx.accounts.author_bond.as_ref(),
+        ctx.bumps.skill_listing,
+        skill_uri,
+        name,
+        description,
+        price_lamports,
+    )
+}
+
+/// Validates and writes a new listing and counts it on the author's profile. Shared
+/// by the owner/delegate path and the session-key path.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_listing<'info>(
+    program_id: &Pubkey,
+    skill_listing: &mut Account<'info, SkillListing>,
+    author_profile: &mut Account<'info, AgentProfile>,
+    config: &Account<'info, ReputationConfig>,
+    author_bond: Option<&Account<'info, AuthorBond>>,
+    skill_listing_bump: u8,
+    skill_uri: String,
+    name: String,
+    description: String,
+    price_lamports: u64,
 ) -> Result<()> {
     require!(
         skill_uri.len() <= SkillListing::MAX_URI_LEN,
@@ -64,18 +94,17 @@ pub fn handler(
 
     if SkillListing::is_free_price(price_lamports) {
         validate_free_listing_bond(
-            ctx.program_id,
-            &ctx.accounts.author.key(),
-            &ctx.accounts.author_profile,
-            &ctx.accounts.config,
-            ctx.accounts.author_bond.as_ref(),
+            program_id,
+            &author_profile.agent_id,
+            author_profile,
+            config,
+            author_bond,
         )?;
     }
     
-    let skill_listing = &mut ctx.accounts.skill_listing;
     let clock = Clock::get()?;
     
-    skill_listing.author = ctx.accounts.author.key();
+    skill_listing.author = author_profile.agent_id;
     skill_listing.skill_uri = skill_uri;
     skill_listing.name = name.clone();
     skill_listing.description = description;
@@ -86,20 +115,25 @@ pub fn handler(
     skill_listing.created_at = clock.unix_timestamp;
     skill_listing.updated_at = clock.unix_timestamp;
     skill_listing.status = SkillStatus::Active;
-    skill_listing.bump = ctx.bumps.skill_listing;
+    skill_listing.bump = skill_listing_bump;
+    skill_listing.version = SkillListing::CURRENT_VERSION;
 
     if SkillListing::is_free_price(price_lamports) {
-        ctx.accounts.author_profile.active_free_skill_listings = ctx
-            .accounts
-            .author_profile
+        author_profile.active_free_skill_listings = author_profile
             .active_free_skill_listings
             .checked_add(1)
             .ok_or(CreateSkillError::FreeListingCountOverflow)?;
     }
+    author_profile.active_skill_listings = author_profile
+        .active_skill_listings
+        .checked_add(1)
+        .ok_or(CreateSkillError::ListingCountOverflow)?;
     
     emit!(SkillListingCreated {
-        skill_listing: ctx.accounts.skill_listing.key(),
-        author: ctx.accounts.author.key(),
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        skill_listing: skill_listing.key(),
+        author: author_profile.agent_id,
         name,
         price_lamports,
         timestamp: clock.unix_timestamp,
@@ -161,4 +195,8 @@ pub enum CreateSkillError {
     AuthorBondProfileMismatch,
     #[msg("Active free listing count overflowed")]
     FreeListingCountOverflow,
+    #[msg("Active listing count overflowed")]
+    ListingCountOverflow,
+    #[msg("Signer is neither the agent authority nor its delegate")]
+    UnauthorizedAgentOperator,
 }
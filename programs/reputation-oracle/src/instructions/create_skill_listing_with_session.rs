@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{SessionKeySpend, EVENT_VERSION};
+use crate::instructions::create_skill_listing::open_listing;
+use crate::state::{
+    AgentProfile, AgentSessionKey, AuthorBond, ReputationConfig, SkillListing,
+    SESSION_SCOPE_LIST,
+};
+
+/// Creates a skill listing for an agent using a scoped session key. The session wallet
+/// pays the listing rent, which is charged against the session key's spend cap.
+#[derive(Accounts)]
+#[instruction(skill_id: String)]
+pub struct CreateSkillListingWithSession<'info> {
+    #[account(
+        init,
+        payer = session_signer,
+        space = SkillListing::SPACE,
+        seeds = [b"skill", author_profile.agent_id.as_ref(), skill_id.as_bytes()],
+        bump
+    )]
+    pub skill_listing: Account<'info, SkillListing>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"session_key", author_profile.key().as_ref(), session_signer.key().as_ref()],
+        bump = agent_session_key.bump,
+        constraint = agent_session_key.is_issued_by_current_authority(&author_profile) @ CreateSkillListingWithSessionError::SessionKeyAuthorityRotated,
+    )]
+    pub agent_session_key: Account<'info, AgentSessionKey>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub author_bond: Option<Account<'info, AuthorBond>>,
+
+    #[account(mut)]
+    pub session_signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<CreateSkillListingWithSession>,
+    _skill_id: String,
+    skill_uri: String,
+    name: String,
+    description: String,
+    price_lamports: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let rent = ctx.accounts.skill_listing.to_account_info().lamports();
+    let agent_session_key = &mut ctx.accounts.agent_session_key;
+    require!(
+        agent_session_key.allows(SESSION_SCOPE_LIST, clock.unix_timestamp),
+        CreateSkillListingWithSessionError::SessionKeyNotAuthorized
+    );
+    agent_session_key.spent_lamports = agent_session_key
+        .checked_spend(rent)
+        .ok_or(CreateSkillListingWithSessionError::SpendCapExceeded)?;
+
+    emit!(SessionKeySpend {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_session_key.agent,
+        session_key: agent_session_key.session_key,
+        amount: rent,
+        spent_lamports: agent_session_key.spent_lamports,
+        spend_cap_lamports: agent_session_key.spend_cap_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    open_listing(
+        ctx.program_id,
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.author_profile,
+        &ctx.accounts.config,
+        ctx.accounts.author_bond.as_ref(),
+        ctx.bumps.skill_listing,
+        skill_uri,
+        name,
+        description,
+        price_lamports,
+    )
+}
+
+#[error_code]
+pub enum CreateSkillListingWithSessionError {
+    #[msg("Session key is expired or lacks the list scope")]
+    SessionKeyNotAuthorized,
+    #[msg("Listing rent would exceed the session key spend cap")]
+    SpendCapExceeded,
+    #[msg("Session key was issued by an authority the agent has since rotated away from")]
+    SessionKeyAuthorityRotated,
+}
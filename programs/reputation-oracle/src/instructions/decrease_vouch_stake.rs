@@ -0,0 +1,308 @@
+use anchor_lang::prelude::*;
+use crate::state::{AgentProfile, Vouch, ReputationConfig};
+use crate::events::{emit_reputation_score_changed, VouchStakeDecreased, EVENT_VERSION};
+
+#[derive(Accounts)]
+pub struct DecreaseVouchStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"vouch", voucher_profile.key().as_ref(), vouchee_profile.key().as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.voucher == voucher_profile.key() @ DecreaseVouchStakeError::UnauthorizedStakeDecrease,
+        constraint = vouch.status.is_live() @ DecreaseVouchStakeError::VouchNotLive
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ DecreaseVouchStakeError::UnauthorizedAgentAuthority
+    )]
+    pub voucher_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", vouchee_profile.agent_id.as_ref()],
+        bump = vouchee_profile.bump
+    )]
+    pub vouchee_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<DecreaseVouchStake>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let previous_score = ctx.accounts.vouchee_profile.reputation_score;
+    let vouch_info = ctx.accounts.vouch.to_account_info();
+    let remaining_stake = decrease_stake(
+        &mut ctx.accounts.vouch,
+        &vouch_info,
+        &ctx.accounts.voucher.to_account_info(),
+        &mut ctx.accounts.vouchee_profile,
+        &ctx.accounts.config,
+        amount,
+        clock.unix_timestamp,
+    )?;
+
+    emit_reputation_score_changed(
+        &ctx.accounts.config,
+        ctx.accounts.vouchee_profile.key(),
+        previous_score,
+        ctx.accounts.vouchee_profile.reputation_score,
+    )?;
+
+    emit!(VouchStakeDecreased {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        vouch: ctx.accounts.vouch.key(),
+        voucher: ctx.accounts.voucher_profile.key(),
+        vouchee: ctx.accounts.vouchee_profile.key(),
+        amount,
+        total_stake_amount: remaining_stake,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Refunds `amount` of a live vouch's stake to the voucher and rescores the vouchee,
+/// returning the stake left on the vouch.
+pub(crate) fn decrease_stake(
+    vouch: &mut Vouch,
+    vouch_info: &AccountInfo,
+    voucher: &AccountInfo,
+    vouchee_profile: &mut AgentProfile,
+    config: &ReputationConfig,
+    amount: u64,
+    now: i64,
+) -> Result<u64> {
+    require!(amount > 0, DecreaseVouchStakeError::AmountMustBePositive);
+    require!(
+        !vouch.is_locked_by_disputes(),
+        DecreaseVouchStakeError::VouchLockedByPendingDisputes
+    );
+    // A dispute that has not linked this vouch yet may still settle against it.
+    require!(
+        vouchee_profile.open_author_disputes == 0,
+        DecreaseVouchStakeError::VouchLockedWhileDisputesOpen
+    );
+    // Measured from the latest top-up, so freshly added stake cannot leave at once.
+    require!(
+        now >= vouch.last_stake_increase_at.saturating_add(config.cooldown_period),
+        DecreaseVouchStakeError::CooldownNotElapsed
+    );
+
+    let remaining_stake = vouch
+        .stake_amount
+        .checked_sub(amount)
+        .ok_or(DecreaseVouchStakeError::InsufficientStake)?;
+    require!(
+        remaining_stake >= config.min_stake,
+        DecreaseVouchStakeError::RemainingStakeBelowMinimum
+    );
+
+    vouch.stake_amount = remaining_stake;
+
+    **vouch_info.try_borrow_mut_lamports()? = vouch_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(DecreaseVouchStakeError::InsufficientFunds)?;
+    **voucher.try_borrow_mut_lamports()? = voucher
+        .lamports()
+        .checked_add(amount)
+        .ok_or(DecreaseVouchStakeError::InsufficientFunds)?;
+
+    // Revenue claims read stake_amount against total_staked_for, so both move together.
+    vouchee_profile.total_staked_for = vouchee_profile.total_staked_for.saturating_sub(amount);
+    vouchee_profile.reputation_score = vouchee_profile.compute_reputation_at(config, now);
+    vouchee_profile.trust_tier = vouchee_profile.compute_trust_tier_at(config, now);
+
+    Ok(remaining_stake)
+}
+
+#[error_code]
+pub enum DecreaseVouchStakeError {
+    #[msg("Amount must be greater than zero")]
+    AmountMustBePositive,
+    #[msg("Only the voucher can decrease this vouch's stake")]
+    UnauthorizedStakeDecrease,
+    #[msg("Vouch is not live")]
+    VouchNotLive,
+    #[msg("Vouch stake cannot be decreased while it is linked to unsettled author disputes")]
+    VouchLockedByPendingDisputes,
+    #[msg("Vouch stake cannot be decreased while the vouchee has open author disputes")]
+    VouchLockedWhileDisputesOpen,
+    #[msg("Vouch stake cannot be decreased before the cooldown period elapses")]
+    CooldownNotElapsed,
+    #[msg("Vouch stake is lower than the requested decrease")]
+    InsufficientStake,
+    #[msg("Remaining vouch stake would fall below the minimum stake")]
+    RemainingStakeBelowMinimum,
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::{config, profile, NOW};
+    use crate::state::VouchStatus;
+
+    const STAKE: u64 = 50_000_000;
+    const MIN_STAKE: u64 = 10_000_000;
+
+    fn vouch(pending_dispute_links: u32) -> Vouch {
+        Vouch {
+            voucher: Pubkey::new_unique(),
+            vouchee: Pubkey::new_unique(),
+            stake_amount: STAKE,
+            created_at: NOW - 86_400,
+            status: VouchStatus::Active,
+            cumulative_revenue: 0,
+            last_payout_at: NOW - 86_400,
+            bump: 0,
+            voucher_reputation_snapshot: 0,
+            effective_weight_bps: 10_000,
+            low_reputation_voucher: false,
+            pending_dispute_links,
+            version: Vouch::CURRENT_VERSION,
+            last_stake_increase_at: NOW - 86_400,
+        }
+    }
+
+    /// Runs `decrease_stake` against lamport-backed vouch and voucher accounts and
+    /// returns the result with both accounts' final balances.
+    fn run(
+        vouch: &mut Vouch,
+        vouchee: &mut AgentProfile,
+        amount: u64,
+    ) -> (Result<u64>, u64, u64) {
+        let mut config = config();
+        config.min_stake = MIN_STAKE;
+        config.cooldown_period = 86_400;
+
+        let (vouch_key, voucher_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut vouch_lamports, mut voucher_lamports) = (1_000_000 + STAKE, 0);
+        let (mut vouch_data, mut voucher_data) = (Vec::new(), Vec::new());
+        let vouch_info = AccountInfo::new(
+            &vouch_key,
+            false,
+            true,
+            &mut vouch_lamports,
+            &mut vouch_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let voucher_info = AccountInfo::new(
+            &voucher_key,
+            true,
+            true,
+            &mut voucher_lamports,
+            &mut voucher_data,
+            &crate::ID,
+            false,
+            0,
+        );
+
+        let result = decrease_stake(vouch, &vouch_info, &voucher_info, vouchee, &config, amount, NOW);
+        (result, vouch_info.lamports(), voucher_info.lamports())
+    }
+
+    fn vouchee() -> AgentProfile {
+        let mut vouchee = profile(0, NOW);
+        vouchee.total_staked_for = STAKE;
+        vouchee.total_vouches_received = 1;
+        vouchee.effective_vouch_weight = 10_000;
+        vouchee
+    }
+
+    #[test]
+    fn refunds_lamports_and_moves_stake_and_score_together() {
+        let mut vouch = vouch(0);
+        let mut vouchee = vouchee();
+        let amount = 15_000_000;
+
+        let (result, vouch_lamports, voucher_lamports) = run(&mut vouch, &mut vouchee, amount);
+
+        assert_eq!(result.unwrap(), STAKE - amount);
+        assert_eq!(vouch.stake_amount, STAKE - amount);
+        assert_eq!(vouch_lamports, 1_000_000 + STAKE - amount);
+        assert_eq!(voucher_lamports, amount);
+        assert_eq!(vouchee.total_staked_for, STAKE - amount);
+        assert_eq!(vouchee.reputation_score, STAKE - amount + 100);
+    }
+
+    #[test]
+    fn rejects_decreases_below_min_stake() {
+        let mut vouch = vouch(0);
+        let mut vouchee = vouchee();
+
+        let (result, vouch_lamports, voucher_lamports) =
+            run(&mut vouch, &mut vouchee, STAKE - MIN_STAKE + 1);
+
+        assert_eq!(
+            result.unwrap_err(),
+            DecreaseVouchStakeError::RemainingStakeBelowMinimum.into()
+        );
+        assert_eq!(vouch.stake_amount, STAKE);
+        assert_eq!((vouch_lamports, voucher_lamports), (1_000_000 + STAKE, 0));
+        assert_eq!(vouchee.total_staked_for, STAKE);
+    }
+
+    #[test]
+    fn rejects_decreases_while_dispute_links_are_pending() {
+        let mut vouch = vouch(1);
+        let mut vouchee = vouchee();
+
+        let (result, _, voucher_lamports) = run(&mut vouch, &mut vouchee, 1);
+
+        assert_eq!(
+            result.unwrap_err(),
+            DecreaseVouchStakeError::VouchLockedByPendingDisputes.into()
+        );
+        assert_eq!(vouch.stake_amount, STAKE);
+        assert_eq!(voucher_lamports, 0);
+    }
+
+    #[test]
+    fn rejects_decreases_while_the_vouchee_has_open_disputes() {
+        let mut vouch = vouch(0);
+        let mut vouchee = vouchee();
+        vouchee.open_author_disputes = 1;
+
+        let (result, _, voucher_lamports) = run(&mut vouch, &mut vouchee, 1);
+
+        assert_eq!(
+            result.unwrap_err(),
+            DecreaseVouchStakeError::VouchLockedWhileDisputesOpen.into()
+        );
+        assert_eq!(vouch.stake_amount, STAKE);
+        assert_eq!(voucher_lamports, 0);
+    }
+
+    #[test]
+    fn measures_the_cooldown_from_the_latest_top_up() {
+        let mut vouch = vouch(0);
+        vouch.last_stake_increase_at = NOW - 1;
+        let mut vouchee = vouchee();
+
+        let (result, _, voucher_lamports) = run(&mut vouch, &mut vouchee, 1);
+
+        assert_eq!(
+            result.unwrap_err(),
+            DecreaseVouchStakeError::CooldownNotElapsed.into()
+        );
+        assert_eq!(vouch.stake_amount, STAKE);
+        assert_eq!(voucher_lamports, 0);
+    }
+}
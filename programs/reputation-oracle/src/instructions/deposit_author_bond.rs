@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
-use crate::events::AuthorBondDeposited;
+use crate::events::{emit_reputation_score_changed, AuthorBondDeposited, EVENT_VERSION};
 use crate::state::{AgentProfile, AuthorBond, ReputationConfig, AUTHOR_BOND_SEED};
 
 #[derive(Accounts)]
@@ -10,15 +10,16 @@ pub struct DepositAuthorBond<'info> {
         init_if_needed,
         payer = author,
         space = AuthorBond::LEN,
-        seeds = [AUTHOR_BOND_SEED, author.key().as_ref()],
+        seeds = [AUTHOR_BOND_SEED, author_profile.agent_id.as_ref()],
         bump
     )]
     pub author_bond: Account<'info, AuthorBond>,
 
     #[account(
         mut,
-        seeds = [b"agent", author.key().as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump,
+        constraint = author_profile.is_owner(&author.key()) @ DepositAuthorBondError::UnauthorizedAgentAuthority,
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
@@ -34,8 +35,8 @@ pub struct DepositAuthorBond<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<DepositAuthorBond>, amount: u64) -> Result<()> {
-    require!(amount > 0, ErrorCode::AmountMustBePositive);
+pub(crate) fn handler(ctx: Context<DepositAuthorBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, DepositAuthorBondError::AmountMustBePositive);
 
     system_program::transfer(
         CpiContext::new(
@@ -53,7 +54,7 @@ pub fn handler(ctx: Context<DepositAuthorBond>, amount: u64) -> Result<()> {
     let is_new = author_bond.is_uninitialized();
 
     if is_new {
-        author_bond.author = ctx.accounts.author.key();
+        author_bond.author = ctx.accounts.author_profile.agent_id;
         author_bond.amount = 0;
         author_bond.created_at = clock.unix_timestamp;
         author_bond.bump = ctx.bumps.author_bond;
@@ -62,19 +63,29 @@ pub fn handler(ctx: Context<DepositAuthorBond>, amount: u64) -> Result<()> {
     author_bond.amount = author_bond
         .amount
         .checked_add(amount)
-        .ok_or(ErrorCode::BondAmountOverflow)?;
+        .ok_or(DepositAuthorBondError::BondAmountOverflow)?;
     author_bond.updated_at = clock.unix_timestamp;
 
     let author_profile = &mut ctx.accounts.author_profile;
     author_profile.author_bond_lamports = author_profile
         .author_bond_lamports
         .checked_add(amount)
-        .ok_or(ErrorCode::BondAmountOverflow)?;
+        .ok_or(DepositAuthorBondError::BondAmountOverflow)?;
+    let previous_score = author_profile.reputation_score;
     author_profile.reputation_score = author_profile.compute_reputation(&ctx.accounts.config);
+    author_profile.trust_tier = author_profile.compute_trust_tier(&ctx.accounts.config);
+    emit_reputation_score_changed(
+        &ctx.accounts.config,
+        author_profile.key(),
+        previous_score,
+        author_profile.reputation_score,
+    )?;
 
     emit!(AuthorBondDeposited {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
         author_bond: author_bond.key(),
-        author: ctx.accounts.author.key(),
+        author: ctx.accounts.author_profile.agent_id,
         amount,
         total_bond_amount: author_bond.amount,
         timestamp: clock.unix_timestamp,
@@ -84,9 +95,11 @@ pub fn handler(ctx: Context<DepositAuthorBond>, amount: u64) -> Result<()> {
 }
 
 #[error_code]
-pub enum ErrorCode {
+pub enum DepositAuthorBondError {
     #[msg("Amount must be greater than zero")]
     AmountMustBePositive,
     #[msg("Author bond amount overflowed")]
     BondAmountOverflow,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
 }
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AgentProfile, ReputationConfig, TrustTier};
+
+pub const REPUTATION_ATTESTATION_VERSION: u8 = 1;
+
+/// Versioned reputation summary returned through `set_return_data`.
+///
+/// Integrators build against the `cpi` feature, invoke `get_reputation`, and read the
+/// Borsh-encoded attestation from the return value. New fields are only ever appended
+/// together with a `version` bump so older decoders can keep reading the prefix.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ReputationAttestation {
+    pub version: u8,
+    pub agent: Pubkey,
+    pub authority: Pubkey,
+    pub reputation_score: u64,
+    pub trust_tier: TrustTier,
+    pub total_staked_for: u64,
+    pub total_vouches_received: u32,
+    pub author_bond_lamports: u64,
+    pub open_author_disputes: u32,
+    pub slot: u64,
+}
+
+#[derive(Accounts)]
+pub struct GetReputation<'info> {
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+pub(crate) fn handler(ctx: Context<GetReputation>) -> Result<ReputationAttestation> {
+    let agent_profile = &ctx.accounts.agent_profile;
+    let config = &ctx.accounts.config;
+
+    // Scores are recomputed so longevity and tier reflect the current clock.
+    Ok(ReputationAttestation {
+        version: REPUTATION_ATTESTATION_VERSION,
+        agent: agent_profile.key(),
+        authority: agent_profile.authority,
+        reputation_score: agent_profile.compute_reputation(config),
+        trust_tier: agent_profile.compute_trust_tier(config),
+        total_staked_for: agent_profile.total_staked_for,
+        total_vouches_received: agent_profile.total_vouches_received,
+        author_bond_lamports: agent_profile.author_bond_lamports,
+        open_author_disputes: agent_profile.open_author_disputes,
+        slot: Clock::get()?.slot,
+    })
+}
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::events::{ConfigInitialized, EVENT_VERSION};
+use crate::identity::is_solana_caip2_chain_id;
 use crate::state::ReputationConfig;
 
 #[derive(Accounts)]
@@ -18,14 +20,20 @@ pub struct InitializeConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<InitializeConfig>,
     min_stake: u64,
     dispute_bond: u64,
     min_author_bond_for_free_listing: u64,
     slash_percentage: u8,
     cooldown_period: i64,
+    chain_context: String,
 ) -> Result<()> {
+    require!(
+        is_solana_caip2_chain_id(&chain_context),
+        InitializeConfigError::InvalidChainContext
+    );
+
     let config = &mut ctx.accounts.config;
     
     config.authority = ctx.accounts.authority.key();
@@ -39,8 +47,47 @@ pub fn handler(
     config.stake_weight = 1;
     config.vouch_weight = 100;
     config.longevity_bonus = 10;
+    config.transitive_trust_bps = ReputationConfig::DEFAULT_TRANSITIVE_TRUST_BPS;
+    config.max_trust_depth = ReputationConfig::DEFAULT_MAX_TRUST_DEPTH;
+
+    // Default verified tier thresholds
+    config.verified_min_vouchers = ReputationConfig::DEFAULT_VERIFIED_MIN_VOUCHERS;
+    config.verified_min_total_stake = ReputationConfig::DEFAULT_VERIFIED_MIN_TOTAL_STAKE;
+    config.verified_min_author_bond = ReputationConfig::DEFAULT_VERIFIED_MIN_AUTHOR_BOND;
+    config.verified_dispute_lookback = ReputationConfig::DEFAULT_VERIFIED_DISPUTE_LOOKBACK;
+    config.verified_min_account_age = ReputationConfig::DEFAULT_VERIFIED_MIN_ACCOUNT_AGE;
+
+    // Default sybil-resistance parameters
+    config.min_voucher_age = ReputationConfig::DEFAULT_MIN_VOUCHER_AGE;
+    config.min_voucher_reputation = ReputationConfig::DEFAULT_MIN_VOUCHER_REPUTATION;
+    config.full_weight_voucher_reputation = ReputationConfig::DEFAULT_FULL_WEIGHT_VOUCHER_REPUTATION;
+
+    config.set_chain_context(chain_context);
     
     config.bump = ctx.bumps.config;
+    config.version = ReputationConfig::CURRENT_VERSION;
+    config.agent_registry_program = Pubkey::default();
+    config.agent_registry_collection = Pubkey::default();
+
+    emit!(ConfigInitialized {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        config: config.key(),
+        chain_context: config.chain_context.clone(),
+        authority: config.authority,
+        min_stake,
+        dispute_bond,
+        min_author_bond_for_free_listing,
+        slash_percentage,
+        cooldown_period,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     
     Ok(())
 }
+
+#[error_code]
+pub enum InitializeConfigError {
+    #[msg("Chain context must be a solana: CAIP-2 chain ID")]
+    InvalidChainContext,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AccountMigrated, EVENT_VERSION};
+use crate::instructions::account_migration::{
+    migrate_account, MigratableAccount, MigratableAccountKind,
+};
+use crate::state::{AgentProfile, AuthorDispute, ReputationConfig, SkillListing, Vouch};
+
+/// Config-authority batch upgrade of program accounts to their current layouts.
+///
+/// `remaining_accounts` lists writable `AgentProfile`, `Vouch`, `SkillListing`,
+/// `AuthorDispute`, or config accounts in any layout the program has shipped. Each is
+/// resized, with rent topped up from or refunded to the authority, and rewritten in
+/// place; accounts already current are skipped, so a batch can be retried. A config
+/// too old to load here still goes through `migrate_config` first.
+#[derive(Accounts)]
+pub struct MigrateAccounts<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ MigrateAccountsError::UnauthorizedMigrationAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateAccounts<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let authority = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    for account in ctx.remaining_accounts {
+        require_keys_eq!(
+            *account.owner,
+            *ctx.program_id,
+            MigrateAccountsError::MigrationAccountNotOwnedByProgram
+        );
+        require!(
+            account.is_writable,
+            MigrateAccountsError::MigrationAccountNotWritable
+        );
+
+        let kind = MigratableAccountKind::from_data(&account.try_borrow_data()?)
+            .ok_or(error!(MigrateAccountsError::UnsupportedMigrationAccount))?;
+        let previous_data_len = account.data_len();
+        let (previous_version, version) = match kind {
+            MigratableAccountKind::ReputationConfig => (
+                migrate_account::<ReputationConfig>(account, &authority, &system_program, now)?,
+                ReputationConfig::LAYOUT_VERSION,
+            ),
+            MigratableAccountKind::AgentProfile => (
+                migrate_account::<AgentProfile>(account, &authority, &system_program, now)?,
+                AgentProfile::LAYOUT_VERSION,
+            ),
+            MigratableAccountKind::Vouch => (
+                migrate_account::<Vouch>(account, &authority, &system_program, now)?,
+                Vouch::LAYOUT_VERSION,
+            ),
+            MigratableAccountKind::SkillListing => (
+                migrate_account::<SkillListing>(account, &authority, &system_program, now)?,
+                SkillListing::LAYOUT_VERSION,
+            ),
+            MigratableAccountKind::AuthorDispute => (
+                migrate_account::<AuthorDispute>(account, &authority, &system_program, now)?,
+                AuthorDispute::LAYOUT_VERSION,
+            ),
+        };
+
+        if let Some(previous_version) = previous_version {
+            emit!(AccountMigrated {
+                event_version: EVENT_VERSION,
+                chain_context_id: ctx.accounts.config.chain_context_id,
+                account: account.key(),
+                kind,
+                migrated_by: authority.key(),
+                previous_data_len: previous_data_len as u32,
+                previous_version,
+                data_len: account.data_len() as u32,
+                version,
+                timestamp: now,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[error_code]
+pub enum MigrateAccountsError {
+    #[msg("Only the config authority can migrate program accounts")]
+    UnauthorizedMigrationAuthority,
+    #[msg("Migrated accounts must be owned by the reputation program")]
+    MigrationAccountNotOwnedByProgram,
+    #[msg("Migrated accounts must be passed as writable")]
+    MigrationAccountNotWritable,
+    #[msg("The account type has no migration")]
+    UnsupportedMigrationAccount,
+}
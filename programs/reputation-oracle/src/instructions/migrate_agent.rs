@@ -1,11 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::state::AgentProfile;
+use crate::events::{AgentProfileMigrated, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig};
 
-use crate::instructions::agent_profile_migration::{
-    parse_agent_profile_for_migration, resize_agent_profile_account_if_needed,
-    serialize_migrated_agent_profile, ParsedAgentProfile,
-};
+use crate::instructions::account_migration::{resize_account, write_account};
+use crate::instructions::agent_profile_migration::parse_agent_profile_for_migration;
 
 /// Migrates an existing AgentProfile PDA to the current struct layout.
 /// This is needed when the on-chain struct changed (e.g. fields were removed)
@@ -27,33 +26,19 @@ pub struct MigrateAgent<'info> {
     )]
     pub agent_profile: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-fn build_migrated_profile(
-    parsed: ParsedAgentProfile,
-    metadata_uri: String,
-    canonical_bump: u8,
-) -> AgentProfile {
-    AgentProfile {
-        authority: parsed.authority,
-        metadata_uri,
-        reputation_score: parsed.reputation_score,
-        total_vouches_received: parsed.total_vouches_received,
-        total_vouches_given: parsed.total_vouches_given,
-        total_staked_for: parsed.total_staked_for,
-        author_bond_lamports: parsed.author_bond_lamports,
-        active_free_skill_listings: parsed.active_free_skill_listings,
-        open_author_disputes: parsed.open_author_disputes,
-        registered_at: parsed.registered_at,
-        bump: canonical_bump,
-    }
-}
-
-pub fn handler(ctx: Context<MigrateAgent>, metadata_uri: String) -> Result<()> {
+pub(crate) fn handler(ctx: Context<MigrateAgent>, metadata_uri: String) -> Result<()> {
     require!(
         metadata_uri.len() <= AgentProfile::MAX_URI_LENGTH,
         MigrateAgentError::MetadataUriTooLong
@@ -69,14 +54,32 @@ pub fn handler(ctx: Context<MigrateAgent>, metadata_uri: String) -> Result<()> {
         MigrateAgentError::UnauthorizedAgentAuthority
     );
 
-    resize_agent_profile_account_if_needed(
+    resize_account(
         &profile_info.to_account_info(),
         &ctx.accounts.authority.to_account_info(),
         &ctx.accounts.system_program.to_account_info(),
+        AgentProfile::LEN,
     )?;
 
-    let profile = build_migrated_profile(parsed, metadata_uri, canonical_bump);
-    serialize_migrated_agent_profile(&profile_info.to_account_info(), &profile)?;
+    let previous_metadata_uri = parsed.metadata_uri.clone();
+    let profile = AgentProfile {
+        metadata_uri,
+        ..parsed.into_agent_profile(canonical_bump)
+    };
+    write_account(&profile_info.to_account_info(), &profile)?;
+
+    emit!(AgentProfileMigrated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: profile_info.key(),
+        agent_id: profile.agent_id,
+        migrated_by: authority_key,
+        admin_migration: false,
+        previous_data_len: raw.len() as u32,
+        previous_metadata_uri,
+        metadata_uri: profile.metadata_uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
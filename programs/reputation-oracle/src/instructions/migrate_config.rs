@@ -1,102 +1,195 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
-use std::convert::TryInto;
 
+use crate::bytes::Reader;
+use crate::events::{ConfigMigrated, EVENT_VERSION};
+use crate::identity::is_solana_caip2_chain_id;
+use crate::instructions::account_migration::{decode_appended_layout, resize_account, write_account};
 use crate::state::ReputationConfig;
 
 const LEGACY_REPUTATION_CONFIG_LEN: usize = 82;
+const BONDED_REPUTATION_CONFIG_LEN: usize = 86;
 
-#[derive(Clone, Copy)]
-struct ParsedReputationConfig {
-    authority: Pubkey,
-    min_stake: u64,
-    dispute_bond: u64,
-    min_author_bond_for_free_listing: u64,
-    slash_percentage: u8,
-    cooldown_period: i64,
-    stake_weight: u32,
-    vouch_weight: u32,
-    longevity_bonus: u32,
-    bump: u8,
+/// A config read from the legacy, bonded, or current layout, with fields the older
+/// layouts lacked filled from the program defaults. `version` is the stored layout
+/// version, 0 for layouts from before versioning. `chain_context` is `None` for the
+/// layouts that predate it; there is no safe default, so the authority must supply it.
+#[derive(Clone)]
+pub struct ParsedReputationConfig {
+    pub authority: Pubkey,
+    pub min_stake: u64,
+    pub dispute_bond: u64,
+    pub min_author_bond_for_free_listing: u64,
+    pub slash_percentage: u8,
+    pub cooldown_period: i64,
+    pub stake_weight: u32,
+    pub vouch_weight: u32,
+    pub longevity_bonus: u32,
+    pub transitive_trust_bps: u16,
+    pub max_trust_depth: u8,
+    pub verified_min_vouchers: u32,
+    pub verified_min_total_stake: u64,
+    pub verified_min_author_bond: u64,
+    pub verified_dispute_lookback: i64,
+    pub verified_min_account_age: i64,
+    pub min_voucher_age: i64,
+    pub min_voucher_reputation: u64,
+    pub full_weight_voucher_reputation: u64,
+    pub chain_context: Option<String>,
+    pub bump: u8,
+    pub version: u8,
+    pub agent_registry_program: Pubkey,
+    pub agent_registry_collection: Pubkey,
 }
 
-fn read_pubkey(raw: &[u8], start: usize) -> Result<Pubkey> {
-    let bytes: [u8; 32] = raw
-        .get(start..start + 32)
-        .ok_or(error!(MigrateConfigError::InvalidConfigLayout))?
-        .try_into()
-        .map_err(|_| error!(MigrateConfigError::InvalidConfigLayout))?;
-    Ok(Pubkey::new_from_array(bytes))
+impl ParsedReputationConfig {
+    pub fn into_reputation_config(self) -> ReputationConfig {
+        let chain_context = self.chain_context.unwrap_or_default();
+        ReputationConfig {
+            authority: self.authority,
+            min_stake: self.min_stake,
+            dispute_bond: self.dispute_bond,
+            min_author_bond_for_free_listing: self.min_author_bond_for_free_listing,
+            slash_percentage: self.slash_percentage,
+            cooldown_period: self.cooldown_period,
+            stake_weight: self.stake_weight,
+            vouch_weight: self.vouch_weight,
+            longevity_bonus: self.longevity_bonus,
+            transitive_trust_bps: self.transitive_trust_bps,
+            max_trust_depth: self.max_trust_depth,
+            verified_min_vouchers: self.verified_min_vouchers,
+            verified_min_total_stake: self.verified_min_total_stake,
+            verified_min_author_bond: self.verified_min_author_bond,
+            verified_dispute_lookback: self.verified_dispute_lookback,
+            verified_min_account_age: self.verified_min_account_age,
+            min_voucher_age: self.min_voucher_age,
+            min_voucher_reputation: self.min_voucher_reputation,
+            full_weight_voucher_reputation: self.full_weight_voucher_reputation,
+            chain_context_id: ReputationConfig::compact_chain_id(&chain_context),
+            chain_context,
+            bump: self.bump,
+            version: ReputationConfig::CURRENT_VERSION,
+            agent_registry_program: self.agent_registry_program,
+            agent_registry_collection: self.agent_registry_collection,
+        }
+    }
 }
 
-fn read_u64(raw: &[u8], start: usize) -> Result<u64> {
-    let bytes: [u8; 8] = raw
-        .get(start..start + 8)
-        .ok_or(error!(MigrateConfigError::InvalidConfigLayout))?
-        .try_into()
-        .map_err(|_| error!(MigrateConfigError::InvalidConfigLayout))?;
-    Ok(u64::from_le_bytes(bytes))
+fn invalid_config_layout() -> Error {
+    error!(MigrateConfigError::InvalidConfigLayout)
 }
 
-fn read_i64(raw: &[u8], start: usize) -> Result<i64> {
-    let bytes: [u8; 8] = raw
-        .get(start..start + 8)
-        .ok_or(error!(MigrateConfigError::InvalidConfigLayout))?
-        .try_into()
-        .map_err(|_| error!(MigrateConfigError::InvalidConfigLayout))?;
-    Ok(i64::from_le_bytes(bytes))
-}
+fn parse_legacy_config(raw: &[u8], canonical_bump: u8) -> Result<ParsedReputationConfig> {
+    Reader::at(raw, 8).decode(
+        |reader| {
+            let authority = reader.pubkey()?;
+            let min_stake = reader.u64_le()?;
+            let dispute_bond = reader.u64_le()?;
+            let slash_percentage = reader.u8()?;
+            let cooldown_period = reader.i64_le()?;
+            let stake_weight = reader.u32_le()?;
+            let vouch_weight = reader.u32_le()?;
+            let dispute_penalty = reader.u32_le()?;
+            let longevity_bonus = reader.u32_le()?;
+            msg!(
+                "Migrating legacy config layout; carrying forward values and replacing legacy dispute_penalty={} with min_author_bond_for_free_listing={}",
+                dispute_penalty,
+                dispute_bond
+            );
 
-fn read_u32(raw: &[u8], start: usize) -> Result<u32> {
-    let bytes: [u8; 4] = raw
-        .get(start..start + 4)
-        .ok_or(error!(MigrateConfigError::InvalidConfigLayout))?
-        .try_into()
-        .map_err(|_| error!(MigrateConfigError::InvalidConfigLayout))?;
-    Ok(u32::from_le_bytes(bytes))
+            Some(ParsedReputationConfig {
+                authority,
+                min_stake,
+                dispute_bond,
+                min_author_bond_for_free_listing: dispute_bond,
+                slash_percentage,
+                cooldown_period,
+                stake_weight,
+                vouch_weight,
+                longevity_bonus,
+                transitive_trust_bps: ReputationConfig::DEFAULT_TRANSITIVE_TRUST_BPS,
+                max_trust_depth: ReputationConfig::DEFAULT_MAX_TRUST_DEPTH,
+                verified_min_vouchers: ReputationConfig::DEFAULT_VERIFIED_MIN_VOUCHERS,
+                verified_min_total_stake: ReputationConfig::DEFAULT_VERIFIED_MIN_TOTAL_STAKE,
+                verified_min_author_bond: ReputationConfig::DEFAULT_VERIFIED_MIN_AUTHOR_BOND,
+                verified_dispute_lookback: ReputationConfig::DEFAULT_VERIFIED_DISPUTE_LOOKBACK,
+                verified_min_account_age: ReputationConfig::DEFAULT_VERIFIED_MIN_ACCOUNT_AGE,
+                min_voucher_age: ReputationConfig::DEFAULT_MIN_VOUCHER_AGE,
+                min_voucher_reputation: ReputationConfig::DEFAULT_MIN_VOUCHER_REPUTATION,
+                full_weight_voucher_reputation:
+                    ReputationConfig::DEFAULT_FULL_WEIGHT_VOUCHER_REPUTATION,
+                chain_context: None,
+                bump: canonical_bump,
+                version: 0,
+                agent_registry_program: Pubkey::default(),
+                agent_registry_collection: Pubkey::default(),
+            })
+        },
+        invalid_config_layout,
+    )
 }
 
-fn read_u8(raw: &[u8], start: usize) -> Result<u8> {
-    raw.get(start)
-        .copied()
-        .ok_or(error!(MigrateConfigError::InvalidConfigLayout))
+fn parse_bonded_config(raw: &[u8], canonical_bump: u8) -> Result<ParsedReputationConfig> {
+    Reader::at(raw, 8).decode(
+        |reader| {
+            Some(ParsedReputationConfig {
+                authority: reader.pubkey()?,
+                min_stake: reader.u64_le()?,
+                dispute_bond: reader.u64_le()?,
+                min_author_bond_for_free_listing: reader.u64_le()?,
+                slash_percentage: reader.u8()?,
+                cooldown_period: reader.i64_le()?,
+                stake_weight: reader.u32_le()?,
+                vouch_weight: reader.u32_le()?,
+                longevity_bonus: reader.u32_le()?,
+                transitive_trust_bps: ReputationConfig::DEFAULT_TRANSITIVE_TRUST_BPS,
+                max_trust_depth: ReputationConfig::DEFAULT_MAX_TRUST_DEPTH,
+                verified_min_vouchers: ReputationConfig::DEFAULT_VERIFIED_MIN_VOUCHERS,
+                verified_min_total_stake: ReputationConfig::DEFAULT_VERIFIED_MIN_TOTAL_STAKE,
+                verified_min_author_bond: ReputationConfig::DEFAULT_VERIFIED_MIN_AUTHOR_BOND,
+                verified_dispute_lookback: ReputationConfig::DEFAULT_VERIFIED_DISPUTE_LOOKBACK,
+                verified_min_account_age: ReputationConfig::DEFAULT_VERIFIED_MIN_ACCOUNT_AGE,
+                min_voucher_age: ReputationConfig::DEFAULT_MIN_VOUCHER_AGE,
+                min_voucher_reputation: ReputationConfig::DEFAULT_MIN_VOUCHER_REPUTATION,
+                full_weight_voucher_reputation:
+                    ReputationConfig::DEFAULT_FULL_WEIGHT_VOUCHER_REPUTATION,
+                chain_context: None,
+                bump: canonical_bump,
+                version: 0,
+                agent_registry_program: Pubkey::default(),
+                agent_registry_collection: Pubkey::default(),
+            })
+        },
+        invalid_config_layout,
+    )
 }
 
-fn parse_legacy_config(raw: &[u8], canonical_bump: u8) -> Result<ParsedReputationConfig> {
-    let dispute_bond = read_u64(raw, 48)?;
-    let dispute_penalty = read_u32(raw, 73)?;
-    msg!(
-        "Migrating legacy config layout; carrying forward values and replacing legacy dispute_penalty={} with min_author_bond_for_free_listing={}",
-        dispute_penalty,
-        dispute_bond
-    );
-
+fn parse_versioned_config(raw: &[u8], canonical_bump: u8) -> Result<ParsedReputationConfig> {
+    let (config, version) = decode_appended_layout::<ReputationConfig>(raw)?;
     Ok(ParsedReputationConfig {
-        authority: read_pubkey(raw, 8)?,
-        min_stake: read_u64(raw, 40)?,
-        dispute_bond,
-        min_author_bond_for_free_listing: dispute_bond,
-        slash_percentage: read_u8(raw, 56)?,
-        cooldown_period: read_i64(raw, 57)?,
-        stake_weight: read_u32(raw, 65)?,
-        vouch_weight: read_u32(raw, 69)?,
-        longevity_bonus: read_u32(raw, 77)?,
-        bump: canonical_bump,
-    })
-}
-
-fn parse_current_config(raw: &[u8], canonical_bump: u8) -> Result<ParsedReputationConfig> {
-    Ok(ParsedReputationConfig {
-        authority: read_pubkey(raw, 8)?,
-        min_stake: read_u64(raw, 40)?,
-        dispute_bond: read_u64(raw, 48)?,
-        min_author_bond_for_free_listing: read_u64(raw, 56)?,
-        slash_percentage: read_u8(raw, 64)?,
-        cooldown_period: read_i64(raw, 65)?,
-        stake_weight: read_u32(raw, 73)?,
-        vouch_weight: read_u32(raw, 77)?,
-        longevity_bonus: read_u32(raw, 81)?,
+        authority: config.authority,
+        min_stake: config.min_stake,
+        dispute_bond: config.dispute_bond,
+        min_author_bond_for_free_listing: config.min_author_bond_for_free_listing,
+        slash_percentage: config.slash_percentage,
+        cooldown_period: config.cooldown_period,
+        stake_weight: config.stake_weight,
+        vouch_weight: config.vouch_weight,
+        longevity_bonus: config.longevity_bonus,
+        transitive_trust_bps: config.transitive_trust_bps,
+        max_trust_depth: config.max_trust_depth,
+        verified_min_vouchers: config.verified_min_vouchers,
+        verified_min_total_stake: config.verified_min_total_stake,
+        verified_min_author_bond: config.verified_min_author_bond,
+        verified_dispute_lookback: config.verified_dispute_lookback,
+        verified_min_account_age: config.verified_min_account_age,
+        min_voucher_age: config.min_voucher_age,
+        min_voucher_reputation: config.min_voucher_reputation,
+        full_weight_voucher_reputation: config.full_weight_voucher_reputation,
+        chain_context: Some(config.chain_context),
         bump: canonical_bump,
+        version,
+        agent_registry_program: config.agent_registry_program,
+        agent_registry_collection: config.agent_registry_collection,
     })
 }
 
@@ -119,76 +212,76 @@ pub struct MigrateConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
-    let canonical_bump = ctx.bumps.config;
-    let config_info = &ctx.accounts.config;
-    let authority_key = ctx.accounts.authority.key();
-    let current_len = config_info.data_len();
-
+/// Decodes the legacy, bonded, and current config layouts. The stored bump is never trusted;
+/// `canonical_bump` is used instead, as the migration writes it.
+pub fn decode_reputation_config(raw: &[u8], canonical_bump: u8) -> Result<ParsedReputationConfig> {
     require!(
-        current_len == LEGACY_REPUTATION_CONFIG_LEN || current_len == ReputationConfig::LEN,
+        raw.len() == LEGACY_REPUTATION_CONFIG_LEN
+            || raw.len() == BONDED_REPUTATION_CONFIG_LEN
+            || raw.len() == ReputationConfig::LEN,
         MigrateConfigError::UnsupportedConfigLayout
     );
-
-    let raw = config_info.try_borrow_data()?.to_vec();
     require!(
-        raw.starts_with(&ReputationConfig::DISCRIMINATOR),
+        raw.starts_with(ReputationConfig::DISCRIMINATOR),
         MigrateConfigError::InvalidConfigDiscriminator
     );
 
-    let stored_authority = read_pubkey(&raw, 8)?;
+    match raw.len() {
+        LEGACY_REPUTATION_CONFIG_LEN => parse_legacy_config(raw, canonical_bump),
+        BONDED_REPUTATION_CONFIG_LEN => parse_bonded_config(raw, canonical_bump),
+        _ => parse_versioned_config(raw, canonical_bump),
+    }
+}
+
+/// `chain_context` is required when the stored layout predates it and must otherwise be
+/// omitted or match the stored value; `update_chain_context` changes it.
+pub(crate) fn handler(ctx: Context<MigrateConfig>, chain_context: Option<String>) -> Result<()> {
+    let canonical_bump = ctx.bumps.config;
+    let config_info = &ctx.accounts.config;
+    let authority_key = ctx.accounts.authority.key();
+    let current_len = config_info.data_len();
+
+    let raw = config_info.try_borrow_data()?.to_vec();
+    let mut parsed = decode_reputation_config(&raw, canonical_bump)?;
     require!(
-        stored_authority == authority_key,
+        parsed.authority == authority_key,
         MigrateConfigError::UnauthorizedConfigAuthority
     );
-
-    let parsed = if current_len == LEGACY_REPUTATION_CONFIG_LEN {
-        parse_legacy_config(&raw, canonical_bump)?
-    } else {
-        parse_current_config(&raw, canonical_bump)?
-    };
-
-    if current_len != ReputationConfig::LEN {
-        config_info.resize(ReputationConfig::LEN)?;
-
-        let rent = Rent::get()?;
-        let target_rent = rent.minimum_balance(ReputationConfig::LEN);
-        let current_lamports = config_info.lamports();
-
-        if target_rent > current_lamports {
-            let diff = target_rent - current_lamports;
-            let cpi = system_program::Transfer {
-                from: ctx.accounts.authority.to_account_info(),
-                to: config_info.to_account_info(),
-            };
-            system_program::transfer(
-                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi),
-                diff,
-            )?;
-        } else if current_lamports > target_rent {
-            let diff = current_lamports - target_rent;
-            **config_info.try_borrow_mut_lamports()? -= diff;
-            **ctx.accounts.authority.try_borrow_mut_lamports()? += diff;
+    match (&parsed.chain_context, chain_context) {
+        (None, Some(chain_context)) => {
+            require!(
+                is_solana_caip2_chain_id(&chain_context),
+                MigrateConfigError::InvalidChainContext
+            );
+            parsed.chain_context = Some(chain_context);
         }
+        (None, None) => return err!(MigrateConfigError::MissingChainContext),
+        (Some(stored), Some(chain_context)) => require!(
+            *stored == chain_context,
+            MigrateConfigError::ChainContextMismatch
+        ),
+        (Some(_), None) => {}
     }
 
-    let next = ReputationConfig {
-        authority: parsed.authority,
-        min_stake: parsed.min_stake,
-        dispute_bond: parsed.dispute_bond,
-        min_author_bond_for_free_listing: parsed.min_author_bond_for_free_listing,
-        slash_percentage: parsed.slash_percentage,
-        cooldown_period: parsed.cooldown_period,
-        stake_weight: parsed.stake_weight,
-        vouch_weight: parsed.vouch_weight,
-        longevity_bonus: parsed.longevity_bonus,
-        bump: parsed.bump,
-    };
-
-    let mut data = config_info.try_borrow_mut_data()?;
-    data[..8].copy_from_slice(&ReputationConfig::DISCRIMINATOR);
-    let mut writer = &mut data[8..];
-    AnchorSerialize::serialize(&next, &mut writer)?;
+    resize_account(
+        &config_info.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ReputationConfig::LEN,
+    )?;
+    let next = parsed.into_reputation_config();
+    write_account(&config_info.to_account_info(), &next)?;
+
+    emit!(ConfigMigrated {
+        event_version: EVENT_VERSION,
+        chain_context_id: next.chain_context_id,
+        config: config_info.key(),
+        chain_context: next.chain_context,
+        authority: next.authority,
+        previous_data_len: current_len as u32,
+        data_len: ReputationConfig::LEN as u32,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     Ok(())
 }
@@ -203,4 +296,10 @@ pub enum MigrateConfigError {
     InvalidConfigDiscriminator,
     #[msg("The config PDA data could not be parsed")]
     InvalidConfigLayout,
+    #[msg("This config layout predates the chain context; migrate_config must supply it")]
+    MissingChainContext,
+    #[msg("Chain context must be a solana: CAIP-2 chain ID")]
+    InvalidChainContext,
+    #[msg("Chain context differs from the stored one; change it with update_chain_context")]
+    ChainContextMismatch,
 }
@@ -1,39 +1,109 @@
 pub mod initialize_config;
+pub mod account_migration;
 pub mod migrate_config;
-pub(crate) mod agent_profile_migration;
+pub mod migrate_accounts;
+pub mod update_trust_thresholds;
+pub mod update_vouch_weighting;
+pub mod update_chain_context;
+pub mod update_agent_registry;
+pub mod agent_profile_migration;
 pub mod register_agent;
 pub mod migrate_agent;
 pub mod admin_migrate_agent;
 pub mod repair_agent_registered_at;
+pub mod agent_profile_counters;
+pub mod reconcile_agent_profile;
+pub mod sync_agent_profile;
+pub mod close_agent_profile;
+pub mod rotate_agent_authority;
+pub mod set_agent_delegate;
+pub mod create_agent_session_key;
+pub mod revoke_agent_session_key;
+pub mod bind_agent_identity;
+pub mod attest_agent_identity;
+pub mod reassign_agent_identity;
+pub mod create_foreign_agent_record;
+pub mod vouch_foreign_agent;
+pub mod revoke_foreign_vouch;
+pub mod claim_foreign_agent_record;
+pub mod register_wormhole_emitter;
+pub mod post_foreign_reputation_attestation;
+pub mod publish_reputation_message;
 pub mod deposit_author_bond;
 pub mod withdraw_author_bond;
 pub mod vouch;
 pub mod revoke_vouch;
+pub mod decrease_vouch_stake;
+pub mod withdraw_slashed_vouch_remainder;
+pub mod close_vouch;
+pub mod refresh_trust_depth;
 pub mod open_author_dispute;
 pub mod resolve_author_dispute;
+pub mod release_author_dispute_vouch_link;
 pub(crate) mod vouch_settlement;
 pub mod create_skill_listing;
 pub mod update_skill_listing;
+pub mod create_skill_listing_with_session;
+pub mod update_skill_listing_with_session;
 pub mod remove_skill_listing;
 pub mod close_skill_listing;
 pub mod purchase_skill;
+pub mod close_purchase;
+pub mod close_agent_purchase;
+pub mod purchase_skill_with_session;
 pub mod claim_voucher_revenue;
+pub mod claim_voucher_revenue_with_session;
+pub mod get_reputation;
 
 pub use initialize_config::*;
 pub use migrate_config::*;
+pub use migrate_accounts::*;
+pub use update_trust_thresholds::*;
+pub use update_vouch_weighting::*;
+pub use update_chain_context::*;
+pub use update_agent_registry::*;
 pub use register_agent::*;
 pub use migrate_agent::*;
 pub use admin_migrate_agent::*;
 pub use repair_agent_registered_at::*;
+pub use reconcile_agent_profile::*;
+pub use sync_agent_profile::*;
+pub use close_agent_profile::*;
+pub use rotate_agent_authority::*;
+pub use set_agent_delegate::*;
+pub use create_agent_session_key::*;
+pub use revoke_agent_session_key::*;
+pub use bind_agent_identity::*;
+pub use attest_agent_identity::*;
+pub use reassign_agent_identity::*;
+pub use create_foreign_agent_record::*;
+pub use vouch_foreign_agent::*;
+pub use revoke_foreign_vouch::*;
+pub use claim_foreign_agent_record::*;
+pub use register_wormhole_emitter::*;
+pub use post_foreign_reputation_attestation::*;
+pub use publish_reputation_message::*;
 pub use deposit_author_bond::*;
 pub use withdraw_author_bond::*;
 pub use vouch::*;
 pub use revoke_vouch::*;
+pub use decrease_vouch_stake::*;
+pub use withdraw_slashed_vouch_remainder::*;
+pub use close_vouch::*;
+pub use refresh_trust_depth::*;
 pub use open_author_dispute::*;
 pub use resolve_author_dispute::*;
+pub use release_author_dispute_vouch_link::*;
 pub use create_skill_listing::*;
 pub use update_skill_listing::*;
+pub use create_skill_listing_with_session::*;
+pub use update_skill_listing_with_session::*;
 pub use remove_skill_listing::*;
 pub use close_skill_listing::*;
 pub use purchase_skill::*;
+pub use close_purchase::*;
+pub use close_agent_purchase::*;
+pub use purchase_skill_with_session::*;
 pub use claim_voucher_revenue::*;
+pub use claim_voucher_revenue_with_session::*;
+pub use get_reputation::*;
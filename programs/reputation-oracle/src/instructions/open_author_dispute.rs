@@ -5,6 +5,7 @@ use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
 use anchor_lang::system_program;
 
 use crate::events::AuthorDisputeOpened as AuthorDisputeOpenedEvent;
+use crate::events::{AuthorDisputeVouchLinked, EVENT_VERSION};
 use crate::state::{
     AgentProfile, AuthorDispute, AuthorDisputeLiabilityScope, AuthorDisputeReason,
     AuthorDisputeStatus,
@@ -18,14 +19,14 @@ pub struct OpenAuthorDispute<'info> {
         init,
         payer = challenger,
         space = AuthorDispute::LEN,
-        seeds = [b"author_dispute", author_profile.authority.as_ref(), &dispute_id.to_le_bytes()],
+        seeds = [b"author_dispute", author_profile.agent_id.as_ref(), &dispute_id.to_le_bytes()],
         bump
     )]
     pub author_dispute: Account<'info, AuthorDispute>,
 
     #[account(
         mut,
-        seeds = [b"agent", author_profile.authority.as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump
     )]
     pub author_profile: Account<'info, AgentProfile>,
@@ -46,7 +47,7 @@ pub struct OpenAuthorDispute<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler<'info>(
+pub(crate) fn handler<'info>(
     ctx: Context<'_, '_, 'info, 'info, OpenAuthorDispute<'info>>,
     dispute_id: u64,
     reason: AuthorDisputeReason,
@@ -54,20 +55,20 @@ pub fn handler<'info>(
 ) -> Result<()> {
     require!(
         evidence_uri.len() <= AuthorDispute::MAX_EVIDENCE_URI_LENGTH,
-        ErrorCode::EvidenceUriTooLong
+        OpenAuthorDisputeError::EvidenceUriTooLong
     );
 
-    let author = ctx.accounts.author_profile.authority;
+    let author = ctx.accounts.author_profile.agent_id;
 
     require!(
         ctx.accounts.skill_listing.author == author,
-        ErrorCode::SkillListingAuthorMismatch
+        OpenAuthorDisputeError::SkillListingAuthorMismatch
     );
 
     if let Some(purchase) = &ctx.accounts.purchase {
         require!(
             purchase.skill_listing == ctx.accounts.skill_listing.key(),
-            ErrorCode::PurchaseSkillMismatch
+            OpenAuthorDisputeError::PurchaseSkillMismatch
         );
     }
 
@@ -99,13 +100,13 @@ pub fn handler<'info>(
 
     require!(
         remaining_account_count % 2 == 0,
-        ErrorCode::InvalidBackingVouchAccounts
+        OpenAuthorDisputeError::InvalidBackingVouchAccounts
     );
 
     let supplied_backing_vouch_count = (remaining_account_count / 2) as u32;
     require!(
         supplied_backing_vouch_count == expected_backing_vouch_count,
-        ErrorCode::IncompleteBackingVouchSet
+        OpenAuthorDisputeError::IncompleteBackingVouchSet
     );
 
     let system_program_info = ctx.accounts.system_program.to_account_info();
@@ -117,20 +118,20 @@ pub fn handler<'info>(
     for account_pair in ctx.remaining_accounts.chunks_exact(2) {
         let link_account = &account_pair[0];
         let vouch_account = &account_pair[1];
-        let vouch = Account::<Vouch>::try_from(vouch_account)?;
+        let mut vouch = Account::<Vouch>::try_from(vouch_account)?;
         let vouch_key = vouch.key();
 
         require!(
             unique_vouches.insert(vouch_key),
-            ErrorCode::DuplicateBackingVouch
+            OpenAuthorDisputeError::DuplicateBackingVouch
         );
         require!(
             vouch.vouchee == ctx.accounts.author_profile.key(),
-            ErrorCode::BackingVouchAuthorMismatch
+            OpenAuthorDisputeError::BackingVouchAuthorMismatch
         );
         require!(
             vouch.status.counts_toward_author_wide_backing_snapshot(),
-            ErrorCode::BackingVouchNotLive
+            OpenAuthorDisputeError::BackingVouchNotLive
         );
 
         let (expected_link_key, link_bump) = Pubkey::find_program_address(
@@ -144,11 +145,11 @@ pub fn handler<'info>(
         require_keys_eq!(
             link_account.key(),
             expected_link_key,
-            ErrorCode::AuthorDisputeVouchLinkMismatch
+            OpenAuthorDisputeError::AuthorDisputeVouchLinkMismatch
         );
         require!(
             link_account.owner == &system_program::ID && link_account.data_is_empty(),
-            ErrorCode::AuthorDisputeVouchLinkAlreadyInitialized
+            OpenAuthorDisputeError::AuthorDisputeVouchLinkAlreadyInitialized
         );
 
         let link_bump_seed = [link_bump];
@@ -184,14 +185,28 @@ pub fn handler<'info>(
         let mut link_data_slice: &mut [u8] = &mut link_data;
         link_state.try_serialize(&mut link_data_slice)?;
 
+        vouch.pending_dispute_links = vouch
+            .pending_dispute_links
+            .checked_add(1)
+            .ok_or(OpenAuthorDisputeError::PendingDisputeLinkOverflow)?;
+        vouch.exit(ctx.program_id)?;
+
+        emit!(AuthorDisputeVouchLinked {
+            event_version: EVENT_VERSION,
+            chain_context_id: config.chain_context_id,
+            author_dispute: author_dispute_key,
+            vouch: vouch_key,
+            timestamp: clock.unix_timestamp,
+        });
+
         linked_vouch_count = linked_vouch_count
             .checked_add(1)
-            .ok_or(ErrorCode::BackingVouchCountOverflow)?;
+            .ok_or(OpenAuthorDisputeError::BackingVouchCountOverflow)?;
     }
 
     require!(
         linked_vouch_count == expected_backing_vouch_count,
-        ErrorCode::IncompleteBackingVouchSet
+        OpenAuthorDisputeError::IncompleteBackingVouchSet
     );
 
     let author_dispute = &mut ctx.accounts.author_dispute;
@@ -212,19 +227,22 @@ pub fn handler<'info>(
     author_dispute.created_at = clock.unix_timestamp;
     author_dispute.resolved_at = None;
     author_dispute.bump = ctx.bumps.author_dispute;
+    author_dispute.version = AuthorDispute::CURRENT_VERSION;
     ctx.accounts.author_profile.open_author_disputes = ctx
         .accounts
         .author_profile
         .open_author_disputes
         .checked_add(1)
-        .ok_or(ErrorCode::OpenAuthorDisputeCountOverflow)?;
+        .ok_or(OpenAuthorDisputeError::OpenAuthorDisputeCountOverflow)?;
 
     emit!(AuthorDisputeOpenedEvent {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
         author_dispute: author_dispute_key,
         author,
         challenger: ctx.accounts.challenger.key(),
-        reason: reason_label(reason).to_string(),
-        liability_scope: liability_scope_label(liability_scope).to_string(),
+        reason,
+        liability_scope,
         skill_listing,
         skill_price_lamports_snapshot,
         purchase,
@@ -236,24 +254,8 @@ pub fn handler<'info>(
     Ok(())
 }
 
-fn reason_label(reason: AuthorDisputeReason) -> &'static str {
-    match reason {
-        AuthorDisputeReason::MaliciousSkill => "MaliciousSkill",
-        AuthorDisputeReason::FraudulentClaims => "FraudulentClaims",
-        AuthorDisputeReason::FailedDelivery => "FailedDelivery",
-        AuthorDisputeReason::Other => "Other",
-    }
-}
-
-fn liability_scope_label(liability_scope: AuthorDisputeLiabilityScope) -> &'static str {
-    match liability_scope {
-        AuthorDisputeLiabilityScope::AuthorBondOnly => "AuthorBondOnly",
-        AuthorDisputeLiabilityScope::AuthorBondThenVouchers => "AuthorBondThenVouchers",
-    }
-}
-
 #[error_code]
-pub enum ErrorCode {
+pub enum OpenAuthorDisputeError {
     #[msg("Evidence URI is too long")]
     EvidenceUriTooLong,
     #[msg("The provided skill listing does not belong to the disputed author")]
@@ -278,4 +280,6 @@ pub enum ErrorCode {
     BackingVouchCountOverflow,
     #[msg("Open author dispute count overflowed")]
     OpenAuthorDisputeCountOverflow,
+    #[msg("Pending dispute link count overflowed on a backing vouch")]
+    PendingDisputeLinkOverflow,
 }
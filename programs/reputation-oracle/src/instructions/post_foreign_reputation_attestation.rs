@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{ForeignReputationAttested, EVENT_VERSION};
+use crate::identity::parse_canonical_agent_id;
+use crate::state::{ForeignReputationAttestation, WormholeEmitter, ReputationConfig};
+use crate::wormhole::{
+    is_core_bridge_program, parse_guardian_set, parse_posted_vaa, parse_signature_set,
+    verify_posted_vaa, ReputationSummaryPayload, VaaVerificationError,
+};
+
+/// Permissionless relay of a guardian-signed reputation summary from a registered
+/// foreign emitter, for an agent on the chain that emitter is registered for.
+#[derive(Accounts)]
+#[instruction(canonical_agent_id: String)]
+pub struct PostForeignReputationAttestation<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ForeignReputationAttestation::LEN,
+        seeds = [b"foreign_reputation", ForeignReputationAttestation::seed_hash(&canonical_agent_id).as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, ForeignReputationAttestation>,
+
+    #[account(
+        seeds = [b"wormhole_emitter".as_ref(), &wormhole_emitter.chain.to_le_bytes()],
+        bump = wormhole_emitter.bump
+    )]
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    /// CHECK: Core-bridge `PostedVAA` account; owner and layout are checked in the handler.
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    /// CHECK: Core-bridge `SignatureSet` referenced by the posted VAA; checked in the handler.
+    pub signature_set: UncheckedAccount<'info>,
+
+    /// CHECK: Core-bridge `GuardianSet` the signatures were verified against; checked in the handler.
+    pub guardian_set: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PostForeignReputationAttestation>,
+    canonical_agent_id: String,
+) -> Result<()> {
+    for account in [
+        &ctx.accounts.posted_vaa,
+        &ctx.accounts.signature_set,
+        &ctx.accounts.guardian_set,
+    ] {
+        require!(
+            is_core_bridge_program(account.owner),
+            PostForeignReputationAttestationError::NotCoreBridgeAccount
+        );
+    }
+
+    let vaa = parse_posted_vaa(&ctx.accounts.posted_vaa.try_borrow_data()?)
+        .ok_or(PostForeignReputationAttestationError::InvalidPostedVaa)?;
+    let signature_set = parse_signature_set(&ctx.accounts.signature_set.try_borrow_data()?)
+        .ok_or(PostForeignReputationAttestationError::InvalidSignatureSet)?;
+    let guardian_set = parse_guardian_set(&ctx.accounts.guardian_set.try_borrow_data()?)
+        .ok_or(PostForeignReputationAttestationError::InvalidGuardianSet)?;
+
+    let clock = Clock::get()?;
+    verify_posted_vaa(
+        &vaa,
+        &ctx.accounts.signature_set.key(),
+        &signature_set,
+        &guardian_set,
+        clock.unix_timestamp,
+    )
+    .map_err(|error| match error {
+        VaaVerificationError::SignatureSetMismatch => {
+            PostForeignReputationAttestationError::SignatureSetMismatch
+        }
+        VaaVerificationError::GuardianSetMismatch => {
+            PostForeignReputationAttestationError::GuardianSetMismatch
+        }
+        VaaVerificationError::GuardianSetExpired => {
+            PostForeignReputationAttestationError::GuardianSetExpired
+        }
+        VaaVerificationError::InsufficientSignatures => {
+            PostForeignReputationAttestationError::InsufficientSignatures
+        }
+    })?;
+
+    let emitter = &ctx.accounts.wormhole_emitter;
+    require!(
+        vaa.emitter_chain == emitter.chain && vaa.emitter_address == emitter.address,
+        PostForeignReputationAttestationError::UnregisteredEmitter
+    );
+
+    let summary = ReputationSummaryPayload::decode(&vaa.payload)
+        .ok_or(PostForeignReputationAttestationError::InvalidPayload)?;
+    require!(
+        summary.canonical_agent_id == canonical_agent_id,
+        PostForeignReputationAttestationError::CanonicalAgentIdMismatch
+    );
+    let chain_context = parse_canonical_agent_id(&summary.canonical_agent_id)
+        .ok_or(PostForeignReputationAttestationError::InvalidPayload)?
+        .chain_id
+        .to_string();
+    require!(
+        chain_context == emitter.chain_context,
+        PostForeignReputationAttestationError::EmitterChainMismatch
+    );
+
+    let attestation = &mut ctx.accounts.attestation;
+    if attestation.is_uninitialized() {
+        attestation.canonical_agent_id = canonical_agent_id;
+        attestation.chain_context = chain_context;
+        attestation.bump = ctx.bumps.attestation;
+    } else {
+        require!(
+            attestation.emitter_chain == vaa.emitter_chain,
+            PostForeignReputationAttestationError::EmitterChanged
+        );
+        require!(
+            attestation.is_superseded_by(&vaa.emitter_address, vaa.sequence, summary.observed_at),
+            PostForeignReputationAttestationError::StaleAttestation
+        );
+    }
+    attestation.emitter_chain = vaa.emitter_chain;
+    attestation.emitter_address = vaa.emitter_address;
+    attestation.sequence = vaa.sequence;
+    attestation.posted_vaa = ctx.accounts.posted_vaa.key();
+    attestation.reputation_score = summary.reputation_score;
+    attestation.total_vouches_received = summary.total_vouches_received;
+    attestation.feedback_count = summary.feedback_count;
+    attestation.observed_at = summary.observed_at;
+    attestation.updated_at = clock.unix_timestamp;
+
+    emit!(ForeignReputationAttested {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        attestation: attestation.key(),
+        canonical_agent_id: attestation.canonical_agent_id.clone(),
+        agent_chain_context: attestation.chain_context.clone(),
+        emitter_chain: attestation.emitter_chain,
+        sequence: attestation.sequence,
+        reputation_score: attestation.reputation_score,
+        total_vouches_received: attestation.total_vouches_received,
+        feedback_count: attestation.feedback_count,
+        observed_at: attestation.observed_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum PostForeignReputationAttestationError {
+    #[msg("VAA accounts must be owned by the Wormhole core bridge")]
+    NotCoreBridgeAccount,
+    #[msg("Posted VAA account could not be decoded")]
+    InvalidPostedVaa,
+    #[msg("Signature set account could not be decoded")]
+    InvalidSignatureSet,
+    #[msg("Guardian set account could not be decoded")]
+    InvalidGuardianSet,
+    #[msg("Posted VAA was not verified with the provided signature set")]
+    SignatureSetMismatch,
+    #[msg("Signature set does not belong to the provided guardian set")]
+    GuardianSetMismatch,
+    #[msg("Guardian set has expired")]
+    GuardianSetExpired,
+    #[msg("Signature set does not reach guardian quorum")]
+    InsufficientSignatures,
+    #[msg("VAA was not emitted by the registered emitter for its chain")]
+    UnregisteredEmitter,
+    #[msg("VAA payload is not a supported reputation summary")]
+    InvalidPayload,
+    #[msg("Reputation summary is for a different canonical agent ID")]
+    CanonicalAgentIdMismatch,
+    #[msg("Reputation summary is for an agent outside the emitter's registered chain")]
+    EmitterChainMismatch,
+    #[msg("Attestation updates must come from the emitter registered for its chain")]
+    EmitterChanged,
+    #[msg("Reputation summary is not newer than the stored attestation")]
+    StaleAttestation,
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+
+use crate::events::{ReputationMessagePublished, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig};
+use crate::wormhole::{
+    is_core_bridge_program, parse_bridge_fee, parse_emitter_sequence, post_message_data,
+    ReputationSummaryPayload, OUTBOUND_EMITTER_SEED, OUTBOUND_MESSAGE_SEED,
+    WORMHOLE_CHAIN_ID_SOLANA,
+};
+
+/// Posts the profile's reputation through the Wormhole core bridge in the same payload
+/// format the inbound path accepts, keyed by `<config.chain_context>:<program id>#<agent_id>`.
+/// The program's emitter PDA signs the message. Anyone may publish it and pays the
+/// bridge fee and message rent.
+#[derive(Accounts)]
+pub struct PublishReputationMessage<'info> {
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    /// CHECK: This program's emitter PDA; signs the core-bridge message and holds no data.
+    #[account(
+        seeds = [OUTBOUND_EMITTER_SEED],
+        bump
+    )]
+    pub emitter: UncheckedAccount<'info>,
+
+    /// CHECK: Core-bridge `Bridge` config; the owner is checked here and the bridge checks the address.
+    #[account(mut)]
+    pub core_bridge_config: UncheckedAccount<'info>,
+
+    /// CHECK: Fresh message account at `[OUTBOUND_MESSAGE_SEED, sequence]`; derived in the handler.
+    #[account(mut)]
+    pub core_message: UncheckedAccount<'info>,
+
+    /// CHECK: The emitter's core-bridge `Sequence` tracker; the bridge checks the address.
+    #[account(mut)]
+    pub core_emitter_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: Core-bridge fee collector; the bridge checks the address.
+    #[account(mut)]
+    pub core_fee_collector: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the Wormhole core bridge.
+    #[account(
+        executable,
+        constraint = is_core_bridge_program(&core_bridge_program.key()) @ PublishReputationMessageError::NotCoreBridgeProgram
+    )]
+    pub core_bridge_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<PublishReputationMessage>) -> Result<()> {
+    let core_bridge_program = ctx.accounts.core_bridge_program.key();
+    require_keys_eq!(
+        *ctx.accounts.core_bridge_config.owner,
+        core_bridge_program,
+        PublishReputationMessageError::InvalidCoreBridgeConfig
+    );
+    let fee = parse_bridge_fee(&ctx.accounts.core_bridge_config.try_borrow_data()?)
+        .ok_or(PublishReputationMessageError::InvalidCoreBridgeConfig)?;
+
+    let sequence_info = ctx.accounts.core_emitter_sequence.to_account_info();
+    require!(
+        sequence_info.data_is_empty() || *sequence_info.owner == core_bridge_program,
+        PublishReputationMessageError::InvalidEmitterSequence
+    );
+    let sequence = parse_emitter_sequence(&sequence_info.try_borrow_data()?)
+        .ok_or(PublishReputationMessageError::InvalidEmitterSequence)?;
+    let sequence_seed = sequence.to_le_bytes();
+    let (message_key, message_bump) =
+        Pubkey::find_program_address(&[OUTBOUND_MESSAGE_SEED, &sequence_seed], ctx.program_id);
+    require_keys_eq!(
+        ctx.accounts.core_message.key(),
+        message_key,
+        PublishReputationMessageError::InvalidMessageAccount
+    );
+
+    let clock = Clock::get()?;
+    let profile = &ctx.accounts.agent_profile;
+    let canonical_agent_id = format!(
+        "{}:{}#{}",
+        ctx.accounts.config.chain_context,
+        crate::ID,
+        profile.agent_id
+    );
+    let payload = ReputationSummaryPayload {
+        canonical_agent_id: canonical_agent_id.clone(),
+        reputation_score: profile.reputation_score,
+        total_vouches_received: profile.total_vouches_received,
+        feedback_count: 0,
+        observed_at: clock.unix_timestamp,
+    }
+    .encode();
+
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.core_fee_collector.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    let post_message = Instruction {
+        program_id: core_bridge_program,
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.core_bridge_config.key(), false),
+            AccountMeta::new(message_key, true),
+            AccountMeta::new_readonly(ctx.accounts.emitter.key(), true),
+            AccountMeta::new(ctx.accounts.core_emitter_sequence.key(), false),
+            AccountMeta::new(ctx.accounts.payer.key(), true),
+            AccountMeta::new(ctx.accounts.core_fee_collector.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+        ],
+        data: post_message_data(0, &payload),
+    };
+    invoke_signed(
+        &post_message,
+        &[
+            ctx.accounts.core_bridge_config.to_account_info(),
+            ctx.accounts.core_message.to_account_info(),
+            ctx.accounts.emitter.to_account_info(),
+            sequence_info,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.core_fee_collector.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[
+            &[OUTBOUND_EMITTER_SEED, &[ctx.bumps.emitter]],
+            &[OUTBOUND_MESSAGE_SEED, &sequence_seed, &[message_bump]],
+        ],
+    )?;
+
+    emit!(ReputationMessagePublished {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: profile.key(),
+        canonical_agent_id,
+        emitter_chain: WORMHOLE_CHAIN_ID_SOLANA,
+        emitter: ctx.accounts.emitter.key(),
+        sequence,
+        message: message_key,
+        payload,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum PublishReputationMessageError {
+    #[msg("Reputation messages must be posted through the Wormhole core bridge")]
+    NotCoreBridgeProgram,
+    #[msg("Core bridge config account could not be decoded")]
+    InvalidCoreBridgeConfig,
+    #[msg("Emitter sequence account could not be decoded")]
+    InvalidEmitterSequence,
+    #[msg("Message account is not the PDA for the emitter's next sequence")]
+    InvalidMessageAccount,
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use crate::state::{SkillListing, SkillStatus, Purchase, AgentProfile};
-use crate::events::SkillPurchased;
+use crate::state::{SkillListing, SkillStatus, Purchase, AgentProfile, TrustTier, ReputationConfig};
+use crate::events::{SkillPurchased, EVENT_VERSION};
 
 #[derive(Accounts)]
 pub struct PurchaseSkill<'info> {
@@ -20,8 +20,8 @@ pub struct PurchaseSkill<'info> {
     )]
     pub purchase: Account<'info, Purchase>,
     
-    /// CHECK: Author receives 60% of payment
-    #[account(mut, constraint = author.key() == skill_listing.author @ PurchaseError::InvalidAuthor)]
+    /// CHECK: Author's current wallet receives 60% of payment
+    #[account(mut, constraint = author.key() == author_profile.authority @ PurchaseError::InvalidAuthor)]
     pub author: UncheckedAccount<'info>,
     
     #[account(
@@ -30,16 +30,54 @@ pub struct PurchaseSkill<'info> {
     )]
     pub author_profile: Account<'info, AgentProfile>,
     
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
     #[account(mut)]
     pub buyer: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<PurchaseSkill>) -> Result<()> {
+pub(crate) fn handler(ctx: Context<PurchaseSkill>, min_author_trust_tier: Option<TrustTier>) -> Result<()> {
+    if let Some(min_author_trust_tier) = min_author_trust_tier {
+        require!(
+            ctx.accounts.author_profile.compute_trust_tier(&ctx.accounts.config) >= min_author_trust_tier,
+            PurchaseError::AuthorBelowMinimumTrustTier
+        );
+    }
+
+    settle_purchase(
+        &ctx.accounts.system_program,
+        ctx.accounts.buyer.to_account_info(),
+        ctx.accounts.author.to_account_info(),
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.purchase,
+        ctx.accounts.buyer.key(),
+        ctx.bumps.purchase,
+        &ctx.accounts.config,
+    )
+}
+
+/// Splits the listing price between the author and the voucher pool and writes the
+/// receipt. Shared by wallet purchases and session-key purchases.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_purchase<'info>(
+    system_program: &Program<'info, System>,
+    payer: AccountInfo<'info>,
+    author: AccountInfo<'info>,
+    skill_listing: &mut Account<'info, SkillListing>,
+    purchase: &mut Account<'info, Purchase>,
+    buyer: Pubkey,
+    purchase_bump: u8,
+    config: &Account<'info, ReputationConfig>,
+) -> Result<()> {
     // Get immutable values first
-    let skill_listing_key = ctx.accounts.skill_listing.key();
-    let price = ctx.accounts.skill_listing.price_lamports;
+    let skill_listing_key = skill_listing.key();
+    let price = skill_listing.price_lamports;
     // Calculate splits: 60% author, 40% vouchers
     let author_share = price.checked_mul(60).unwrap().checked_div(100).unwrap();
     let voucher_pool = price.checked_mul(40).unwrap().checked_div(100).unwrap();
@@ -47,10 +85,10 @@ pub fn handler(ctx: Context<PurchaseSkill>) -> Result<()> {
     // Transfer 60% to author
     system_program::transfer(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
+            system_program.to_account_info(),
             system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.author.to_account_info(),
+                from: payer.clone(),
+                to: author,
             },
         ),
         author_share,
@@ -59,35 +97,35 @@ pub fn handler(ctx: Context<PurchaseSkill>) -> Result<()> {
     // Transfer 40% voucher pool to skill listing PDA (claimable by vouchers later)
     system_program::transfer(
         CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
+            system_program.to_account_info(),
             system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: ctx.accounts.skill_listing.to_account_info(),
+                from: payer.clone(),
+                to: skill_listing.to_account_info(),
             },
         ),
         voucher_pool,
     )?;
     
     // Update skill listing stats
-    let skill_listing = &mut ctx.accounts.skill_listing;
     skill_listing.total_downloads = skill_listing.total_downloads.checked_add(1).unwrap();
     skill_listing.total_revenue = skill_listing.total_revenue.checked_add(price).unwrap();
     skill_listing.unclaimed_voucher_revenue = skill_listing.unclaimed_voucher_revenue
         .checked_add(voucher_pool).unwrap();
     
     // Create purchase record
-    let purchase = &mut ctx.accounts.purchase;
     let clock = Clock::get()?;
-    purchase.buyer = ctx.accounts.buyer.key();
+    purchase.buyer = buyer;
     purchase.skill_listing = skill_listing_key;
     purchase.purchased_at = clock.unix_timestamp;
     purchase.price_paid = price;
-    purchase.bump = ctx.bumps.purchase;
+    purchase.bump = purchase_bump;
     
     emit!(SkillPurchased {
-        purchase: ctx.accounts.purchase.key(),
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        purchase: purchase.key(),
         skill_listing: skill_listing_key,
-        buyer: ctx.accounts.buyer.key(),
+        buyer,
         price,
         author_share,
         voucher_pool,
@@ -103,4 +141,6 @@ pub enum PurchaseError {
     SkillNotActive,
     #[msg("Invalid author")]
     InvalidAuthor,
+    #[msg("Skill author does not meet the requested minimum trust tier")]
+    AuthorBelowMinimumTrustTier,
 }
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{SessionKeySpend, EVENT_VERSION};
+use crate::instructions::purchase_skill::{settle_purchase, PurchaseError};
+use crate::state::{
+    AgentProfile, AgentSessionKey, Purchase, SkillListing, SkillStatus, TrustTier,
+    SESSION_SCOPE_PURCHASE, ReputationConfig,
+};
+
+/// Purchases a skill for an agent using a scoped session key. The session wallet
+/// funds the payment, the receipt is recorded under the agent's stable ID, and the
+/// price is charged against the session key's spend cap.
+#[derive(Accounts)]
+pub struct PurchaseSkillWithSession<'info> {
+    #[account(
+        mut,
+        constraint = skill_listing.status == SkillStatus::Active @ PurchaseError::SkillNotActive,
+    )]
+    pub skill_listing: Account<'info, SkillListing>,
+
+    #[account(
+        init,
+        payer = session_signer,
+        space = Purchase::SPACE,
+        seeds = [b"purchase", agent_profile.agent_id.as_ref(), skill_listing.key().as_ref()],
+        bump
+    )]
+    pub purchase: Account<'info, Purchase>,
+
+    /// CHECK: Author's current wallet receives 60% of payment
+    #[account(mut, constraint = author.key() == author_profile.authority @ PurchaseError::InvalidAuthor)]
+    pub author: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"agent", skill_listing.author.as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"session_key", agent_profile.key().as_ref(), session_signer.key().as_ref()],
+        bump = agent_session_key.bump,
+        constraint = agent_session_key.is_issued_by_current_authority(&agent_profile) @ PurchaseSkillWithSessionError::SessionKeyAuthorityRotated,
+    )]
+    pub agent_session_key: Account<'info, AgentSessionKey>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub session_signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<PurchaseSkillWithSession>,
+    min_author_trust_tier: Option<TrustTier>,
+) -> Result<()> {
+    if let Some(min_author_trust_tier) = min_author_trust_tier {
+        require!(
+            ctx.accounts.author_profile.compute_trust_tier(&ctx.accounts.config) >= min_author_trust_tier,
+            PurchaseError::AuthorBelowMinimumTrustTier
+        );
+    }
+
+    let clock = Clock::get()?;
+    let price = ctx.accounts.skill_listing.price_lamports;
+    let agent_session_key = &mut ctx.accounts.agent_session_key;
+    require!(
+        agent_session_key.allows(SESSION_SCOPE_PURCHASE, clock.unix_timestamp),
+        PurchaseSkillWithSessionError::SessionKeyNotAuthorized
+    );
+    agent_session_key.spent_lamports = agent_session_key
+        .checked_spend(price)
+        .ok_or(PurchaseSkillWithSessionError::SpendCapExceeded)?;
+
+    emit!(SessionKeySpend {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_session_key.agent,
+        session_key: agent_session_key.session_key,
+        amount: price,
+        spent_lamports: agent_session_key.spent_lamports,
+        spend_cap_lamports: agent_session_key.spend_cap_lamports,
+        timestamp: clock.unix_timestamp,
+    });
+
+    settle_purchase(
+        &ctx.accounts.system_program,
+        ctx.accounts.session_signer.to_account_info(),
+        ctx.accounts.author.to_account_info(),
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.purchase,
+        ctx.accounts.agent_profile.agent_id,
+        ctx.bumps.purchase,
+        &ctx.accounts.config,
+    )
+}
+
+#[error_code]
+pub enum PurchaseSkillWithSessionError {
+    #[msg("Session key is expired or lacks the purchase scope")]
+    SessionKeyNotAuthorized,
+    #[msg("Purchase would exceed the session key spend cap")]
+    SpendCapExceeded,
+    #[msg("Session key was issued by an authority the agent has since rotated away from")]
+    SessionKeyAuthorityRotated,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentIdentityReassigned, EVENT_VERSION};
+use crate::state::{AgentIdentityBinding, AgentProfile, ReputationConfig};
+
+/// Config-authority recovery for a binding whose registry lives on another chain.
+/// `bind_agent_identity` cannot check those IDs, so the first profile to bind one holds
+/// it; before attesting, the authority can hand it to the agent that proved control.
+#[derive(Accounts)]
+pub struct ReassignAgentIdentity<'info> {
+    #[account(
+        mut,
+        seeds = [b"identity_binding", AgentIdentityBinding::seed_hash(&identity_binding.canonical_agent_id).as_ref()],
+        bump = identity_binding.bump,
+        constraint = !identity_binding.is_registry_verified() @ ReassignAgentIdentityError::BindingRegistryVerified,
+        constraint = !identity_binding.is_attested() @ ReassignAgentIdentityError::BindingAlreadyAttested
+    )]
+    pub identity_binding: Account<'info, AgentIdentityBinding>,
+
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ ReassignAgentIdentityError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ReassignAgentIdentity>) -> Result<()> {
+    let clock = Clock::get()?;
+    let agent = ctx.accounts.agent_profile.key();
+    let identity_binding = &mut ctx.accounts.identity_binding;
+    let previous_agent = identity_binding.agent;
+    require_keys_neq!(
+        previous_agent,
+        agent,
+        ReassignAgentIdentityError::BindingAlreadyHeld
+    );
+
+    // The label described the previous holder's record; the new holder re-binds to set its own.
+    identity_binding.agent = agent;
+    identity_binding.raw_upstream_label = String::new();
+    identity_binding.bound_at = clock.unix_timestamp;
+    identity_binding.updated_at = clock.unix_timestamp;
+
+    emit!(AgentIdentityReassigned {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        binding: identity_binding.key(),
+        canonical_agent_id: identity_binding.canonical_agent_id.clone(),
+        previous_agent,
+        agent,
+        reassigned_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ReassignAgentIdentityError {
+    #[msg("Only the config authority can reassign identity bindings")]
+    UnauthorizedConfigAuthority,
+    #[msg("Solana registry bindings follow the asset's owner and cannot be reassigned")]
+    BindingRegistryVerified,
+    #[msg("Attested bindings cannot be reassigned")]
+    BindingAlreadyAttested,
+    #[msg("The binding already belongs to this agent")]
+    BindingAlreadyHeld,
+}
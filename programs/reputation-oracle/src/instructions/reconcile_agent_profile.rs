@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::agent_profile_counters::{recompute_agent_profile, tally_agent_accounts};
+use crate::state::{AgentProfile, ReputationConfig, AUTHOR_BOND_SEED};
+
+/// Config-authority repair that overwrites an agent's denormalized counters with a
+/// tally of the supplied accounts.
+///
+/// `remaining_accounts` lists the agent's vouches (given and received) and skill
+/// listings, read-only. The authority vouches for the set being complete, since
+/// anything left out is dropped from the counters. A reconciled profile's counters are
+/// trusted again, which a profile migrated from an older layout needs before closing.
+#[derive(Accounts)]
+pub struct ReconcileAgentProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: The agent's author bond PDA, read only if it exists; a missing bond is zero.
+    #[account(
+        seeds = [AUTHOR_BOND_SEED, agent_profile.agent_id.as_ref()],
+        bump
+    )]
+    pub author_bond: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ ReconcileAgentProfileError::UnauthorizedReconcileAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ReconcileAgentProfile>) -> Result<()> {
+    let agent_profile_key = ctx.accounts.agent_profile.key();
+    let counters = tally_agent_accounts(
+        ctx.remaining_accounts,
+        ctx.program_id,
+        &agent_profile_key,
+        &ctx.accounts.agent_profile.agent_id,
+        &ctx.accounts.author_bond.to_account_info(),
+    )?;
+
+    recompute_agent_profile(
+        &ctx.accounts.config,
+        &mut ctx.accounts.agent_profile,
+        counters,
+        ctx.accounts.authority.key(),
+        true,
+    )?;
+    ctx.accounts.agent_profile.counters_verified = true;
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ReconcileAgentProfileError {
+    #[msg("Only the config authority can reconcile agent profiles")]
+    UnauthorizedReconcileAuthority,
+}
@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang::prelude::*;
+
+use crate::events::{TrustDepthRefreshed, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig, Vouch};
+
+/// Permissionless crank that caches how many upstream vouch hops back an agent.
+///
+/// The caller supplies `(vouch, voucher_profile)` pairs covering every live vouch
+/// received by each agent visited within `config.max_trust_depth` hops. The walk is a
+/// breadth-first search from the target, so each agent is counted once at its
+/// shortest distance and cycles terminate. Off-chain clients reproduce the result by
+/// running the same search over the same live vouch set.
+#[derive(Accounts)]
+pub struct RefreshTrustDepth<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+}
+
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefreshTrustDepth<'info>>,
+) -> Result<()> {
+    let max_trust_depth = ctx
+        .accounts
+        .config
+        .max_trust_depth
+        .min(ReputationConfig::MAX_TRUST_DEPTH_LIMIT);
+    let target_key = ctx.accounts.agent_profile.key();
+    let remaining_account_count = ctx.remaining_accounts.len();
+
+    require!(
+        remaining_account_count % 2 == 0,
+        RefreshTrustDepthError::InvalidTrustGraphAccounts
+    );
+
+    let mut unique_vouches = BTreeSet::new();
+    let mut vouchers_by_vouchee: BTreeMap<Pubkey, Vec<Pubkey>> = BTreeMap::new();
+    let mut vouches_received: BTreeMap<Pubkey, u32> = BTreeMap::new();
+    vouches_received.insert(target_key, ctx.accounts.agent_profile.total_vouches_received);
+
+    for account_pair in ctx.remaining_accounts.chunks_exact(2) {
+        let vouch = Account::<Vouch>::try_from(&account_pair[0])?;
+        let voucher_profile = Account::<AgentProfile>::try_from(&account_pair[1])?;
+
+        require!(
+            unique_vouches.insert(vouch.key()),
+            RefreshTrustDepthError::DuplicateTrustGraphVouch
+        );
+        require!(
+            vouch.status.is_live(),
+            RefreshTrustDepthError::TrustGraphVouchNotLive
+        );
+        require_keys_eq!(
+            vouch.voucher,
+            voucher_profile.key(),
+            RefreshTrustDepthError::TrustGraphVoucherMismatch
+        );
+
+        vouchers_by_vouchee
+            .entry(vouch.vouchee)
+            .or_default()
+            .push(voucher_profile.key());
+        vouches_received.insert(voucher_profile.key(), voucher_profile.total_vouches_received);
+    }
+
+    let mut visited = BTreeSet::from([target_key]);
+    let mut frontier = vec![target_key];
+    let mut consumed_vouch_count = 0usize;
+    let mut trust_depth = 0u8;
+
+    while trust_depth < max_trust_depth && !frontier.is_empty() {
+        let mut next_frontier = Vec::new();
+
+        for agent in frontier.iter() {
+            let expected_count = vouches_received.get(agent).copied().unwrap_or(0) as usize;
+            let vouchers = vouchers_by_vouchee
+                .get(agent)
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            require!(
+                vouchers.len() == expected_count,
+                RefreshTrustDepthError::IncompleteTrustGraph
+            );
+            consumed_vouch_count += vouchers.len();
+
+            for voucher in vouchers {
+                if visited.insert(*voucher) {
+                    next_frontier.push(*voucher);
+                }
+            }
+        }
+
+        if next_frontier.is_empty() {
+            break;
+        }
+        trust_depth += 1;
+        frontier = next_frontier;
+    }
+
+    // Every supplied vouch must belong to the walked graph so the result is canonical.
+    require!(
+        consumed_vouch_count == unique_vouches.len(),
+        RefreshTrustDepthError::UnexpectedTrustGraphVouch
+    );
+
+    let clock = Clock::get()?;
+    let agent_profile = &mut ctx.accounts.agent_profile;
+    let previous_trust_depth = agent_profile.trust_depth;
+    agent_profile.trust_depth = trust_depth;
+    agent_profile.trust_depth_updated_at = clock.unix_timestamp;
+
+    emit!(TrustDepthRefreshed {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: target_key,
+        previous_trust_depth,
+        trust_depth,
+        reachable_agent_count: (visited.len() - 1) as u32,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum RefreshTrustDepthError {
+    #[msg("Trust graph accounts must be provided as vouch and voucher profile pairs")]
+    InvalidTrustGraphAccounts,
+    #[msg("Duplicate vouches are not allowed in a trust graph refresh")]
+    DuplicateTrustGraphVouch,
+    #[msg("Trust graph vouches must be live")]
+    TrustGraphVouchNotLive,
+    #[msg("Trust graph voucher profile does not match the recorded voucher")]
+    TrustGraphVoucherMismatch,
+    #[msg("Trust graph must include every live vouch received by each visited agent")]
+    IncompleteTrustGraph,
+    #[msg("Trust graph includes a vouch outside the walked depth")]
+    UnexpectedTrustGraphVouch,
+}
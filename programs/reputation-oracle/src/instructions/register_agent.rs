@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::state::AgentProfile;
+use crate::events::{AgentRegistered, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig, TrustTier};
 
 #[derive(Accounts)]
 pub struct RegisterAgent<'info> {
@@ -11,6 +12,12 @@ pub struct RegisterAgent<'info> {
         bump
     )]
     pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -18,13 +25,13 @@ pub struct RegisterAgent<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<RegisterAgent>,
     metadata_uri: String,
 ) -> Result<()> {
     require!(
         metadata_uri.len() <= AgentProfile::MAX_URI_LENGTH,
-        ErrorCode::MetadataUriTooLong
+        RegisterAgentError::MetadataUriTooLong
     );
     
     let agent_profile = &mut ctx.accounts.agent_profile;
@@ -32,6 +39,11 @@ pub fn handler(
     
     // Preserve existing on-chain stats when re-registering (only update mutable fields)
     let is_new = agent_profile.registered_at == 0;
+    // A rotated-away key still derives this PDA, so it must not reclaim the profile.
+    require!(
+        is_new || agent_profile.is_owner(&ctx.accounts.authority.key()),
+        RegisterAgentError::AgentAuthorityRotated
+    );
     agent_profile.authority = ctx.accounts.authority.key();
     agent_profile.metadata_uri = metadata_uri;
     if is_new {
@@ -43,14 +55,45 @@ pub fn handler(
         agent_profile.active_free_skill_listings = 0;
         agent_profile.open_author_disputes = 0;
         agent_profile.registered_at = clock.unix_timestamp;
+        agent_profile.vouched_reputation_total = 0;
+        agent_profile.trust_depth = 0;
+        agent_profile.trust_depth_updated_at = 0;
+        agent_profile.trust_tier = TrustTier::Unverified;
+        agent_profile.last_upheld_dispute_at = 0;
+        agent_profile.effective_vouch_weight = 0;
+        agent_profile.low_reputation_vouches_received = 0;
+        agent_profile.active_skill_listings = 0;
+        agent_profile.agent_id = ctx.accounts.authority.key();
+        agent_profile.delegate = Pubkey::default();
+        agent_profile.version = AgentProfile::CURRENT_VERSION;
+        agent_profile.counters_verified = true;
+        agent_profile.claimed_foreign_staked_for = 0;
+        agent_profile.claimed_foreign_vouch_weight = 0;
+        agent_profile.claimed_foreign_reputation_total = 0;
+        agent_profile.vouch_accounts_received = 0;
+        agent_profile.vouch_accounts_given = 0;
     }
     agent_profile.bump = ctx.bumps.agent_profile;
+
+    emit!(AgentRegistered {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_profile.key(),
+        agent_id: agent_profile.agent_id,
+        authority: agent_profile.authority,
+        metadata_uri: agent_profile.metadata_uri.clone(),
+        is_new,
+        registered_at: agent_profile.registered_at,
+        timestamp: clock.unix_timestamp,
+    });
     
     Ok(())
 }
 
 #[error_code]
-pub enum ErrorCode {
+pub enum RegisterAgentError {
     #[msg("Metadata URI is too long")]
     MetadataUriTooLong,
+    #[msg("Agent authority has been rotated to a different wallet")]
+    AgentAuthorityRotated,
 }
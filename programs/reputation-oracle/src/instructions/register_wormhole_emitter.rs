@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{WormholeEmitterRegistered, EVENT_VERSION};
+use crate::identity::is_valid_caip2_chain_id;
+use crate::state::{ReputationConfig, WormholeEmitter};
+
+#[derive(Accounts)]
+#[instruction(chain: u16)]
+pub struct RegisterWormholeEmitter<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = WormholeEmitter::LEN,
+        seeds = [b"wormhole_emitter".as_ref(), &chain.to_le_bytes()],
+        bump
+    )]
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ RegisterWormholeEmitterError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<RegisterWormholeEmitter>,
+    chain: u16,
+    address: [u8; 32],
+    chain_context: String,
+) -> Result<()> {
+    require!(
+        address != [0u8; 32],
+        RegisterWormholeEmitterError::InvalidEmitterAddress
+    );
+    require!(
+        is_valid_caip2_chain_id(&chain_context),
+        RegisterWormholeEmitterError::InvalidEmitterChainContext
+    );
+
+    let clock = Clock::get()?;
+    let wormhole_emitter = &mut ctx.accounts.wormhole_emitter;
+    wormhole_emitter.chain = chain;
+    wormhole_emitter.address = address;
+    wormhole_emitter.registered_at = clock.unix_timestamp;
+    wormhole_emitter.bump = ctx.bumps.wormhole_emitter;
+    wormhole_emitter.chain_context = chain_context;
+
+    emit!(WormholeEmitterRegistered {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        emitter: wormhole_emitter.key(),
+        chain,
+        address,
+        emitter_chain_context: wormhole_emitter.chain_context.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum RegisterWormholeEmitterError {
+    #[msg("Only the config authority can register Wormhole emitters")]
+    UnauthorizedConfigAuthority,
+    #[msg("Wormhole emitter address cannot be zero")]
+    InvalidEmitterAddress,
+    #[msg("Emitter chain context must be a CAIP-2 chain ID")]
+    InvalidEmitterChainContext,
+}
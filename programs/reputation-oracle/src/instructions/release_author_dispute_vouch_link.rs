@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AuthorDisputeVouchLinkReleased, EVENT_VERSION};
+use crate::state::{
+    AuthorDispute, AuthorDisputeStatus, AuthorDisputeVouchLink, ReputationConfig, Vouch,
+};
+
+/// Permissionless crank that unlocks a backing vouch once its author dispute has
+/// resolved without settling it (dismissed or bond-only rulings). Upheld voucher
+/// settlement releases links inline, so only the leftovers ever reach this path.
+#[derive(Accounts)]
+pub struct ReleaseAuthorDisputeVouchLink<'info> {
+    #[account(
+        constraint = author_dispute.status == AuthorDisputeStatus::Resolved @ ReleaseAuthorDisputeVouchLinkError::AuthorDisputeNotResolved
+    )]
+    pub author_dispute: Account<'info, AuthorDispute>,
+
+    #[account(
+        mut,
+        seeds = [b"author_dispute_vouch_link", author_dispute.key().as_ref(), vouch.key().as_ref()],
+        bump = link.bump,
+        constraint = link.author_dispute == author_dispute.key() @ ReleaseAuthorDisputeVouchLinkError::LinkDisputeMismatch,
+        constraint = link.vouch == vouch.key() @ ReleaseAuthorDisputeVouchLinkError::LinkVouchMismatch,
+        close = challenger
+    )]
+    pub link: Account<'info, AuthorDisputeVouchLink>,
+
+    #[account(
+        mut,
+        seeds = [b"vouch", vouch.voucher.as_ref(), vouch.vouchee.as_ref()],
+        bump = vouch.bump
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    /// CHECK: Rent refund destination, validated against the stored challenger pubkey.
+    #[account(mut, address = author_dispute.challenger @ ReleaseAuthorDisputeVouchLinkError::ChallengerMismatch)]
+    pub challenger: AccountInfo<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<ReleaseAuthorDisputeVouchLink>) -> Result<()> {
+    let vouch = &mut ctx.accounts.vouch;
+    vouch.pending_dispute_links = vouch.pending_dispute_links.saturating_sub(1);
+
+    emit!(AuthorDisputeVouchLinkReleased {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        author_dispute: ctx.accounts.author_dispute.key(),
+        vouch: vouch.key(),
+        pending_dispute_links: vouch.pending_dispute_links,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum ReleaseAuthorDisputeVouchLinkError {
+    #[msg("Author dispute must be resolved before its vouch links can be released")]
+    AuthorDisputeNotResolved,
+    #[msg("Vouch link does not belong to this author dispute")]
+    LinkDisputeMismatch,
+    #[msg("Vouch link does not reference this vouch")]
+    LinkVouchMismatch,
+    #[msg("Challenger account does not match the author dispute")]
+    ChallengerMismatch,
+}
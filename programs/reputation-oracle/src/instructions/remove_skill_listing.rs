@@ -1,30 +1,38 @@
 use anchor_lang::prelude::*;
-use crate::state::{SkillListing, SkillStatus, AgentProfile};
+use crate::events::{SkillListingRemoved, EVENT_VERSION};
+use crate::state::{SkillListing, SkillStatus, AgentProfile, ReputationConfig};
 
 #[derive(Accounts)]
 #[instruction(skill_id: String)]
 pub struct RemoveSkillListing<'info> {
     #[account(
         mut,
-        seeds = [b"skill", author.key().as_ref(), skill_id.as_bytes()],
+        seeds = [b"skill", author_profile.agent_id.as_ref(), skill_id.as_bytes()],
         bump = skill_listing.bump,
-        constraint = skill_listing.author == author.key() @ RemoveSkillError::NotAuthor,
+        constraint = skill_listing.author == author_profile.agent_id @ RemoveSkillError::NotAuthor,
         constraint = skill_listing.status != SkillStatus::Removed @ RemoveSkillError::AlreadyRemoved,
     )]
     pub skill_listing: Account<'info, SkillListing>,
 
     #[account(
         mut,
-        seeds = [b"agent", author.key().as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump,
+        constraint = author_profile.can_operate(&author.key()) @ RemoveSkillError::UnauthorizedAgentOperator,
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
     #[account(mut)]
     pub author: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<RemoveSkillListing>, _skill_id: String) -> Result<()> {
+pub(crate) fn handler(ctx: Context<RemoveSkillListing>, _skill_id: String) -> Result<()> {
     if crate::state::SkillListing::is_free_price(ctx.accounts.skill_listing.price_lamports) {
         ctx.accounts.author_profile.active_free_skill_listings = ctx
             .accounts
@@ -33,8 +41,27 @@ pub fn handler(ctx: Context<RemoveSkillListing>, _skill_id: String) -> Result<()
             .checked_sub(1)
             .ok_or(RemoveSkillError::FreeListingCountUnderflow)?;
     }
+    // Listings created before the counter existed were never added, so saturate.
+    ctx.accounts.author_profile.active_skill_listings = ctx
+        .accounts
+        .author_profile
+        .active_skill_listings
+        .saturating_sub(1);
+    let timestamp = Clock::get()?.unix_timestamp;
     ctx.accounts.skill_listing.status = SkillStatus::Removed;
-    ctx.accounts.skill_listing.updated_at = Clock::get()?.unix_timestamp;
+    ctx.accounts.skill_listing.updated_at = timestamp;
+
+    emit!(SkillListingRemoved {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        skill_listing: ctx.accounts.skill_listing.key(),
+        author: ctx.accounts.author_profile.agent_id,
+        operator: ctx.accounts.author.key(),
+        price_lamports: ctx.accounts.skill_listing.price_lamports,
+        active_skill_listings: ctx.accounts.author_profile.active_skill_listings,
+        active_free_skill_listings: ctx.accounts.author_profile.active_free_skill_listings,
+        timestamp,
+    });
     Ok(())
 }
 
@@ -46,4 +73,6 @@ pub enum RemoveSkillError {
     AlreadyRemoved,
     #[msg("Active free listing count underflowed")]
     FreeListingCountUnderflow,
+    #[msg("Signer is neither the agent authority nor its delegate")]
+    UnauthorizedAgentOperator,
 }
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::events::{emit_reputation_score_changed, AgentRegisteredAtRepaired, EVENT_VERSION};
 use crate::state::{AgentProfile, ReputationConfig};
 
 const MIN_PLAUSIBLE_REGISTERED_AT: i64 = 946_684_800; // 2000-01-01T00:00:00Z
@@ -9,7 +10,7 @@ const MAX_FUTURE_SKEW_SECONDS: i64 = 366 * 24 * 60 * 60;
 pub struct RepairAgentRegisteredAt<'info> {
     #[account(
         mut,
-        seeds = [b"agent", agent_profile.authority.as_ref()],
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
         bump = agent_profile.bump
     )]
     pub agent_profile: Account<'info, AgentProfile>,
@@ -24,7 +25,7 @@ pub struct RepairAgentRegisteredAt<'info> {
     pub authority: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<RepairAgentRegisteredAt>, registered_at: i64) -> Result<()> {
+pub(crate) fn handler(ctx: Context<RepairAgentRegisteredAt>, registered_at: i64) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
 
     require!(
@@ -34,8 +35,26 @@ pub fn handler(ctx: Context<RepairAgentRegisteredAt>, registered_at: i64) -> Res
     );
 
     let agent_profile = &mut ctx.accounts.agent_profile;
+    let previous_registered_at = agent_profile.registered_at;
     agent_profile.registered_at = registered_at;
+    let previous_score = agent_profile.reputation_score;
     agent_profile.reputation_score = agent_profile.compute_reputation(&ctx.accounts.config);
+    agent_profile.trust_tier = agent_profile.compute_trust_tier(&ctx.accounts.config);
+    emit_reputation_score_changed(
+        &ctx.accounts.config,
+        agent_profile.key(),
+        previous_score,
+        agent_profile.reputation_score,
+    )?;
+
+    emit!(AgentRegisteredAtRepaired {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_profile.key(),
+        previous_registered_at,
+        registered_at,
+        timestamp: now,
+    });
 
     Ok(())
 }
@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 use std::collections::BTreeSet;
 
 use crate::events::AuthorBondSlashed as AuthorBondSlashedEvent;
+use crate::events::EVENT_VERSION;
 use crate::events::AuthorDisputeResolved as AuthorDisputeResolvedEvent;
+use crate::events::VouchSlashed;
 use crate::instructions::vouch_settlement::{
     compute_slash_amount, slash_author_bond, slash_vouch_with_amount,
 };
@@ -17,16 +19,16 @@ use crate::state::{
 pub struct ResolveAuthorDispute<'info> {
     #[account(
         mut,
-        seeds = [b"author_dispute", author_profile.authority.as_ref(), &dispute_id.to_le_bytes()],
+        seeds = [b"author_dispute", author_profile.agent_id.as_ref(), &dispute_id.to_le_bytes()],
         bump = author_dispute.bump,
-        constraint = author_dispute.status == AuthorDisputeStatus::Open @ ErrorCode::AuthorDisputeNotOpen,
-        constraint = author_dispute.author == author_profile.authority @ ErrorCode::AuthorMismatch,
+        constraint = author_dispute.status == AuthorDisputeStatus::Open @ ResolveAuthorDisputeError::AuthorDisputeNotOpen,
+        constraint = author_dispute.author == author_profile.agent_id @ ResolveAuthorDisputeError::AuthorMismatch,
     )]
     pub author_dispute: Account<'info, AuthorDispute>,
 
     #[account(
         mut,
-        seeds = [b"agent", author_profile.authority.as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump
     )]
     pub author_profile: Account<'info, AgentProfile>,
@@ -38,32 +40,32 @@ pub struct ResolveAuthorDispute<'info> {
         mut,
         seeds = [b"config"],
         bump = config.bump,
-        constraint = config.authority == authority.key() @ ErrorCode::UnauthorizedResolver
+        constraint = config.authority == authority.key() @ ResolveAuthorDisputeError::UnauthorizedResolver
     )]
     pub config: Account<'info, ReputationConfig>,
 
     pub authority: Signer<'info>,
 
     /// CHECK: This account is validated against the stored challenger pubkey.
-    #[account(mut, address = author_dispute.challenger @ ErrorCode::ChallengerMismatch)]
+    #[account(mut, address = author_dispute.challenger @ ResolveAuthorDisputeError::ChallengerMismatch)]
     pub challenger: AccountInfo<'info>,
 }
 
-pub fn handler<'info>(
+pub(crate) fn handler<'info>(
     ctx: Context<'_, '_, 'info, 'info, ResolveAuthorDispute<'info>>,
     _dispute_id: u64,
     ruling: AuthorDisputeRuling,
 ) -> Result<()> {
     let clock = Clock::get()?;
     let author_dispute_key = ctx.accounts.author_dispute.key();
-    let author_key = ctx.accounts.author_profile.authority;
+    let author_key = ctx.accounts.author_profile.agent_id;
     let liability_scope = ctx.accounts.author_dispute.liability_scope;
     let linked_vouch_count = ctx.accounts.author_dispute.linked_vouch_count;
     let backing_vouch_count_snapshot = ctx.accounts.author_dispute.backing_vouch_count_snapshot;
     let bond_amount = ctx.accounts.author_dispute.bond_amount;
     require!(
         linked_vouch_count == backing_vouch_count_snapshot,
-        ErrorCode::IncompleteBackingSnapshot
+        ResolveAuthorDisputeError::IncompleteBackingSnapshot
     );
 
     let settlement_totals = match ruling {
@@ -76,6 +78,7 @@ pub fn handler<'info>(
             &mut ctx.accounts.author_profile,
             &ctx.accounts.config,
             &mut ctx.accounts.author_bond,
+            &ctx.accounts.challenger,
             liability_scope,
         )?,
         AuthorDisputeRuling::Dismissed => SettlementTotals::default(),
@@ -91,32 +94,37 @@ pub fn handler<'info>(
         .author_profile
         .open_author_disputes
         .checked_sub(1)
-        .ok_or(ErrorCode::OpenAuthorDisputeCountUnderflow)?;
+        .ok_or(ResolveAuthorDisputeError::OpenAuthorDisputeCountUnderflow)?;
+    if ruling == AuthorDisputeRuling::Upheld {
+        let author_profile = &mut ctx.accounts.author_profile;
+        author_profile.last_upheld_dispute_at = clock.unix_timestamp;
+        author_profile.trust_tier = author_profile.compute_trust_tier(&ctx.accounts.config);
+    }
 
     match ruling {
         AuthorDisputeRuling::Upheld => {
             let total_payout = bond_amount
                 .checked_add(total_slashed_amount)
-                .ok_or(ErrorCode::SlashAmountOverflow)?;
+                .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?;
             **author_dispute.to_account_info().try_borrow_mut_lamports()? = author_dispute
                 .to_account_info()
                 .lamports()
                 .checked_sub(bond_amount)
-                .ok_or(ErrorCode::InsufficientFunds)?;
+                .ok_or(ResolveAuthorDisputeError::InsufficientFunds)?;
 
             **ctx.accounts.challenger.try_borrow_mut_lamports()? = ctx
                 .accounts
                 .challenger
                 .lamports()
                 .checked_add(total_payout)
-                .ok_or(ErrorCode::InsufficientFunds)?;
+                .ok_or(ResolveAuthorDisputeError::InsufficientFunds)?;
         }
         AuthorDisputeRuling::Dismissed => {
             **author_dispute.to_account_info().try_borrow_mut_lamports()? = author_dispute
                 .to_account_info()
                 .lamports()
                 .checked_sub(bond_amount)
-                .ok_or(ErrorCode::InsufficientFunds)?;
+                .ok_or(ResolveAuthorDisputeError::InsufficientFunds)?;
 
             **ctx.accounts.config.to_account_info().try_borrow_mut_lamports()? = ctx
                 .accounts
@@ -124,15 +132,17 @@ pub fn handler<'info>(
                 .to_account_info()
                 .lamports()
                 .checked_add(bond_amount)
-                .ok_or(ErrorCode::InsufficientFunds)?;
+                .ok_or(ResolveAuthorDisputeError::InsufficientFunds)?;
         }
     }
 
     emit!(AuthorDisputeResolvedEvent {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
         author_dispute: author_dispute.key(),
         author: author_dispute.author,
-        ruling: ruling_label(ruling).to_string(),
-        liability_scope: liability_scope_label(liability_scope).to_string(),
+        ruling,
+        liability_scope,
         linked_vouch_count: author_dispute.linked_vouch_count,
         author_bond_slashed_amount: settlement_totals.author_bond_slashed_amount,
         voucher_slashed_amount: settlement_totals.voucher_slashed_amount,
@@ -153,10 +163,11 @@ impl SettlementTotals {
     fn total_slashed_amount(&self) -> Result<u64> {
         self.author_bond_slashed_amount
             .checked_add(self.voucher_slashed_amount)
-            .ok_or(ErrorCode::SlashAmountOverflow.into())
+            .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow.into())
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn settle_author_liability<'info>(
     remaining_accounts: &'info [AccountInfo<'info>],
     program_id: &Pubkey,
@@ -166,6 +177,7 @@ fn settle_author_liability<'info>(
     author_profile: &mut Account<'info, AgentProfile>,
     config: &Account<'info, ReputationConfig>,
     author_bond: &mut Option<Account<'info, AuthorBond>>,
+    challenger: &AccountInfo<'info>,
     liability_scope: AuthorDisputeLiabilityScope,
 ) -> Result<SettlementTotals> {
     validate_author_bond(
@@ -179,7 +191,7 @@ fn settle_author_liability<'info>(
         AuthorDisputeLiabilityScope::AuthorBondOnly => {
             require!(
                 remaining_accounts.is_empty(),
-                ErrorCode::BondOnlyDisputeMustNotProvideSettlementAccounts
+                ResolveAuthorDisputeError::BondOnlyDisputeMustNotProvideSettlementAccounts
             );
             compute_slash_amount(author_bond_amount, config.slash_percentage)
         }
@@ -193,7 +205,7 @@ fn settle_author_liability<'info>(
             )?;
             let total_stake_at_risk = author_bond_amount
                 .checked_add(backing_summary.total_backing_stake)
-                .ok_or(ErrorCode::SlashAmountOverflow)?;
+                .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?;
             compute_slash_amount(total_stake_at_risk, config.slash_percentage)
         }
     };
@@ -204,6 +216,8 @@ fn settle_author_liability<'info>(
 
         if slashed_amount > 0 {
             emit!(AuthorBondSlashedEvent {
+                event_version: EVENT_VERSION,
+                chain_context_id: config.chain_context_id,
                 author_bond: author_bond.key(),
                 author: author_key,
                 amount: slashed_amount,
@@ -236,8 +250,10 @@ fn settle_author_liability<'info>(
                 expected_backing_vouch_count,
                 author_profile,
                 config,
+                challenger,
                 remaining_voucher_liability,
                 backing_summary.full_backing_slash_amount,
+                backing_summary.slashable_vouch_count,
             )?
         }
     };
@@ -259,22 +275,22 @@ fn validate_author_bond(
     }
 
     let author_bond_account =
-        author_bond_account.ok_or(ErrorCode::MissingAuthorBondForSettlement)?;
+        author_bond_account.ok_or(ResolveAuthorDisputeError::MissingAuthorBondForSettlement)?;
     let (expected_author_bond, _) = find_author_bond_pda(author_key, program_id);
     require_keys_eq!(
         author_bond_account.key(),
         expected_author_bond,
-        ErrorCode::AuthorBondAccountMismatch
+        ResolveAuthorDisputeError::AuthorBondAccountMismatch
     );
 
     require_keys_eq!(
         author_bond_account.author,
         *author_key,
-        ErrorCode::AuthorBondAccountMismatch
+        ResolveAuthorDisputeError::AuthorBondAccountMismatch
     );
     require!(
         author_bond_account.amount == author_bond_lamports_on_profile,
-        ErrorCode::AuthorBondProfileMismatch
+        ResolveAuthorDisputeError::AuthorBondProfileMismatch
     );
 
     Ok(())
@@ -283,6 +299,7 @@ fn validate_author_bond(
 struct BackingVouchSummary {
     total_backing_stake: u64,
     full_backing_slash_amount: u64,
+    slashable_vouch_count: u32,
 }
 
 fn summarize_backing_vouches<'info>(
@@ -294,16 +311,17 @@ fn summarize_backing_vouches<'info>(
 ) -> Result<BackingVouchSummary> {
     let expected_account_count = expected_backing_vouch_count
         .checked_mul(3)
-        .ok_or(ErrorCode::BackingVouchCountOverflow)? as usize;
+        .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)? as usize;
     require!(
         remaining_accounts.len() == expected_account_count,
-        ErrorCode::InvalidSettlementAccounts
+        ResolveAuthorDisputeError::InvalidSettlementAccounts
     );
 
     let mut unique_vouches = BTreeSet::new();
     let mut total_backing_stake = 0u64;
     let mut full_backing_slash_amount = 0u64;
     let mut settled_vouch_count = 0u32;
+    let mut slashable_vouch_count = 0u32;
 
     for account_triple in remaining_accounts.chunks_exact(3) {
         let link = Account::<AuthorDisputeVouchLink>::try_from(&account_triple[0])?;
@@ -313,57 +331,65 @@ fn summarize_backing_vouches<'info>(
         require_keys_eq!(
             link.author_dispute,
             author_dispute_key,
-            ErrorCode::AuthorDisputeVouchLinkMismatch
+            ResolveAuthorDisputeError::AuthorDisputeVouchLinkMismatch
         );
         require_keys_eq!(
             link.vouch,
             vouch.key(),
-            ErrorCode::AuthorDisputeSettlementVouchMismatch
+            ResolveAuthorDisputeError::AuthorDisputeSettlementVouchMismatch
         );
         require!(
             unique_vouches.insert(vouch.key()),
-            ErrorCode::DuplicateSettlementBackingVouch
+            ResolveAuthorDisputeError::DuplicateSettlementBackingVouch
         );
         require_keys_eq!(
             vouch.vouchee,
             author_profile_key,
-            ErrorCode::BackingVouchAuthorMismatch
+            ResolveAuthorDisputeError::BackingVouchAuthorMismatch
         );
         require_keys_eq!(
             vouch.voucher,
             voucher_profile.key(),
-            ErrorCode::BackingVouchVoucherMismatch
+            ResolveAuthorDisputeError::BackingVouchVoucherMismatch
         );
+        settled_vouch_count = settled_vouch_count
+            .checked_add(1)
+            .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)?;
+        if vouch.status.exited_before_settlement() {
+            continue;
+        }
         require!(
-            vouch.status.counts_toward_author_wide_backing_snapshot(),
-            ErrorCode::BackingVouchNotSlashable
+            vouch.status.slashable_by_linked_dispute(),
+            ResolveAuthorDisputeError::BackingVouchNotSlashable
         );
 
         total_backing_stake = total_backing_stake
             .checked_add(vouch.stake_amount)
-            .ok_or(ErrorCode::SlashAmountOverflow)?;
+            .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?;
         full_backing_slash_amount = full_backing_slash_amount
             .checked_add(compute_slash_amount(
                 vouch.stake_amount,
                 config.slash_percentage,
             ))
-            .ok_or(ErrorCode::SlashAmountOverflow)?;
-        settled_vouch_count = settled_vouch_count
+            .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?;
+        slashable_vouch_count = slashable_vouch_count
             .checked_add(1)
-            .ok_or(ErrorCode::BackingVouchCountOverflow)?;
+            .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)?;
     }
 
     require!(
         settled_vouch_count == expected_backing_vouch_count,
-        ErrorCode::InvalidSettlementAccounts
+        ResolveAuthorDisputeError::InvalidSettlementAccounts
     );
 
     Ok(BackingVouchSummary {
         total_backing_stake,
         full_backing_slash_amount,
+        slashable_vouch_count,
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 fn settle_backing_vouches<'info>(
     remaining_accounts: &'info [AccountInfo<'info>],
     program_id: &Pubkey,
@@ -371,19 +397,22 @@ fn settle_backing_vouches<'info>(
     expected_backing_vouch_count: u32,
     author_profile: &mut Account<'info, AgentProfile>,
     config: &Account<'info, ReputationConfig>,
+    challenger: &AccountInfo<'info>,
     remaining_voucher_liability: u64,
     full_backing_slash_amount: u64,
+    slashable_vouch_count: u32,
 ) -> Result<u64> {
     let expected_account_count = expected_backing_vouch_count
         .checked_mul(3)
-        .ok_or(ErrorCode::BackingVouchCountOverflow)? as usize;
+        .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)? as usize;
     require!(
         remaining_accounts.len() == expected_account_count,
-        ErrorCode::InvalidSettlementAccounts
+        ResolveAuthorDisputeError::InvalidSettlementAccounts
     );
 
     let mut unique_vouches = BTreeSet::new();
     let mut settled_vouch_count = 0u32;
+    let mut slashed_vouch_count = 0u32;
     let mut total_slashed_amount = 0u64;
     let mut remaining_liability = remaining_voucher_liability;
     let author_profile_key = author_profile.key();
@@ -396,34 +425,46 @@ fn settle_backing_vouches<'info>(
         require_keys_eq!(
             link.author_dispute,
             author_dispute_key,
-            ErrorCode::AuthorDisputeVouchLinkMismatch
+            ResolveAuthorDisputeError::AuthorDisputeVouchLinkMismatch
         );
         require_keys_eq!(
             link.vouch,
             vouch.key(),
-            ErrorCode::AuthorDisputeSettlementVouchMismatch
+            ResolveAuthorDisputeError::AuthorDisputeSettlementVouchMismatch
         );
         require!(
             unique_vouches.insert(vouch.key()),
-            ErrorCode::DuplicateSettlementBackingVouch
+            ResolveAuthorDisputeError::DuplicateSettlementBackingVouch
         );
         require_keys_eq!(
             vouch.vouchee,
             author_profile_key,
-            ErrorCode::BackingVouchAuthorMismatch
+            ResolveAuthorDisputeError::BackingVouchAuthorMismatch
         );
         require_keys_eq!(
             vouch.voucher,
             voucher_profile.key(),
-            ErrorCode::BackingVouchVoucherMismatch
+            ResolveAuthorDisputeError::BackingVouchVoucherMismatch
         );
+
+        // Settling releases this dispute's lock on the vouch and refunds the link rent.
+        vouch.pending_dispute_links = vouch.pending_dispute_links.saturating_sub(1);
+        link.close(challenger.clone())?;
+
+        if vouch.status.exited_before_settlement() {
+            vouch.exit(program_id)?;
+            settled_vouch_count = settled_vouch_count
+                .checked_add(1)
+                .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)?;
+            continue;
+        }
         require!(
-            vouch.status.counts_toward_author_wide_backing_snapshot(),
-            ErrorCode::BackingVouchNotSlashable
+            vouch.status.slashable_by_linked_dispute(),
+            ResolveAuthorDisputeError::BackingVouchNotSlashable
         );
 
         let full_vouch_slash_amount = compute_slash_amount(vouch.stake_amount, config.slash_percentage);
-        let is_last_vouch = settled_vouch_count + 1 == expected_backing_vouch_count;
+        let is_last_vouch = slashed_vouch_count + 1 == slashable_vouch_count;
         let actual_slash_amount = if full_backing_slash_amount == 0 {
             0
         } else if is_last_vouch {
@@ -431,9 +472,9 @@ fn settle_backing_vouches<'info>(
         } else {
             ((full_vouch_slash_amount as u128)
                 .checked_mul(remaining_voucher_liability as u128)
-                .ok_or(ErrorCode::SlashAmountOverflow)?
+                .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?
                 .checked_div(full_backing_slash_amount as u128)
-                .ok_or(ErrorCode::SlashAmountOverflow)?) as u64
+                .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?) as u64
         };
         let slashed_amount = slash_vouch_with_amount(
             &mut vouch,
@@ -442,43 +483,43 @@ fn settle_backing_vouches<'info>(
             config,
             actual_slash_amount,
         )?;
+        emit!(VouchSlashed {
+            event_version: EVENT_VERSION,
+            chain_context_id: config.chain_context_id,
+            author_dispute: author_dispute_key,
+            vouch: vouch.key(),
+            voucher: vouch.voucher,
+            vouchee: vouch.vouchee,
+            amount: slashed_amount,
+            remaining_stake_amount: vouch.stake_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
 
         remaining_liability = remaining_liability.saturating_sub(slashed_amount);
         total_slashed_amount = total_slashed_amount
             .checked_add(slashed_amount)
-            .ok_or(ErrorCode::SlashAmountOverflow)?;
+            .ok_or(ResolveAuthorDisputeError::SlashAmountOverflow)?;
         vouch.exit(program_id)?;
         voucher_profile.exit(program_id)?;
         settled_vouch_count = settled_vouch_count
             .checked_add(1)
-            .ok_or(ErrorCode::BackingVouchCountOverflow)?;
+            .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)?;
+        slashed_vouch_count = slashed_vouch_count
+            .checked_add(1)
+            .ok_or(ResolveAuthorDisputeError::BackingVouchCountOverflow)?;
     }
 
     require!(
         settled_vouch_count == expected_backing_vouch_count,
-        ErrorCode::InvalidSettlementAccounts
+        ResolveAuthorDisputeError::InvalidSettlementAccounts
     );
-    require!(remaining_liability == 0, ErrorCode::InvalidSettlementAmounts);
+    require!(remaining_liability == 0, ResolveAuthorDisputeError::InvalidSettlementAmounts);
 
     Ok(total_slashed_amount)
 }
 
-fn ruling_label(ruling: AuthorDisputeRuling) -> &'static str {
-    match ruling {
-        AuthorDisputeRuling::Upheld => "Upheld",
-        AuthorDisputeRuling::Dismissed => "Dismissed",
-    }
-}
-
-fn liability_scope_label(liability_scope: AuthorDisputeLiabilityScope) -> &'static str {
-    match liability_scope {
-        AuthorDisputeLiabilityScope::AuthorBondOnly => "AuthorBondOnly",
-        AuthorDisputeLiabilityScope::AuthorBondThenVouchers => "AuthorBondThenVouchers",
-    }
-}
-
 #[error_code]
-pub enum ErrorCode {
+pub enum ResolveAuthorDisputeError {
     #[msg("Author dispute is not open")]
     AuthorDisputeNotOpen,
     #[msg("The disputed author does not match this author dispute")]
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentSessionKeyRevoked, EVENT_VERSION};
+use crate::state::{AgentProfile, AgentSessionKey, ReputationConfig};
+
+/// Closes a session key PDA. Scoped instructions require the PDA to exist, so the
+/// key stops working in the same slot and the rent returns to the owner.
+#[derive(Accounts)]
+pub struct RevokeAgentSessionKey<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"session_key", agent_profile.key().as_ref(), agent_session_key.session_key.as_ref()],
+        bump = agent_session_key.bump,
+        constraint = agent_session_key.agent == agent_profile.key() @ RevokeAgentSessionKeyError::SessionKeyAgentMismatch
+    )]
+    pub agent_session_key: Account<'info, AgentSessionKey>,
+
+    #[account(
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ RevokeAgentSessionKeyError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RevokeAgentSessionKey>) -> Result<()> {
+    let agent_session_key = &ctx.accounts.agent_session_key;
+
+    emit!(AgentSessionKeyRevoked {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_session_key.agent,
+        session_key: agent_session_key.session_key,
+        spent_lamports: agent_session_key.spent_lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum RevokeAgentSessionKeyError {
+    #[msg("Only the agent authority can revoke session keys")]
+    UnauthorizedAgentAuthority,
+    #[msg("Session key does not belong to this agent")]
+    SessionKeyAgentMismatch,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{emit_reputation_score_changed, VouchRevoked, EVENT_VERSION};
+use crate::state::{AgentProfile, ForeignAgentRecord, ReputationConfig, Vouch, VouchStatus};
+
+#[derive(Accounts)]
+pub struct RevokeForeignVouch<'info> {
+    #[account(
+        mut,
+        seeds = [b"vouch", voucher_profile.key().as_ref(), foreign_agent.key().as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.voucher == voucher_profile.key() @ RevokeForeignVouchError::UnauthorizedVouchRevocation,
+        constraint = vouch.status.is_live() @ RevokeForeignVouchError::VouchNotRevocable
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ RevokeForeignVouchError::UnauthorizedAgentAuthority
+    )]
+    pub voucher_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"foreign_agent", ForeignAgentRecord::seed_hash(&foreign_agent.canonical_agent_id).as_ref()],
+        bump = foreign_agent.bump
+    )]
+    pub foreign_agent: Account<'info, ForeignAgentRecord>,
+
+    /// The profile that claimed the record, which carries its backing in its score.
+    /// Required once the record is claimed.
+    #[account(mut)]
+    pub claimant_profile: Option<Account<'info, AgentProfile>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RevokeForeignVouch>) -> Result<()> {
+    let vouch = &mut ctx.accounts.vouch;
+    let stake_amount = vouch.stake_amount;
+    vouch.status = VouchStatus::Revoked;
+
+    // Foreign records are never dispute targets, so the stake is always free to leave.
+    **vouch.to_account_info().try_borrow_mut_lamports()? = vouch
+        .to_account_info()
+        .lamports()
+        .saturating_sub(stake_amount);
+    **ctx.accounts.voucher.try_borrow_mut_lamports()? = ctx
+        .accounts
+        .voucher
+        .lamports()
+        .saturating_add(stake_amount);
+
+    let voucher_profile = &mut ctx.accounts.voucher_profile;
+    voucher_profile.total_vouches_given = voucher_profile.total_vouches_given.saturating_sub(1);
+
+    let foreign_agent = &mut ctx.accounts.foreign_agent;
+    foreign_agent.total_vouches_received = foreign_agent.total_vouches_received.saturating_sub(1);
+    foreign_agent.total_staked_for = foreign_agent.total_staked_for.saturating_sub(stake_amount);
+    foreign_agent.vouched_reputation_total = foreign_agent
+        .vouched_reputation_total
+        .saturating_sub(vouch.voucher_reputation_snapshot);
+    foreign_agent.remove_vouch_weight(vouch.effective_weight_bps, vouch.low_reputation_voucher);
+    let previous_score = foreign_agent.reputation_score;
+    foreign_agent.reputation_score = foreign_agent.compute_reputation(&ctx.accounts.config);
+    emit_reputation_score_changed(
+        &ctx.accounts.config,
+        foreign_agent.key(),
+        previous_score,
+        foreign_agent.reputation_score,
+    )?;
+
+    if foreign_agent.is_claimed() {
+        // Claiming requires the claimant to hold no live vouch on the record, so the
+        // claimant is never this voucher and its profile is a separate account.
+        let claimant_profile = ctx
+            .accounts
+            .claimant_profile
+            .as_mut()
+            .ok_or(RevokeForeignVouchError::MissingClaimantProfile)?;
+        require_keys_eq!(
+            claimant_profile.key(),
+            foreign_agent.claimed_by,
+            RevokeForeignVouchError::ClaimantProfileMismatch
+        );
+        claimant_profile.release_claimed_foreign_vouch(vouch);
+        let previous_score = claimant_profile.reputation_score;
+        claimant_profile.reputation_score = claimant_profile.compute_reputation(&ctx.accounts.config);
+        emit_reputation_score_changed(
+            &ctx.accounts.config,
+            claimant_profile.key(),
+            previous_score,
+            claimant_profile.reputation_score,
+        )?;
+    }
+
+    emit!(VouchRevoked {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        vouch: vouch.key(),
+        voucher: vouch.voucher,
+        vouchee: vouch.vouchee,
+        stake_returned: stake_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum RevokeForeignVouchError {
+    #[msg("Unauthorized vouch revocation")]
+    UnauthorizedVouchRevocation,
+    #[msg("Vouch is not currently revocable")]
+    VouchNotRevocable,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+    #[msg("Revoking a vouch on a claimed record requires the claimant's profile")]
+    MissingClaimantProfile,
+    #[msg("Claimant profile does not match the record's claimant")]
+    ClaimantProfileMismatch,
+}
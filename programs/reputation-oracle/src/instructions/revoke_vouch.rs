@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::state::{AgentProfile, Vouch, VouchStatus, ReputationConfig};
-use crate::events::VouchRevoked;
+use crate::events::{emit_reputation_score_changed, VouchRevoked, EVENT_VERSION};
 
 #[derive(Accounts)]
 pub struct RevokeVouch<'info> {
@@ -8,22 +8,25 @@ pub struct RevokeVouch<'info> {
         mut,
         seeds = [b"vouch", voucher_profile.key().as_ref(), vouchee_profile.key().as_ref()],
         bump = vouch.bump,
-        constraint = vouch.voucher == voucher_profile.key() @ ErrorCode::UnauthorizedVouchRevocation,
-        constraint = vouch.status.is_live() @ ErrorCode::VouchNotRevocable
+        constraint = vouch.voucher == voucher_profile.key() @ RevokeVouchError::UnauthorizedVouchRevocation,
+        constraint = vouch.status.is_live() @ RevokeVouchError::VouchNotRevocable,
+        constraint = !vouch.is_locked_by_disputes() @ RevokeVouchError::VouchLockedByPendingDisputes
     )]
     pub vouch: Account<'info, Vouch>,
     
     #[account(
         mut,
-        seeds = [b"agent", voucher.key().as_ref()],
-        bump = voucher_profile.bump
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ RevokeVouchError::UnauthorizedAgentAuthority
     )]
     pub voucher_profile: Account<'info, AgentProfile>,
     
     #[account(
         mut,
-        seeds = [b"agent", vouchee_profile.authority.as_ref()],
-        bump = vouchee_profile.bump
+        seeds = [b"agent", vouchee_profile.agent_id.as_ref()],
+        bump = vouchee_profile.bump,
+        constraint = vouchee_profile.open_author_disputes == 0 @ RevokeVouchError::VouchLockedWhileDisputesOpen
     )]
     pub vouchee_profile: Account<'info, AgentProfile>,
     
@@ -39,9 +42,12 @@ pub struct RevokeVouch<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<RevokeVouch>) -> Result<()> {
+pub(crate) fn handler(ctx: Context<RevokeVouch>) -> Result<()> {
     let vouch = &mut ctx.accounts.vouch;
     let stake_amount = vouch.stake_amount;
+    let voucher_reputation_snapshot = vouch.voucher_reputation_snapshot;
+    let effective_weight_bps = vouch.effective_weight_bps;
+    let low_reputation_voucher = vouch.low_reputation_voucher;
     
     // Mark as revoked
     vouch.status = VouchStatus::Revoked;
@@ -65,12 +71,26 @@ pub fn handler(ctx: Context<RevokeVouch>) -> Result<()> {
     let vouchee_profile = &mut ctx.accounts.vouchee_profile;
     vouchee_profile.total_vouches_received = vouchee_profile.total_vouches_received.saturating_sub(1);
     vouchee_profile.total_staked_for = vouchee_profile.total_staked_for.saturating_sub(stake_amount);
+    vouchee_profile.vouched_reputation_total = vouchee_profile
+        .vouched_reputation_total
+        .saturating_sub(voucher_reputation_snapshot);
+    vouchee_profile.remove_vouch_weight(effective_weight_bps, low_reputation_voucher);
     
     // Recompute reputation
     let config = &ctx.accounts.config;
+    let previous_score = vouchee_profile.reputation_score;
     vouchee_profile.reputation_score = vouchee_profile.compute_reputation(config);
+    vouchee_profile.trust_tier = vouchee_profile.compute_trust_tier(config);
+    emit_reputation_score_changed(
+        config,
+        vouchee_profile.key(),
+        previous_score,
+        vouchee_profile.reputation_score,
+    )?;
 
     emit!(VouchRevoked {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
         vouch: ctx.accounts.vouch.key(),
         voucher: ctx.accounts.voucher_profile.key(),
         vouchee: ctx.accounts.vouchee_profile.key(),
@@ -82,9 +102,15 @@ pub fn handler(ctx: Context<RevokeVouch>) -> Result<()> {
 }
 
 #[error_code]
-pub enum ErrorCode {
+pub enum RevokeVouchError {
     #[msg("Unauthorized vouch revocation")]
     UnauthorizedVouchRevocation,
     #[msg("Vouch is not currently revocable")]
     VouchNotRevocable,
+    #[msg("Vouch cannot be revoked while it is linked to unsettled author disputes")]
+    VouchLockedByPendingDisputes,
+    #[msg("Vouch cannot be revoked while the vouchee has open author disputes")]
+    VouchLockedWhileDisputesOpen,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
 }
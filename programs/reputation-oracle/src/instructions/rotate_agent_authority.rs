@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentAuthorityRotated, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig};
+
+/// Re-keys an agent to a new owner wallet. The profile PDA stays derived from
+/// `agent_id`, so reputation, vouches, bonds, and listings carry over unchanged.
+/// Both wallets sign so a typo cannot strand the profile.
+#[derive(Accounts)]
+pub struct RotateAgentAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ RotateAgentAuthorityError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+
+    pub new_authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<RotateAgentAuthority>) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.key();
+    require_keys_neq!(
+        new_authority,
+        ctx.accounts.authority.key(),
+        RotateAgentAuthorityError::AuthorityUnchanged
+    );
+
+    let agent_profile = &mut ctx.accounts.agent_profile;
+    let previous_authority = agent_profile.authority;
+    agent_profile.authority = new_authority;
+    // A rotation usually follows a compromise, so the old delegate is dropped too.
+    agent_profile.delegate = Pubkey::default();
+
+    emit!(AgentAuthorityRotated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_profile.key(),
+        agent_id: agent_profile.agent_id,
+        previous_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum RotateAgentAuthorityError {
+    #[msg("Only the agent authority can rotate the profile")]
+    UnauthorizedAgentAuthority,
+    #[msg("New authority must differ from the current authority")]
+    AuthorityUnchanged,
+}
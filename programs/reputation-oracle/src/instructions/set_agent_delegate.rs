@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentDelegateUpdated, EVENT_VERSION};
+use crate::state::{AgentProfile, ReputationConfig};
+
+/// Sets or clears the operational key allowed to manage skill listings for an agent.
+/// Passing `None` revokes the current delegate.
+#[derive(Accounts)]
+pub struct SetAgentDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump,
+        constraint = agent_profile.is_owner(&authority.key()) @ SetAgentDelegateError::UnauthorizedAgentAuthority
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SetAgentDelegate>, delegate: Option<Pubkey>) -> Result<()> {
+    let delegate = delegate.unwrap_or_default();
+    require_keys_neq!(
+        delegate,
+        ctx.accounts.authority.key(),
+        SetAgentDelegateError::DelegateIsAuthority
+    );
+
+    let agent_profile = &mut ctx.accounts.agent_profile;
+    let previous_delegate = agent_profile.delegate;
+    agent_profile.delegate = delegate;
+
+    emit!(AgentDelegateUpdated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        agent: agent_profile.key(),
+        previous_delegate,
+        delegate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum SetAgentDelegateError {
+    #[msg("Only the agent authority can change the delegate")]
+    UnauthorizedAgentAuthority,
+    #[msg("The agent authority cannot also be its delegate")]
+    DelegateIsAuthority,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::agent_profile_counters::{
+    recompute_agent_profile, tally_agent_accounts, AgentProfileCounters,
+};
+use crate::state::{AgentProfile, ReputationConfig, AUTHOR_BOND_SEED};
+
+/// Permissionless crank that recomputes an agent's counters and score from its accounts.
+///
+/// `remaining_accounts` lists every vouch account the agent gave or received, settled or
+/// not, and every unremoved listing it authored, read-only; settled vouches count for
+/// nothing beyond their existence. Completeness is checked against the profile's vouch
+/// account counts, which change only when a vouch account is created or closed, and its
+/// `active_skill_listings`, which only creating and removing a listing change; never
+/// against the vouch totals recomputed here. A caller therefore cannot drop accounts to
+/// deflate the totals, and a skewed total is overwritten with the tally. Profiles whose
+/// counters are not yet verified, such as ones migrated from older layouts, need
+/// `reconcile_agent_profile` first.
+#[derive(Accounts)]
+pub struct SyncAgentProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent_profile.agent_id.as_ref()],
+        bump = agent_profile.bump
+    )]
+    pub agent_profile: Account<'info, AgentProfile>,
+
+    /// CHECK: The agent's author bond PDA, read only if it exists; a missing bond is zero.
+    #[account(
+        seeds = [AUTHOR_BOND_SEED, agent_profile.agent_id.as_ref()],
+        bump
+    )]
+    pub author_bond: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub caller: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<SyncAgentProfile>) -> Result<()> {
+    let agent_profile_key = ctx.accounts.agent_profile.key();
+    let counters = tally_agent_accounts(
+        ctx.remaining_accounts,
+        ctx.program_id,
+        &agent_profile_key,
+        &ctx.accounts.agent_profile.agent_id,
+        &ctx.accounts.author_bond.to_account_info(),
+    )?;
+    require!(
+        ctx.accounts.agent_profile.counters_verified,
+        SyncAgentProfileError::CountersNotVerified
+    );
+    require!(
+        counters.covers_accounts(&AgentProfileCounters::from_profile(&ctx.accounts.agent_profile)),
+        SyncAgentProfileError::IncompleteAgentAccounts
+    );
+
+    recompute_agent_profile(
+        &ctx.accounts.config,
+        &mut ctx.accounts.agent_profile,
+        counters,
+        ctx.accounts.caller.key(),
+        false,
+    )
+}
+
+#[error_code]
+pub enum SyncAgentProfileError {
+    #[msg("Supplied accounts must cover every vouch account and unremoved listing of the agent")]
+    IncompleteAgentAccounts,
+    #[msg("Reconcile the migrated profile's counters before syncing it")]
+    CountersNotVerified,
+}
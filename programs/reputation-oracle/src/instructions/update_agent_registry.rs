@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{AgentRegistryUpdated, EVENT_VERSION};
+use crate::state::ReputationConfig;
+
+/// Points `solana:` identity bindings at the Solana Agent Registry program and the core
+/// collection its assets are minted into. Clearing both disables Solana bindings.
+#[derive(Accounts)]
+pub struct UpdateAgentRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ UpdateAgentRegistryError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<UpdateAgentRegistry>,
+    agent_registry_program: Pubkey,
+    agent_registry_collection: Pubkey,
+) -> Result<()> {
+    require!(
+        (agent_registry_program == Pubkey::default())
+            == (agent_registry_collection == Pubkey::default()),
+        UpdateAgentRegistryError::IncompleteAgentRegistry
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.agent_registry_program = agent_registry_program;
+    config.agent_registry_collection = agent_registry_collection;
+
+    emit!(AgentRegistryUpdated {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        config: config.key(),
+        agent_registry_program,
+        agent_registry_collection,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum UpdateAgentRegistryError {
+    #[msg("Only the config authority can update the agent registry")]
+    UnauthorizedConfigAuthority,
+    #[msg("Set both the registry program and collection, or clear both")]
+    IncompleteAgentRegistry,
+}
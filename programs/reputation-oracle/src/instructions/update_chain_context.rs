@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{ChainContextUpdated, EVENT_VERSION};
+use crate::identity::is_solana_caip2_chain_id;
+use crate::state::ReputationConfig;
+
+#[derive(Accounts)]
+pub struct UpdateChainContext<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ UpdateChainContextError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<UpdateChainContext>, chain_context: String) -> Result<()> {
+    require!(
+        is_solana_caip2_chain_id(&chain_context),
+        UpdateChainContextError::InvalidChainContext
+    );
+
+    let config = &mut ctx.accounts.config;
+    let previous_chain_context_id = config.chain_context_id;
+    let previous_chain_context = config.set_chain_context(chain_context);
+
+    emit!(ChainContextUpdated {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        config: config.key(),
+        chain_context: config.chain_context.clone(),
+        previous_chain_context_id,
+        previous_chain_context,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum UpdateChainContextError {
+    #[msg("Only the config authority can update the chain context")]
+    UnauthorizedConfigAuthority,
+    #[msg("Chain context must be a Solana CAIP-2 chain ID")]
+    InvalidChainContext,
+}
@@ -2,24 +2,25 @@ use anchor_lang::prelude::*;
 use crate::state::{
     find_author_bond_pda, AgentProfile, AuthorBond, ReputationConfig, SkillListing, SkillStatus,
 };
-use crate::events::SkillListingUpdated;
+use crate::events::{SkillListingUpdated, EVENT_VERSION};
 
 #[derive(Accounts)]
 #[instruction(skill_id: String)]
 pub struct UpdateSkillListing<'info> {
     #[account(
         mut,
-        seeds = [b"skill", author.key().as_ref(), skill_id.as_bytes()],
+        seeds = [b"skill", author_profile.agent_id.as_ref(), skill_id.as_bytes()],
         bump = skill_listing.bump,
-        constraint = skill_listing.author == author.key() @ UpdateSkillError::NotAuthor,
+        constraint = skill_listing.author == author_profile.agent_id @ UpdateSkillError::NotAuthor,
         constraint = skill_listing.status != SkillStatus::Removed @ UpdateSkillError::SkillRemoved,
     )]
     pub skill_listing: Account<'info, SkillListing>,
 
     #[account(
         mut,
-        seeds = [b"agent", author.key().as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump,
+        constraint = author_profile.can_operate(&author.key()) @ UpdateSkillError::UnauthorizedAgentOperator,
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
@@ -34,13 +35,41 @@ pub struct UpdateSkillListing<'info> {
     pub author: Signer<'info>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<UpdateSkillListing>,
     _skill_id: String,
     skill_uri: String,
     name: String,
     description: String,
     price_lamports: u64,
+) -> Result<()> {
+    apply_listing_update(
+        ctx.program_id,
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.author_profile,
+        &ctx.accounts.config,
+        ctx.accounts.author_bond.as_ref(),
+        skill_uri,
+        name,
+        description,
+        price_lamports,
+    )
+}
+
+/// Validates and applies new listing content, moving the listing between the paid and
+/// free counts if its price crosses zero. Shared by the owner/delegate path and the
+/// session-key path.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn apply_listing_update<'info>(
+    program_id: &Pubkey,
+    skill_listing: &mut Account<'info, SkillListing>,
+    author_profile: &mut Account<'info, AgentProfile>,
+    config: &Account<'info, ReputationConfig>,
+    author_bond: Option<&Account<'info, AuthorBond>>,
+    skill_uri: String,
+    name: String,
+    description: String,
+    price_lamports: u64,
 ) -> Result<()> {
     require!(
         skill_uri.len() <= SkillListing::MAX_URI_LEN,
@@ -61,15 +90,14 @@ pub fn handler(
 
     if SkillListing::is_free_price(price_lamports) {
         validate_free_listing_bond(
-            ctx.program_id,
-            &ctx.accounts.author.key(),
-            &ctx.accounts.author_profile,
-            &ctx.accounts.config,
-            ctx.accounts.author_bond.as_ref(),
+            program_id,
+            &author_profile.agent_id,
+            author_profile,
+            config,
+            author_bond,
         )?;
     }
 
-    let skill_listing = &mut ctx.accounts.skill_listing;
     let clock = Clock::get()?;
     let was_free = SkillListing::is_free_price(skill_listing.price_lamports);
     let will_be_free = SkillListing::is_free_price(price_lamports);
@@ -81,24 +109,22 @@ pub fn handler(
     skill_listing.updated_at = clock.unix_timestamp;
 
     if !was_free && will_be_free {
-        ctx.accounts.author_profile.active_free_skill_listings = ctx
-            .accounts
-            .author_profile
+        author_profile.active_free_skill_listings = author_profile
             .active_free_skill_listings
             .checked_add(1)
             .ok_or(UpdateSkillError::FreeListingCountOverflow)?;
     } else if was_free && !will_be_free {
-        ctx.accounts.author_profile.active_free_skill_listings = ctx
-            .accounts
-            .author_profile
+        author_profile.active_free_skill_listings = author_profile
             .active_free_skill_listings
             .checked_sub(1)
             .ok_or(UpdateSkillError::FreeListingCountUnderflow)?;
     }
 
     emit!(SkillListingUpdated {
-        skill_listing: ctx.accounts.skill_listing.key(),
-        author: ctx.accounts.author.key(),
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        skill_listing: skill_listing.key(),
+        author: author_profile.agent_id,
         name,
         price_lamports,
         timestamp: clock.unix_timestamp,
@@ -166,4 +192,6 @@ pub enum UpdateSkillError {
     FreeListingCountOverflow,
     #[msg("Active free listing count underflowed")]
     FreeListingCountUnderflow,
+    #[msg("Signer is neither the agent authority nor its delegate")]
+    UnauthorizedAgentOperator,
 }
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::update_skill_listing::{apply_listing_update, UpdateSkillError};
+use crate::state::{
+    AgentProfile, AgentSessionKey, AuthorBond, ReputationConfig, SkillListing, SkillStatus,
+    SESSION_SCOPE_UPDATE,
+};
+
+/// Updates an agent's skill listing using a scoped session key.
+#[derive(Accounts)]
+#[instruction(skill_id: String)]
+pub struct UpdateSkillListingWithSession<'info> {
+    #[account(
+        mut,
+        seeds = [b"skill", author_profile.agent_id.as_ref(), skill_id.as_bytes()],
+        bump = skill_listing.bump,
+        constraint = skill_listing.author == author_profile.agent_id @ UpdateSkillError::NotAuthor,
+        constraint = skill_listing.status != SkillStatus::Removed @ UpdateSkillError::SkillRemoved,
+    )]
+    pub skill_listing: Account<'info, SkillListing>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
+        bump = author_profile.bump,
+    )]
+    pub author_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"session_key", author_profile.key().as_ref(), session_signer.key().as_ref()],
+        bump = agent_session_key.bump,
+        constraint = agent_session_key.is_issued_by_current_authority(&author_profile) @ UpdateSkillListingWithSessionError::SessionKeyAuthorityRotated,
+    )]
+    pub agent_session_key: Account<'info, AgentSessionKey>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub author_bond: Option<Account<'info, AuthorBond>>,
+
+    pub session_signer: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<UpdateSkillListingWithSession>,
+    _skill_id: String,
+    skill_uri: String,
+    name: String,
+    description: String,
+    price_lamports: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts
+            .agent_session_key
+            .allows(SESSION_SCOPE_UPDATE, Clock::get()?.unix_timestamp),
+        UpdateSkillListingWithSessionError::SessionKeyNotAuthorized
+    );
+
+    apply_listing_update(
+        ctx.program_id,
+        &mut ctx.accounts.skill_listing,
+        &mut ctx.accounts.author_profile,
+        &ctx.accounts.config,
+        ctx.accounts.author_bond.as_ref(),
+        skill_uri,
+        name,
+        description,
+        price_lamports,
+    )
+}
+
+#[error_code]
+pub enum UpdateSkillListingWithSessionError {
+    #[msg("Session key is expired or lacks the update scope")]
+    SessionKeyNotAuthorized,
+    #[msg("Session key was issued by an authority the agent has since rotated away from")]
+    SessionKeyAuthorityRotated,
+}
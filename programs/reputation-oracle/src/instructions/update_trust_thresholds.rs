@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{TrustThresholdsUpdated, EVENT_VERSION};
+use crate::state::ReputationConfig;
+
+#[derive(Accounts)]
+pub struct UpdateTrustThresholds<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ UpdateTrustThresholdsError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn handler(
+    ctx: Context<UpdateTrustThresholds>,
+    verified_min_vouchers: u32,
+    verified_min_total_stake: u64,
+    verified_min_author_bond: u64,
+    verified_dispute_lookback: i64,
+    verified_min_account_age: i64,
+) -> Result<()> {
+    require!(
+        verified_dispute_lookback >= 0 && verified_min_account_age >= 0,
+        UpdateTrustThresholdsError::InvalidThresholdDuration
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.verified_min_vouchers = verified_min_vouchers;
+    config.verified_min_total_stake = verified_min_total_stake;
+    config.verified_min_author_bond = verified_min_author_bond;
+    config.verified_dispute_lookback = verified_dispute_lookback;
+    config.verified_min_account_age = verified_min_account_age;
+
+    emit!(TrustThresholdsUpdated {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        config: config.key(),
+        verified_min_vouchers,
+        verified_min_total_stake,
+        verified_min_author_bond,
+        verified_dispute_lookback,
+        verified_min_account_age,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum UpdateTrustThresholdsError {
+    #[msg("Only the config authority can update trust thresholds")]
+    UnauthorizedConfigAuthority,
+    #[msg("Trust threshold durations must not be negative")]
+    InvalidThresholdDuration,
+}
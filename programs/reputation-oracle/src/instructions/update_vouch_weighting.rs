@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{VouchWeightingUpdated, EVENT_VERSION};
+use crate::state::ReputationConfig;
+
+#[derive(Accounts)]
+pub struct UpdateVouchWeighting<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.authority == authority.key() @ UpdateVouchWeightingError::UnauthorizedConfigAuthority
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Updates the sybil-resistance parameters applied to future vouches.
+/// Existing vouches keep the weight they were counted at until topped up or revoked.
+pub(crate) fn handler(
+    ctx: Context<UpdateVouchWeighting>,
+    min_voucher_age: i64,
+    min_voucher_reputation: u64,
+    full_weight_voucher_reputation: u64,
+) -> Result<()> {
+    require!(
+        min_voucher_age >= 0,
+        UpdateVouchWeightingError::InvalidMinVoucherAge
+    );
+    require!(
+        full_weight_voucher_reputation >= min_voucher_reputation,
+        UpdateVouchWeightingError::InvalidFullWeightReputation
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.min_voucher_age = min_voucher_age;
+    config.min_voucher_reputation = min_voucher_reputation;
+    config.full_weight_voucher_reputation = full_weight_voucher_reputation;
+
+    emit!(VouchWeightingUpdated {
+        event_version: EVENT_VERSION,
+        chain_context_id: config.chain_context_id,
+        config: config.key(),
+        min_voucher_age,
+        min_voucher_reputation,
+        full_weight_voucher_reputation,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum UpdateVouchWeightingError {
+    #[msg("Only the config authority can update vouch weighting")]
+    UnauthorizedConfigAuthority,
+    #[msg("Minimum voucher age must not be negative")]
+    InvalidMinVoucherAge,
+    #[msg("Full-weight voucher reputation must be at least the minimum voucher reputation")]
+    InvalidFullWeightReputation,
+}
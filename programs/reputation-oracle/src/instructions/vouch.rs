@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use crate::state::{AgentProfile, Vouch, VouchStatus, ReputationConfig};
-use crate::events::VouchCreated;
+use crate::events::{emit_reputation_score_changed, VouchCreated, EVENT_VERSION};
 
 #[derive(Accounts)]
 #[instruction(stake_amount: u64)]
@@ -17,14 +17,15 @@ pub struct CreateVouch<'info> {
     
     #[account(
         mut,
-        seeds = [b"agent", voucher.key().as_ref()],
-        bump = voucher_profile.bump
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ VouchError::UnauthorizedAgentAuthority
     )]
     pub voucher_profile: Account<'info, AgentProfile>,
     
     #[account(
         mut,
-        seeds = [b"agent", vouchee_profile.authority.as_ref()],
+        seeds = [b"agent", vouchee_profile.agent_id.as_ref()],
         bump = vouchee_profile.bump
     )]
     pub vouchee_profile: Account<'info, AgentProfile>,
@@ -41,7 +42,7 @@ pub struct CreateVouch<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
+pub(crate) fn handler(
     ctx: Context<CreateVouch>,
     stake_amount: u64,
 ) -> Result<()> {
@@ -49,12 +50,12 @@ pub fn handler(
 
     require!(
         stake_amount >= config.min_stake,
-        ErrorCode::StakeBelowMinimum
+        VouchError::StakeBelowMinimum
     );
 
     require!(
-        ctx.accounts.voucher_profile.authority != ctx.accounts.vouchee_profile.authority,
-        ErrorCode::CannotVouchForSelf
+        ctx.accounts.voucher_profile.key() != ctx.accounts.vouchee_profile.key(),
+        VouchError::CannotVouchForSelf
     );
 
     let clock = Clock::get()?;
@@ -68,18 +69,18 @@ pub fn handler(
         is_new_relationship
             || is_reactivation
             || existing_status.is_live(),
-        ErrorCode::VouchNotReusable
+        VouchError::VouchNotReusable
     );
 
     require!(
         is_new_relationship
             || existing_voucher == ctx.accounts.voucher_profile.key(),
-        ErrorCode::VouchAccountMismatch
+        VouchError::VouchAccountMismatch
     );
     require!(
         is_new_relationship
             || existing_vouchee == ctx.accounts.vouchee_profile.key(),
-        ErrorCode::VouchAccountMismatch
+        VouchError::VouchAccountMismatch
     );
 
     // Transfer the newly committed stake into the canonical vouch PDA.
@@ -94,6 +95,31 @@ pub fn handler(
         stake_amount,
     )?;
 
+    // A top-up keeps the snapshot taken when the relationship opened, so stake moved
+    // around a clique cannot re-commit credit the vouchers received from each other.
+    let (voucher_reputation_snapshot, previous_reputation_snapshot) =
+        if is_new_relationship || is_reactivation {
+            (ctx.accounts.voucher_profile.reputation_score, 0)
+        } else {
+            let snapshot = ctx.accounts.vouch.voucher_reputation_snapshot;
+            (snapshot, snapshot)
+        };
+
+    // Re-weigh the relationship against the voucher's current age and standing
+    if !is_new_relationship && !is_reactivation {
+        ctx.accounts.vouchee_profile.remove_vouch_weight(
+            ctx.accounts.vouch.effective_weight_bps,
+            ctx.accounts.vouch.low_reputation_voucher,
+        );
+    }
+    let (effective_weight_bps, low_reputation_voucher) = ctx
+        .accounts
+        .vouchee_profile
+        .compute_vouch_weight(&ctx.accounts.voucher_profile, config);
+    ctx.accounts
+        .vouchee_profile
+        .add_vouch_weight(effective_weight_bps, low_reputation_voucher);
+
     let vouch = &mut ctx.accounts.vouch;
     if is_new_relationship {
         vouch.voucher = ctx.accounts.voucher_profile.key();
@@ -103,39 +129,68 @@ pub fn handler(
         vouch.status = VouchStatus::Active;
         vouch.cumulative_revenue = 0;
         vouch.last_payout_at = clock.unix_timestamp;
+        vouch.voucher_reputation_snapshot = voucher_reputation_snapshot;
         vouch.bump = ctx.bumps.vouch;
+        vouch.version = Vouch::CURRENT_VERSION;
     } else if is_reactivation {
         vouch.stake_amount = stake_amount;
         vouch.created_at = clock.unix_timestamp;
         vouch.status = VouchStatus::Active;
         vouch.last_payout_at = clock.unix_timestamp;
+        vouch.voucher_reputation_snapshot = voucher_reputation_snapshot;
     } else {
         vouch.stake_amount = vouch
             .stake_amount
             .checked_add(stake_amount)
-            .ok_or(ErrorCode::StakeOverflow)?;
+            .ok_or(VouchError::StakeOverflow)?;
         vouch.status = VouchStatus::Active;
     }
+    vouch.last_stake_increase_at = clock.unix_timestamp;
+    vouch.effective_weight_bps = effective_weight_bps;
+    vouch.low_reputation_voucher = low_reputation_voucher;
 
     let voucher_profile = &mut ctx.accounts.voucher_profile;
     if is_new_relationship || is_reactivation {
         voucher_profile.total_vouches_given = voucher_profile.total_vouches_given.saturating_add(1);
     }
+    if is_new_relationship {
+        voucher_profile.vouch_accounts_given = voucher_profile.vouch_accounts_given.saturating_add(1);
+    }
 
     let vouchee_profile = &mut ctx.accounts.vouchee_profile;
     if is_new_relationship || is_reactivation {
         vouchee_profile.total_vouches_received = vouchee_profile.total_vouches_received.saturating_add(1);
     }
+    if is_new_relationship {
+        vouchee_profile.vouch_accounts_received =
+            vouchee_profile.vouch_accounts_received.saturating_add(1);
+    }
     vouchee_profile.total_staked_for = vouchee_profile.total_staked_for.saturating_add(stake_amount);
+    vouchee_profile.vouched_reputation_total = vouchee_profile
+        .vouched_reputation_total
+        .saturating_sub(previous_reputation_snapshot)
+        .saturating_add(voucher_reputation_snapshot);
 
     // Recompute reputation
+    let previous_score = vouchee_profile.reputation_score;
     vouchee_profile.reputation_score = vouchee_profile.compute_reputation(config);
+    vouchee_profile.trust_tier = vouchee_profile.compute_trust_tier(config);
+    emit_reputation_score_changed(
+        config,
+        vouchee_profile.key(),
+        previous_score,
+        vouchee_profile.reputation_score,
+    )?;
 
     emit!(VouchCreated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
         vouch: ctx.accounts.vouch.key(),
         voucher: ctx.accounts.voucher_profile.key(),
         vouchee: ctx.accounts.vouchee_profile.key(),
         stake_amount,
+        total_stake_amount: ctx.accounts.vouch.stake_amount,
+        effective_weight_bps,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -143,7 +198,7 @@ pub fn handler(
 }
 
 #[error_code]
-pub enum ErrorCode {
+pub enum VouchError {
     #[msg("Stake amount is below minimum")]
     StakeBelowMinimum,
     #[msg("Cannot vouch for yourself")]
@@ -154,4 +209,6 @@ pub enum ErrorCode {
     VouchAccountMismatch,
     #[msg("This vouch relationship cannot accept new stake in its current state")]
     VouchNotReusable,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
 }
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::events::{emit_reputation_score_changed, VouchCreated, EVENT_VERSION};
+use crate::state::{
+    vouch_weight_for, AgentProfile, ForeignAgentRecord, ReputationConfig, Vouch, VouchStatus,
+};
+
+/// Stakes behind an unclaimed foreign agent. The vouch PDA uses the record as its
+/// vouchee, so the regular `close_vouch` path still applies once it is revoked.
+#[derive(Accounts)]
+pub struct VouchForeignAgent<'info> {
+    #[account(
+        init_if_needed,
+        payer = voucher,
+        space = Vouch::LEN,
+        seeds = [b"vouch", voucher_profile.key().as_ref(), foreign_agent.key().as_ref()],
+        bump
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ VouchForeignAgentError::UnauthorizedAgentAuthority
+    )]
+    pub voucher_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"foreign_agent", ForeignAgentRecord::seed_hash(&foreign_agent.canonical_agent_id).as_ref()],
+        bump = foreign_agent.bump,
+        constraint = !foreign_agent.is_claimed() @ VouchForeignAgentError::ForeignAgentClaimed
+    )]
+    pub foreign_agent: Account<'info, ForeignAgentRecord>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub(crate) fn handler(ctx: Context<VouchForeignAgent>, stake_amount: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(
+        stake_amount >= config.min_stake,
+        VouchForeignAgentError::StakeBelowMinimum
+    );
+
+    let clock = Clock::get()?;
+    let is_new_relationship = ctx.accounts.vouch.is_uninitialized();
+    let existing_status = ctx.accounts.vouch.status;
+    let is_reactivation = !is_new_relationship && existing_status == VouchStatus::Revoked;
+    require!(
+        is_new_relationship || is_reactivation || existing_status.is_live(),
+        VouchForeignAgentError::VouchNotReusable
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.voucher.to_account_info(),
+                to: ctx.accounts.vouch.to_account_info(),
+            },
+        ),
+        stake_amount,
+    )?;
+
+    // A top-up keeps the snapshot taken when the relationship opened, as in `vouch`;
+    // only the weight is re-evaluated.
+    let (voucher_reputation_snapshot, previous_reputation_snapshot) =
+        if is_new_relationship || is_reactivation {
+            (ctx.accounts.voucher_profile.reputation_score, 0)
+        } else {
+            let snapshot = ctx.accounts.vouch.voucher_reputation_snapshot;
+            (snapshot, snapshot)
+        };
+
+    let foreign_agent = &mut ctx.accounts.foreign_agent;
+    if !is_new_relationship && !is_reactivation {
+        foreign_agent.remove_vouch_weight(
+            ctx.accounts.vouch.effective_weight_bps,
+            ctx.accounts.vouch.low_reputation_voucher,
+        );
+    }
+    let (effective_weight_bps, low_reputation_voucher) = vouch_weight_for(
+        &ctx.accounts.voucher_profile,
+        foreign_agent.low_reputation_vouches_received,
+        config,
+    );
+    foreign_agent.add_vouch_weight(effective_weight_bps, low_reputation_voucher);
+
+    let vouch = &mut ctx.accounts.vouch;
+    if is_new_relationship {
+        vouch.voucher = ctx.accounts.voucher_profile.key();
+        vouch.vouchee = foreign_agent.key();
+        vouch.stake_amount = stake_amount;
+        vouch.created_at = clock.unix_timestamp;
+        vouch.cumulative_revenue = 0;
+        vouch.last_payout_at = clock.unix_timestamp;
+        vouch.pending_dispute_links = 0;
+        vouch.voucher_reputation_snapshot = voucher_reputation_snapshot;
+        vouch.bump = ctx.bumps.vouch;
+        vouch.version = Vouch::CURRENT_VERSION;
+    } else if is_reactivation {
+        vouch.stake_amount = stake_amount;
+        vouch.created_at = clock.unix_timestamp;
+        vouch.last_payout_at = clock.unix_timestamp;
+        vouch.voucher_reputation_snapshot = voucher_reputation_snapshot;
+    } else {
+        vouch.stake_amount = vouch
+            .stake_amount
+            .checked_add(stake_amount)
+            .ok_or(VouchForeignAgentError::StakeOverflow)?;
+    }
+    vouch.status = VouchStatus::Active;
+    vouch.last_stake_increase_at = clock.unix_timestamp;
+    vouch.effective_weight_bps = effective_weight_bps;
+    vouch.low_reputation_voucher = low_reputation_voucher;
+
+    if is_new_relationship || is_reactivation {
+        let voucher_profile = &mut ctx.accounts.voucher_profile;
+        voucher_profile.total_vouches_given = voucher_profile.total_vouches_given.saturating_add(1);
+        foreign_agent.total_vouches_received =
+            foreign_agent.total_vouches_received.saturating_add(1);
+    }
+    if is_new_relationship {
+        let voucher_profile = &mut ctx.accounts.voucher_profile;
+        voucher_profile.vouch_accounts_given = voucher_profile.vouch_accounts_given.saturating_add(1);
+    }
+    foreign_agent.total_staked_for = foreign_agent.total_staked_for.saturating_add(stake_amount);
+    foreign_agent.vouched_reputation_total = foreign_agent
+        .vouched_reputation_total
+        .saturating_sub(previous_reputation_snapshot)
+        .saturating_add(voucher_reputation_snapshot);
+    let previous_score = foreign_agent.reputation_score;
+    foreign_agent.reputation_score = foreign_agent.compute_reputation(config);
+    emit_reputation_score_changed(
+        config,
+        foreign_agent.key(),
+        previous_score,
+        foreign_agent.reputation_score,
+    )?;
+
+    emit!(VouchCreated {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        vouch: vouch.key(),
+        voucher: vouch.voucher,
+        vouchee: vouch.vouchee,
+        stake_amount,
+        total_stake_amount: vouch.stake_amount,
+        effective_weight_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum VouchForeignAgentError {
+    #[msg("Stake amount is below minimum")]
+    StakeBelowMinimum,
+    #[msg("Stake amount overflowed the existing vouch")]
+    StakeOverflow,
+    #[msg("This vouch relationship cannot accept new stake in its current state")]
+    VouchNotReusable,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+    #[msg("Foreign agent has been claimed; vouch for its AgentProfile instead")]
+    ForeignAgentClaimed,
+}
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::events::emit_reputation_score_changed;
 use crate::state::{AgentProfile, AuthorBond, ReputationConfig, Vouch, VouchStatus};
 
 pub(crate) fn compute_slash_amount(stake_amount: u64, slash_percentage: u8) -> u64 {
@@ -20,15 +21,44 @@ pub(crate) fn slash_vouch_with_amount<'info>(
         VouchSettlementError::InvalidSlashAmount
     );
 
-    vouch.status = VouchStatus::Slashed;
+    // Only the first slash removes the vouch from both profiles; later overlapping
+    // disputes just take from the residual stake left on the account.
+    if vouch.status.is_live() {
+        voucher_profile.total_vouches_given = voucher_profile.total_vouches_given.saturating_sub(1);
+        let previous_score = voucher_profile.reputation_score;
+        voucher_profile.reputation_score = voucher_profile.compute_reputation(config);
+        voucher_profile.trust_tier = voucher_profile.compute_trust_tier(config);
+        emit_reputation_score_changed(
+            config,
+            voucher_profile.key(),
+            previous_score,
+            voucher_profile.reputation_score,
+        )?;
 
-    voucher_profile.total_vouches_given = voucher_profile.total_vouches_given.saturating_sub(1);
-    voucher_profile.reputation_score = voucher_profile.compute_reputation(config);
+        vouchee_profile.total_vouches_received =
+            vouchee_profile.total_vouches_received.saturating_sub(1);
+        vouchee_profile.total_staked_for =
+            vouchee_profile.total_staked_for.saturating_sub(vouch.stake_amount);
+        vouchee_profile.vouched_reputation_total = vouchee_profile
+            .vouched_reputation_total
+            .saturating_sub(vouch.voucher_reputation_snapshot);
+        vouchee_profile.remove_vouch_weight(vouch.effective_weight_bps, vouch.low_reputation_voucher);
+        let previous_score = vouchee_profile.reputation_score;
+        vouchee_profile.reputation_score = vouchee_profile.compute_reputation(config);
+        vouchee_profile.trust_tier = vouchee_profile.compute_trust_tier(config);
+        emit_reputation_score_changed(
+            config,
+            vouchee_profile.key(),
+            previous_score,
+            vouchee_profile.reputation_score,
+        )?;
 
-    vouchee_profile.total_vouches_received = vouchee_profile.total_vouches_received.saturating_sub(1);
-    vouchee_profile.total_staked_for =
-        vouchee_profile.total_staked_for.saturating_sub(vouch.stake_amount);
-    vouchee_profile.reputation_score = vouchee_profile.compute_reputation(config);
+        vouch.status = VouchStatus::Slashed;
+    }
+    vouch.stake_amount = vouch
+        .stake_amount
+        .checked_sub(slash_amount)
+        .ok_or(VouchSettlementError::InvalidSlashAmount)?;
 
     **vouch.to_account_info().try_borrow_mut_lamports()? = vouch
         .to_account_info()
@@ -60,7 +90,15 @@ pub(crate) fn slash_author_bond<'info>(
         .author_bond_lamports
         .checked_sub(slash_amount)
         .ok_or(VouchSettlementError::InsufficientBondAmount)?;
+    let previous_score = author_profile.reputation_score;
     author_profile.reputation_score = author_profile.compute_reputation(config);
+    author_profile.trust_tier = author_profile.compute_trust_tier(config);
+    emit_reputation_score_changed(
+        config,
+        author_profile.key(),
+        previous_score,
+        author_profile.reputation_score,
+    )?;
 
     **author_bond.to_account_info().try_borrow_mut_lamports()? = author_bond
         .to_account_info()
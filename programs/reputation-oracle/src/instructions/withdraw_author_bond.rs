@@ -1,22 +1,23 @@
 use anchor_lang::prelude::*;
 
-use crate::events::AuthorBondWithdrawn;
+use crate::events::{emit_reputation_score_changed, AuthorBondWithdrawn, EVENT_VERSION};
 use crate::state::{AgentProfile, AuthorBond, ReputationConfig, AUTHOR_BOND_SEED};
 
 #[derive(Accounts)]
 pub struct WithdrawAuthorBond<'info> {
     #[account(
         mut,
-        seeds = [AUTHOR_BOND_SEED, author.key().as_ref()],
+        seeds = [AUTHOR_BOND_SEED, author_profile.agent_id.as_ref()],
         bump = author_bond.bump,
-        constraint = author_bond.author == author.key() @ ErrorCode::AuthorBondAuthorityMismatch
+        constraint = author_bond.author == author_profile.agent_id @ WithdrawAuthorBondError::AuthorBondAuthorityMismatch
     )]
     pub author_bond: Account<'info, AuthorBond>,
 
     #[account(
         mut,
-        seeds = [b"agent", author.key().as_ref()],
+        seeds = [b"agent", author_profile.agent_id.as_ref()],
         bump = author_profile.bump,
+        constraint = author_profile.is_owner(&author.key()) @ WithdrawAuthorBondError::UnauthorizedAgentAuthority,
     )]
     pub author_profile: Account<'info, AgentProfile>,
 
@@ -30,25 +31,25 @@ pub struct WithdrawAuthorBond<'info> {
     pub author: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<WithdrawAuthorBond>, amount: u64) -> Result<()> {
-    require!(amount > 0, ErrorCode::AmountMustBePositive);
+pub(crate) fn handler(ctx: Context<WithdrawAuthorBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, WithdrawAuthorBondError::AmountMustBePositive);
 
     let clock = Clock::get()?;
     let author_bond = &mut ctx.accounts.author_bond;
     let remaining_bond = author_bond
         .amount
         .checked_sub(amount)
-        .ok_or(ErrorCode::InsufficientBondAmount)?;
+        .ok_or(WithdrawAuthorBondError::InsufficientBondAmount)?;
 
     if ctx.accounts.author_profile.active_free_skill_listings > 0 {
         require!(
             remaining_bond >= ctx.accounts.config.min_author_bond_for_free_listing,
-            ErrorCode::FreeListingsRequireBondFloor
+            WithdrawAuthorBondError::FreeListingsRequireBondFloor
         );
     }
     require!(
         ctx.accounts.author_profile.open_author_disputes == 0,
-        ErrorCode::AuthorBondLockedWhileDisputesOpen
+        WithdrawAuthorBondError::AuthorBondLockedWhileDisputesOpen
     );
 
     let minimum_rent = Rent::get()?.minimum_balance(AuthorBond::LEN);
@@ -56,10 +57,10 @@ pub fn handler(ctx: Context<WithdrawAuthorBond>, amount: u64) -> Result<()> {
         .to_account_info()
         .lamports()
         .checked_sub(amount)
-        .ok_or(ErrorCode::InsufficientLamports)?;
+        .ok_or(WithdrawAuthorBondError::InsufficientLamports)?;
     require!(
         remaining_lamports >= minimum_rent,
-        ErrorCode::InsufficientLamports
+        WithdrawAuthorBondError::InsufficientLamports
     );
 
     author_bond.amount = remaining_bond;
@@ -69,8 +70,16 @@ pub fn handler(ctx: Context<WithdrawAuthorBond>, amount: u64) -> Result<()> {
     author_profile.author_bond_lamports = author_profile
         .author_bond_lamports
         .checked_sub(amount)
-        .ok_or(ErrorCode::InsufficientBondAmount)?;
+        .ok_or(WithdrawAuthorBondError::InsufficientBondAmount)?;
+    let previous_score = author_profile.reputation_score;
     author_profile.reputation_score = author_profile.compute_reputation(&ctx.accounts.config);
+    author_profile.trust_tier = author_profile.compute_trust_tier(&ctx.accounts.config);
+    emit_reputation_score_changed(
+        &ctx.accounts.config,
+        author_profile.key(),
+        previous_score,
+        author_profile.reputation_score,
+    )?;
 
     **author_bond.to_account_info().try_borrow_mut_lamports()? = remaining_lamports;
     **ctx.accounts.author.to_account_info().try_borrow_mut_lamports()? = ctx
@@ -79,11 +88,13 @@ pub fn handler(ctx: Context<WithdrawAuthorBond>, amount: u64) -> Result<()> {
         .to_account_info()
         .lamports()
         .checked_add(amount)
-        .ok_or(ErrorCode::LamportOverflow)?;
+        .ok_or(WithdrawAuthorBondError::LamportOverflow)?;
 
     emit!(AuthorBondWithdrawn {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
         author_bond: author_bond.key(),
-        author: ctx.accounts.author.key(),
+        author: ctx.accounts.author_profile.agent_id,
         amount,
         total_bond_amount: author_bond.amount,
         timestamp: clock.unix_timestamp,
@@ -93,7 +104,7 @@ pub fn handler(ctx: Context<WithdrawAuthorBond>, amount: u64) -> Result<()> {
 }
 
 #[error_code]
-pub enum ErrorCode {
+pub enum WithdrawAuthorBondError {
     #[msg("Amount must be greater than zero")]
     AmountMustBePositive,
     #[msg("Author bond authority mismatch")]
@@ -108,4 +119,6 @@ pub enum ErrorCode {
     InsufficientLamports,
     #[msg("Lamport amount overflowed")]
     LamportOverflow,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
 }
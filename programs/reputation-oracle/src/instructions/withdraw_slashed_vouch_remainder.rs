@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::events::{SlashedVouchRemainderWithdrawn, EVENT_VERSION};
+use crate::state::{AgentProfile, Vouch, VouchStatus, ReputationConfig};
+
+#[derive(Accounts)]
+pub struct WithdrawSlashedVouchRemainder<'info> {
+    #[account(
+        mut,
+        seeds = [b"vouch", voucher_profile.key().as_ref(), vouch.vouchee.as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.voucher == voucher_profile.key() @ WithdrawSlashedVouchRemainderError::UnauthorizedRemainderWithdrawal,
+        constraint = vouch.status == VouchStatus::Slashed @ WithdrawSlashedVouchRemainderError::VouchNotSlashed,
+        constraint = !vouch.is_locked_by_disputes() @ WithdrawSlashedVouchRemainderError::VouchLockedByPendingDisputes
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(
+        seeds = [b"agent", voucher_profile.agent_id.as_ref()],
+        bump = voucher_profile.bump,
+        constraint = voucher_profile.is_owner(&voucher.key()) @ WithdrawSlashedVouchRemainderError::UnauthorizedAgentAuthority
+    )]
+    pub voucher_profile: Account<'info, AgentProfile>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+}
+
+pub(crate) fn handler(ctx: Context<WithdrawSlashedVouchRemainder>) -> Result<()> {
+    let vouch = &mut ctx.accounts.vouch;
+    let remainder = vouch.stake_amount;
+    require!(remainder > 0, WithdrawSlashedVouchRemainderError::NoRemainderToWithdraw);
+
+    // Profiles already dropped this vouch when it was first slashed, so only the
+    // residual lamports move here. Rent stays until the vouch is closed.
+    vouch.stake_amount = 0;
+    **vouch.to_account_info().try_borrow_mut_lamports()? = vouch
+        .to_account_info()
+        .lamports()
+        .checked_sub(remainder)
+        .ok_or(WithdrawSlashedVouchRemainderError::InsufficientFunds)?;
+    **ctx.accounts.voucher.try_borrow_mut_lamports()? = ctx
+        .accounts
+        .voucher
+        .lamports()
+        .checked_add(remainder)
+        .ok_or(WithdrawSlashedVouchRemainderError::InsufficientFunds)?;
+
+    emit!(SlashedVouchRemainderWithdrawn {
+        event_version: EVENT_VERSION,
+        chain_context_id: ctx.accounts.config.chain_context_id,
+        vouch: vouch.key(),
+        voucher: vouch.voucher,
+        vouchee: vouch.vouchee,
+        stake_returned: remainder,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[error_code]
+pub enum WithdrawSlashedVouchRemainderError {
+    #[msg("Only the original voucher can withdraw a slashed vouch remainder")]
+    UnauthorizedRemainderWithdrawal,
+    #[msg("Vouch has not been slashed")]
+    VouchNotSlashed,
+    #[msg("Slashed vouch remainder is locked while linked to unsettled author disputes")]
+    VouchLockedByPendingDisputes,
+    #[msg("Slashed vouch has no remaining stake to withdraw")]
+    NoRemainderToWithdraw,
+    #[msg("Insufficient funds")]
+    InsufficientFunds,
+    #[msg("Signer is not the agent authority")]
+    UnauthorizedAgentAuthority,
+}
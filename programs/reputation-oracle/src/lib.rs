@@ -2,10 +2,14 @@ use anchor_lang::prelude::*;
 
 pub mod state;
 pub mod instructions;
+pub mod agent_registry;
+mod bytes;
 pub mod events;
+pub mod identity;
+pub mod wormhole;
 
 use instructions::*;
-use state::{AuthorDisputeReason, AuthorDisputeRuling};
+use state::{AuthorDisputeReason, AuthorDisputeRuling, TrustTier};
 
 declare_id!("ELmVnLSNuwNca4PfPqeqNowoUF8aDdtfto3rF9d89wf");
 
@@ -20,6 +24,7 @@ pub mod reputation_oracle {
         min_author_bond_for_free_listing: u64,
         slash_percentage: u8,
         cooldown_period: i64,
+        chain_context: String,
     ) -> Result<()> {
         instructions::initialize_config::handler(
             ctx,
@@ -28,11 +33,69 @@ pub mod reputation_oracle {
             min_author_bond_for_free_listing,
             slash_percentage,
             cooldown_period,
+            chain_context,
         )
     }
 
-    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
-        instructions::migrate_config::handler(ctx)
+    pub fn migrate_config(ctx: Context<MigrateConfig>, chain_context: Option<String>) -> Result<()> {
+        instructions::migrate_config::handler(ctx, chain_context)
+    }
+
+    pub fn migrate_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateAccounts<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_accounts::handler(ctx)
+    }
+
+    pub fn update_trust_thresholds(
+        ctx: Context<UpdateTrustThresholds>,
+        verified_min_vouchers: u32,
+        verified_min_total_stake: u64,
+        verified_min_author_bond: u64,
+        verified_dispute_lookback: i64,
+        verified_min_account_age: i64,
+    ) -> Result<()> {
+        instructions::update_trust_thresholds::handler(
+            ctx,
+            verified_min_vouchers,
+            verified_min_total_stake,
+            verified_min_author_bond,
+            verified_dispute_lookback,
+            verified_min_account_age,
+        )
+    }
+
+    pub fn update_vouch_weighting(
+        ctx: Context<UpdateVouchWeighting>,
+        min_voucher_age: i64,
+        min_voucher_reputation: u64,
+        full_weight_voucher_reputation: u64,
+    ) -> Result<()> {
+        instructions::update_vouch_weighting::handler(
+            ctx,
+            min_voucher_age,
+            min_voucher_reputation,
+            full_weight_voucher_reputation,
+        )
+    }
+
+    pub fn update_chain_context(
+        ctx: Context<UpdateChainContext>,
+        chain_context: String,
+    ) -> Result<()> {
+        instructions::update_chain_context::handler(ctx, chain_context)
+    }
+
+    pub fn update_agent_registry(
+        ctx: Context<UpdateAgentRegistry>,
+        agent_registry_program: Pubkey,
+        agent_registry_collection: Pubkey,
+    ) -> Result<()> {
+        instructions::update_agent_registry::handler(
+            ctx,
+            agent_registry_program,
+            agent_registry_collection,
+        )
     }
 
     pub fn register_agent(
@@ -60,6 +123,104 @@ pub mod reputation_oracle {
         instructions::repair_agent_registered_at::handler(ctx, registered_at)
     }
 
+    pub fn reconcile_agent_profile(ctx: Context<ReconcileAgentProfile>) -> Result<()> {
+        instructions::reconcile_agent_profile::handler(ctx)
+    }
+
+    pub fn sync_agent_profile(ctx: Context<SyncAgentProfile>) -> Result<()> {
+        instructions::sync_agent_profile::handler(ctx)
+    }
+
+    pub fn close_agent_profile(ctx: Context<CloseAgentProfile>) -> Result<()> {
+        instructions::close_agent_profile::handler(ctx)
+    }
+
+    pub fn rotate_agent_authority(ctx: Context<RotateAgentAuthority>) -> Result<()> {
+        instructions::rotate_agent_authority::handler(ctx)
+    }
+
+    pub fn set_agent_delegate(
+        ctx: Context<SetAgentDelegate>,
+        delegate: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_agent_delegate::handler(ctx, delegate)
+    }
+
+    pub fn create_agent_session_key(
+        ctx: Context<CreateAgentSessionKey>,
+        session_key: Pubkey,
+        scopes: u8,
+        spend_cap_lamports: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_agent_session_key::handler(
+            ctx,
+            session_key,
+            scopes,
+            spend_cap_lamports,
+            expires_at,
+        )
+    }
+
+    pub fn revoke_agent_session_key(ctx: Context<RevokeAgentSessionKey>) -> Result<()> {
+        instructions::revoke_agent_session_key::handler(ctx)
+    }
+
+    pub fn bind_agent_identity(
+        ctx: Context<BindAgentIdentity>,
+        canonical_agent_id: String,
+        raw_upstream_label: String,
+    ) -> Result<()> {
+        instructions::bind_agent_identity::handler(ctx, canonical_agent_id, raw_upstream_label)
+    }
+
+    pub fn attest_agent_identity(ctx: Context<AttestAgentIdentity>) -> Result<()> {
+        instructions::attest_agent_identity::handler(ctx)
+    }
+
+    pub fn reassign_agent_identity(ctx: Context<ReassignAgentIdentity>) -> Result<()> {
+        instructions::reassign_agent_identity::handler(ctx)
+    }
+
+    pub fn create_foreign_agent_record(
+        ctx: Context<CreateForeignAgentRecord>,
+        canonical_agent_id: String,
+    ) -> Result<()> {
+        instructions::create_foreign_agent_record::handler(ctx, canonical_agent_id)
+    }
+
+    pub fn vouch_foreign_agent(ctx: Context<VouchForeignAgent>, stake_amount: u64) -> Result<()> {
+        instructions::vouch_foreign_agent::handler(ctx, stake_amount)
+    }
+
+    pub fn revoke_foreign_vouch(ctx: Context<RevokeForeignVouch>) -> Result<()> {
+        instructions::revoke_foreign_vouch::handler(ctx)
+    }
+
+    pub fn claim_foreign_agent_record(ctx: Context<ClaimForeignAgentRecord>) -> Result<()> {
+        instructions::claim_foreign_agent_record::handler(ctx)
+    }
+
+    pub fn register_wormhole_emitter(
+        ctx: Context<RegisterWormholeEmitter>,
+        chain: u16,
+        address: [u8; 32],
+        chain_context: String,
+    ) -> Result<()> {
+        instructions::register_wormhole_emitter::handler(ctx, chain, address, chain_context)
+    }
+
+    pub fn post_foreign_reputation_attestation(
+        ctx: Context<PostForeignReputationAttestation>,
+        canonical_agent_id: String,
+    ) -> Result<()> {
+        instructions::post_foreign_reputation_attestation::handler(ctx, canonical_agent_id)
+    }
+
+    pub fn publish_reputation_message(ctx: Context<PublishReputationMessage>) -> Result<()> {
+        instructions::publish_reputation_message::handler(ctx)
+    }
+
     pub fn deposit_author_bond(
         ctx: Context<DepositAuthorBond>,
         amount: u64,
@@ -85,6 +246,29 @@ pub mod reputation_oracle {
         instructions::revoke_vouch::handler(ctx)
     }
 
+    pub fn decrease_vouch_stake(
+        ctx: Context<DecreaseVouchStake>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::decrease_vouch_stake::handler(ctx, amount)
+    }
+
+    pub fn withdraw_slashed_vouch_remainder(
+        ctx: Context<WithdrawSlashedVouchRemainder>,
+    ) -> Result<()> {
+        instructions::withdraw_slashed_vouch_remainder::handler(ctx)
+    }
+
+    pub fn close_vouch(ctx: Context<CloseVouch>) -> Result<()> {
+        instructions::close_vouch::handler(ctx)
+    }
+
+    pub fn refresh_trust_depth<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefreshTrustDepth<'info>>,
+    ) -> Result<()> {
+        instructions::refresh_trust_depth::handler(ctx)
+    }
+
     pub fn open_author_dispute<'info>(
         ctx: Context<'_, '_, 'info, 'info, OpenAuthorDispute<'info>>,
         dispute_id: u64,
@@ -102,6 +286,12 @@ pub mod reputation_oracle {
         instructions::resolve_author_dispute::handler(ctx, dispute_id, ruling)
     }
 
+    pub fn release_author_dispute_vouch_link(
+        ctx: Context<ReleaseAuthorDisputeVouchLink>,
+    ) -> Result<()> {
+        instructions::release_author_dispute_vouch_link::handler(ctx)
+    }
+
     pub fn create_skill_listing(
         ctx: Context<CreateSkillListing>,
         skill_id: String,
@@ -138,6 +328,42 @@ pub mod reputation_oracle {
         )
     }
 
+    pub fn create_skill_listing_with_session(
+        ctx: Context<CreateSkillListingWithSession>,
+        skill_id: String,
+        skill_uri: String,
+        name: String,
+        description: String,
+        price_lamports: u64,
+    ) -> Result<()> {
+        instructions::create_skill_listing_with_session::handler(
+            ctx,
+            skill_id,
+            skill_uri,
+            name,
+            description,
+            price_lamports,
+        )
+    }
+
+    pub fn update_skill_listing_with_session(
+        ctx: Context<UpdateSkillListingWithSession>,
+        skill_id: String,
+        skill_uri: String,
+        name: String,
+        description: String,
+        price_lamports: u64,
+    ) -> Result<()> {
+        instructions::update_skill_listing_with_session::handler(
+            ctx,
+            skill_id,
+            skill_uri,
+            name,
+            description,
+            price_lamports,
+        )
+    }
+
     pub fn remove_skill_listing(
         ctx: Context<RemoveSkillListing>,
         skill_id: String,
@@ -153,10 +379,42 @@ pub mod reputation_oracle {
     }
 
     pub fn purchase_skill(ctx: Context<PurchaseSkill>) -> Result<()> {
-        instructions::purchase_skill::handler(ctx)
+        instructions::purchase_skill::handler(ctx, None)
+    }
+
+    pub fn purchase_skill_with_min_tier(
+        ctx: Context<PurchaseSkill>,
+        min_author_trust_tier: TrustTier,
+    ) -> Result<()> {
+        instructions::purchase_skill::handler(ctx, Some(min_author_trust_tier))
+    }
+
+    pub fn close_purchase(ctx: Context<ClosePurchase>) -> Result<()> {
+        instructions::close_purchase::handler(ctx)
+    }
+
+    pub fn close_agent_purchase(ctx: Context<CloseAgentPurchase>) -> Result<()> {
+        instructions::close_agent_purchase::handler(ctx)
+    }
+
+    pub fn purchase_skill_with_session(
+        ctx: Context<PurchaseSkillWithSession>,
+        min_author_trust_tier: Option<TrustTier>,
+    ) -> Result<()> {
+        instructions::purchase_skill_with_session::handler(ctx, min_author_trust_tier)
     }
 
     pub fn claim_voucher_revenue(ctx: Context<ClaimVoucherRevenue>) -> Result<()> {
         instructions::claim_voucher_revenue::handler(ctx)
     }
+
+    pub fn claim_voucher_revenue_with_session(
+        ctx: Context<ClaimVoucherRevenueWithSession>,
+    ) -> Result<()> {
+        instructions::claim_voucher_revenue_with_session::handler(ctx)
+    }
+
+    pub fn get_reputation(ctx: Context<GetReputation>) -> Result<ReputationAttestation> {
+        instructions::get_reputation::handler(ctx)
+    }
 }
@@ -1,5 +1,16 @@
 use anchor_lang::prelude::*;
 
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Trust classification derived from the config's verification thresholds.
+/// Variants are ordered so clients can compare against a minimum tier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TrustTier {
+    Unverified, // No live backing, or an upheld dispute inside the lookback window
+    Backed,     // Has live vouches or an author bond, but misses a verification threshold
+    Verified,   // Meets every configured verification threshold
+}
+
 #[account]
 pub struct AgentProfile {
     pub authority: Pubkey,           // Agent's wallet
@@ -12,11 +23,30 @@ pub struct AgentProfile {
     pub active_free_skill_listings: u32, // Active zero-price listings gated by the author bond
     pub open_author_disputes: u32,   // Open author-wide disputes that freeze bond withdrawals
     pub registered_at: i64,          // Timestamp
+    pub vouched_reputation_total: u64, // Sum of voucher reputation snapshots across live vouches received
+    pub trust_depth: u8,             // Upstream vouch hops reached by the last trust-depth refresh
+    pub trust_depth_updated_at: i64, // Timestamp of the last trust-depth refresh
+    pub trust_tier: TrustTier,       // Recomputed whenever vouch, bond, or dispute state changes
+    pub last_upheld_dispute_at: i64, // Timestamp of the most recent upheld author dispute (0 if none)
+    pub effective_vouch_weight: u64, // Sybil-adjusted vouch weight in basis points (10_000 = one full vouch)
+    pub low_reputation_vouches_received: u32, // Live counted vouches from vouchers below the full-weight reputation
+    pub active_skill_listings: u32,  // Listings created and not yet removed, free or paid
+    pub agent_id: Pubkey,            // Registering wallet; stable PDA seed that survives authority rotation
+    pub delegate: Pubkey,            // Operational key for day-to-day listing management (default = none)
     pub bump: u8,                    // PDA bump
+    pub version: u8,                 // Layout version; accounts written before versioning read as 0
+    pub counters_verified: bool,     // Counters match a complete account tally; false for profiles migrated from older layouts
+    pub claimed_foreign_staked_for: u64, // Stake on foreign records this agent claimed; scored but never slashed or paid revenue
+    pub claimed_foreign_vouch_weight: u64, // Vouch weight on claimed foreign records, in basis points
+    pub claimed_foreign_reputation_total: u64, // Voucher reputation snapshots across live vouches on claimed foreign records
+    pub vouch_accounts_received: u32, // Vouch accounts naming this agent as vouchee, any status; changed only on creation and close
+    pub vouch_accounts_given: u32,   // Vouch accounts this agent opened, any status; changed only on creation and close
 }
 
 impl AgentProfile {
     pub const MAX_URI_LENGTH: usize = 200;
+    /// Bumped with each layout change; `account_migration` upgrades older versions.
+    pub const CURRENT_VERSION: u8 = 2;
     
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
@@ -29,23 +59,371 @@ impl AgentProfile {
         4 + // active_free_skill_listings
         4 + // open_author_disputes
         8 + // registered_at
-        1; // bump
+        8 + // vouched_reputation_total
+        1 + // trust_depth
+        8 + // trust_depth_updated_at
+        1 + // trust_tier
+        8 + // last_upheld_dispute_at
+        8 + // effective_vouch_weight
+        4 + // low_reputation_vouches_received
+        4 + // active_skill_listings
+        32 + // agent_id
+        32 + // delegate
+        1 + // bump
+        1 + // version
+        1 + // counters_verified
+        8 + // claimed_foreign_staked_for
+        8 + // claimed_foreign_vouch_weight
+        8 + // claimed_foreign_reputation_total
+        4 + // vouch_accounts_received
+        4; // vouch_accounts_given
     
+    pub fn is_owner(&self, key: &Pubkey) -> bool {
+        self.authority == *key
+    }
+
+    /// Owners can always operate the profile; a configured delegate may too.
+    pub fn can_operate(&self, key: &Pubkey) -> bool {
+        self.is_owner(key) || (self.delegate != Pubkey::default() && self.delegate == *key)
+    }
+
     pub fn compute_reputation(&self, config: &super::ReputationConfig) -> u64 {
+        self.compute_reputation_at(config, Clock::get().unwrap().unix_timestamp)
+    }
+
+    pub fn compute_reputation_at(&self, config: &super::ReputationConfig, now: i64) -> u64 {
         let total_stake_at_risk = self
             .total_staked_for
+            .saturating_add(self.claimed_foreign_staked_for)
             .saturating_add(self.author_bond_lamports);
         let stake_component = total_stake_at_risk.saturating_mul(config.stake_weight as u64);
-        let vouch_component = (self.total_vouches_received as u64).saturating_mul(config.vouch_weight as u64);
+        let vouch_component = self
+            .effective_vouch_weight
+            .saturating_add(self.claimed_foreign_vouch_weight)
+            .saturating_mul(config.vouch_weight as u64)
+            / BPS_DENOMINATOR;
         
         // Calculate days since registration
-        let now = Clock::get().unwrap().unix_timestamp;
         let age_seconds = now.saturating_sub(self.registered_at);
         let age_days = age_seconds / 86400;
         let longevity_component = (age_days as u64).saturating_mul(config.longevity_bonus as u64);
+
+        // Damped credit for the reputation vouchers carried when they vouched
+        let transitive_component = ((self
+            .vouched_reputation_total
+            .saturating_add(self.claimed_foreign_reputation_total) as u128)
+            .saturating_mul(config.transitive_trust_bps as u128)
+            / BPS_DENOMINATOR as u128) as u64;
         
         stake_component
             .saturating_add(vouch_component)
             .saturating_add(longevity_component)
+            .saturating_add(transitive_component)
+    }
+
+    /// Returns the basis-point weight a new vouch from `voucher` earns toward this profile.
+    ///
+    /// Vouchers younger than `min_voucher_age` or below `min_voucher_reputation` earn
+    /// nothing. Vouchers below `full_weight_voucher_reputation` earn diminishing weight:
+    /// the Nth live low-reputation vouch is worth 1/N of a full vouch.
+    pub fn compute_vouch_weight(
+        &self,
+        voucher: &AgentProfile,
+        config: &super::ReputationConfig,
+    ) -> (u32, bool) {
+        vouch_weight_for(voucher, self.low_reputation_vouches_received, config)
+    }
+
+    /// `compute_vouch_weight` evaluated at `now` instead of the clock sysvar.
+    pub fn compute_vouch_weight_at(
+        &self,
+        voucher: &AgentProfile,
+        config: &super::ReputationConfig,
+        now: i64,
+    ) -> (u32, bool) {
+        vouch_weight_at(voucher, self.low_reputation_vouches_received, config, now)
+    }
+
+    pub fn add_vouch_weight(&mut self, weight_bps: u32, low_reputation_voucher: bool) {
+        self.effective_vouch_weight = self.effective_vouch_weight.saturating_add(weight_bps as u64);
+        if low_reputation_voucher {
+            self.low_reputation_vouches_received =
+                self.low_reputation_vouches_received.saturating_add(1);
+        }
+    }
+
+    pub fn remove_vouch_weight(&mut self, weight_bps: u32, low_reputation_voucher: bool) {
+        self.effective_vouch_weight = self.effective_vouch_weight.saturating_sub(weight_bps as u64);
+        if low_reputation_voucher {
+            self.low_reputation_vouches_received =
+                self.low_reputation_vouches_received.saturating_sub(1);
+        }
+    }
+
+    /// Takes over a claimed foreign record's backing so it counts toward this profile's
+    /// score. Tiers and revenue still read only the profile's own, slashable vouches.
+    pub fn absorb_claimed_foreign_record(&mut self, record: &super::ForeignAgentRecord) {
+        self.claimed_foreign_staked_for = self
+            .claimed_foreign_staked_for
+            .saturating_add(record.total_staked_for);
+        self.claimed_foreign_vouch_weight = self
+            .claimed_foreign_vouch_weight
+            .saturating_add(record.effective_vouch_weight);
+        self.claimed_foreign_reputation_total = self
+            .claimed_foreign_reputation_total
+            .saturating_add(record.vouched_reputation_total);
+    }
+
+    /// Drops a vouch that leaves a foreign record this profile has claimed.
+    pub fn release_claimed_foreign_vouch(&mut self, vouch: &super::Vouch) {
+        self.claimed_foreign_staked_for = self
+            .claimed_foreign_staked_for
+            .saturating_sub(vouch.stake_amount);
+        self.claimed_foreign_vouch_weight = self
+            .claimed_foreign_vouch_weight
+            .saturating_sub(vouch.effective_weight_bps as u64);
+        self.claimed_foreign_reputation_total = self
+            .claimed_foreign_reputation_total
+            .saturating_sub(vouch.voucher_reputation_snapshot);
+    }
+
+    /// Whether vouches on a claimed foreign record still count toward this profile.
+    /// Their revocation updates the claimant, so the profile must outlive them.
+    pub fn holds_claimed_foreign_backing(&self) -> bool {
+        self.claimed_foreign_staked_for != 0
+            || self.claimed_foreign_vouch_weight != 0
+            || self.claimed_foreign_reputation_total != 0
+    }
+
+    pub fn compute_trust_tier(&self, config: &super::ReputationConfig) -> TrustTier {
+        self.compute_trust_tier_at(config, Clock::get().unwrap().unix_timestamp)
+    }
+
+    pub fn compute_trust_tier_at(&self, config: &super::ReputationConfig, now: i64) -> TrustTier {
+        let has_recent_upheld_dispute = self.last_upheld_dispute_at != 0
+            && now.saturating_sub(self.last_upheld_dispute_at) < config.verified_dispute_lookback;
+        if has_recent_upheld_dispute {
+            return TrustTier::Unverified;
+        }
+
+        let account_age = now.saturating_sub(self.registered_at);
+        // Vouchers are counted by weight, so zero-weight sybil vouches add nothing and a
+        // run of low-reputation vouchers counts for less than its size.
+        let weighted_vouchers = self.effective_vouch_weight / BPS_DENOMINATOR;
+        let meets_verified_thresholds = weighted_vouchers >= config.verified_min_vouchers as u64
+            && self.total_staked_for >= config.verified_min_total_stake
+            && self.author_bond_lamports >= config.verified_min_author_bond
+            && account_age >= config.verified_min_account_age;
+
+        if meets_verified_thresholds {
+            TrustTier::Verified
+        } else if self.total_vouches_received > 0 || self.author_bond_lamports > 0 {
+            TrustTier::Backed
+        } else {
+            TrustTier::Unverified
+        }
+    }
+}
+
+/// Sybil-adjusted vouch weight shared by profiles and foreign agent records, where
+/// `low_reputation_vouches_received` is the vouchee's current low-reputation count.
+pub fn vouch_weight_for(
+    voucher: &AgentProfile,
+    low_reputation_vouches_received: u32,
+    config: &super::ReputationConfig,
+) -> (u32, bool) {
+    let now = Clock::get().unwrap().unix_timestamp;
+    vouch_weight_at(voucher, low_reputation_vouches_received, config, now)
+}
+
+pub fn vouch_weight_at(
+    voucher: &AgentProfile,
+    low_reputation_vouches_received: u32,
+    config: &super::ReputationConfig,
+    now: i64,
+) -> (u32, bool) {
+    let voucher_age = now.saturating_sub(voucher.registered_at);
+    if voucher_age < config.min_voucher_age
+        || voucher.reputation_score < config.min_voucher_reputation
+    {
+        return (0, false);
+    }
+
+    if voucher.reputation_score >= config.full_weight_voucher_reputation {
+        return (BPS_DENOMINATOR as u32, false);
+    }
+
+    let low_reputation_rank = (low_reputation_vouches_received as u64).saturating_add(1);
+    ((BPS_DENOMINATOR / low_reputation_rank) as u32, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::{config, profile, NOW};
+    use crate::state::{ForeignAgentRecord, ReputationConfig, Vouch, VouchStatus};
+
+    fn seasoned_voucher(reputation_score: u64) -> AgentProfile {
+        profile(reputation_score, NOW - ReputationConfig::DEFAULT_MIN_VOUCHER_AGE)
+    }
+
+    #[test]
+    fn full_weight_voucher_earns_one_vouch_regardless_of_low_reputation_count() {
+        let config = config();
+        let mut vouchee = profile(0, NOW);
+        vouchee.low_reputation_vouches_received = 4;
+        let voucher = seasoned_voucher(config.full_weight_voucher_reputation);
+
+        let (weight, low) = vouchee.compute_vouch_weight_at(&voucher, &config, NOW);
+        assert_eq!((weight, low), (BPS_DENOMINATOR as u32, false));
+
+        vouchee.add_vouch_weight(weight, low);
+        assert_eq!(vouchee.effective_vouch_weight, BPS_DENOMINATOR);
+        assert_eq!(vouchee.low_reputation_vouches_received, 4);
+    }
+
+    #[test]
+    fn ineligible_vouchers_earn_nothing() {
+        let config = config();
+        let vouchee = profile(0, NOW);
+
+        let too_young = profile(config.full_weight_voucher_reputation, NOW - 1);
+        assert_eq!(vouchee.compute_vouch_weight_at(&too_young, &config, NOW), (0, false));
+
+        let too_weak = seasoned_voucher(config.min_voucher_reputation - 1);
+        assert_eq!(vouchee.compute_vouch_weight_at(&too_weak, &config, NOW), (0, false));
+    }
+
+    #[test]
+    fn low_reputation_vouches_diminish_as_one_over_n() {
+        let config = config();
+        let mut vouchee = profile(0, NOW);
+        let voucher = seasoned_voucher(config.min_voucher_reputation);
+
+        let mut weights = Vec::new();
+        for _ in 0..4 {
+            let (weight, low) = vouchee.compute_vouch_weight_at(&voucher, &config, NOW);
+            assert!(low);
+            vouchee.add_vouch_weight(weight, low);
+            weights.push(weight);
+        }
+
+        assert_eq!(weights, vec![10_000, 5_000, 3_333, 2_500]);
+        assert_eq!(vouchee.effective_vouch_weight, 20_833);
+        assert_eq!(vouchee.low_reputation_vouches_received, 4);
+    }
+
+    #[test]
+    fn revoking_a_low_reputation_vouch_restores_the_previous_rank() {
+        let config = config();
+        let mut vouchee = profile(0, NOW);
+        let voucher = seasoned_voucher(config.min_voucher_reputation);
+
+        let (first, _) = vouchee.compute_vouch_weight_at(&voucher, &config, NOW);
+        vouchee.add_vouch_weight(first, true);
+        let before_second = (vouchee.effective_vouch_weight, vouchee.low_reputation_vouches_received);
+        let (second, low) = vouchee.compute_vouch_weight_at(&voucher, &config, NOW);
+        vouchee.add_vouch_weight(second, low);
+
+        vouchee.remove_vouch_weight(second, low);
+        assert_eq!(
+            (vouchee.effective_vouch_weight, vouchee.low_reputation_vouches_received),
+            before_second
+        );
+
+        // Re-adding lands on the same rank and weight as before the revoke
+        assert_eq!(vouchee.compute_vouch_weight_at(&voucher, &config, NOW), (second, true));
+        vouchee.add_vouch_weight(second, true);
+        assert_eq!(vouchee.effective_vouch_weight, 15_000);
+        assert_eq!(vouchee.low_reputation_vouches_received, 2);
+    }
+
+    #[test]
+    fn claimed_foreign_backing_counts_toward_the_claimant_score() {
+        let config = config();
+        let stake = 50_000_000;
+        let vouch = Vouch {
+            voucher: Pubkey::new_unique(),
+            vouchee: Pubkey::new_unique(),
+            stake_amount: stake,
+            created_at: NOW,
+            status: VouchStatus::Active,
+            cumulative_revenue: 0,
+            last_payout_at: 0,
+            bump: 0,
+            voucher_reputation_snapshot: 400,
+            effective_weight_bps: BPS_DENOMINATOR as u32,
+            low_reputation_voucher: false,
+            pending_dispute_links: 0,
+            version: Vouch::CURRENT_VERSION,
+            last_stake_increase_at: NOW,
+        };
+        let record = ForeignAgentRecord {
+            canonical_agent_id: String::new(),
+            chain_context: String::new(),
+            created_by: Pubkey::new_unique(),
+            created_at: NOW,
+            reputation_score: 0,
+            total_vouches_received: 1,
+            total_staked_for: vouch.stake_amount,
+            vouched_reputation_total: vouch.voucher_reputation_snapshot,
+            effective_vouch_weight: vouch.effective_weight_bps as u64,
+            low_reputation_vouches_received: 0,
+            claimed_by: Pubkey::default(),
+            claimed_at: 0,
+            bump: 0,
+        };
+        let mut claimant = profile(0, NOW);
+        let unclaimed_score = claimant.compute_reputation_at(&config, NOW);
+
+        claimant.absorb_claimed_foreign_record(&record);
+        assert!(claimant.holds_claimed_foreign_backing());
+        assert_eq!(
+            claimant.compute_reputation_at(&config, NOW),
+            unclaimed_score + record.compute_reputation(&config)
+        );
+        // Score only: tiers and revenue shares still read the profile's own vouches.
+        assert_eq!(claimant.total_vouches_received, 0);
+        assert_eq!(claimant.total_staked_for, 0);
+        assert!(claimant.compute_trust_tier_at(&config, NOW) == TrustTier::Unverified);
+
+        claimant.release_claimed_foreign_vouch(&vouch);
+        assert_eq!(claimant.compute_reputation_at(&config, NOW), unclaimed_score);
+        assert!(!claimant.holds_claimed_foreign_backing());
+    }
+
+    #[test]
+    fn any_residual_claimed_backing_keeps_the_claimant_open() {
+        let mut claimant = profile(0, NOW);
+        assert!(!claimant.holds_claimed_foreign_backing());
+
+        // A zero-weight vouch still leaves stake behind, and a zero-stake snapshot
+        // still leaves transitive credit; either one must block the close.
+        claimant.claimed_foreign_staked_for = 1;
+        assert!(claimant.holds_claimed_foreign_backing());
+        claimant.claimed_foreign_staked_for = 0;
+        claimant.claimed_foreign_reputation_total = 1;
+        assert!(claimant.holds_claimed_foreign_backing());
+        claimant.claimed_foreign_reputation_total = 0;
+        claimant.claimed_foreign_vouch_weight = 1;
+        assert!(claimant.holds_claimed_foreign_backing());
+    }
+
+    #[test]
+    fn verified_tier_counts_vouchers_by_weight() {
+        let mut config = config();
+        config.verified_min_vouchers = 2;
+        config.verified_min_total_stake = 0;
+        config.verified_min_author_bond = 0;
+        config.verified_min_account_age = 0;
+        let mut agent = profile(0, NOW);
+        agent.total_vouches_received = 5;
+        agent.effective_vouch_weight = BPS_DENOMINATOR;
+
+        // Five vouches, only one of them weighted: backed, not verified.
+        assert!(agent.compute_trust_tier_at(&config, NOW) == TrustTier::Backed);
+
+        agent.effective_vouch_weight = 2 * BPS_DENOMINATOR;
+        assert!(agent.compute_trust_tier_at(&config, NOW) == TrustTier::Verified);
     }
 }
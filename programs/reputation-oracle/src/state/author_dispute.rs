@@ -45,10 +45,13 @@ pub struct AuthorDispute {
     pub created_at: i64,
     pub resolved_at: Option<i64>,
     pub bump: u8,
+    pub version: u8,
 }
 
 impl AuthorDispute {
     pub const MAX_EVIDENCE_URI_LENGTH: usize = 200;
+    /// Bumped with each layout change; `account_migration` upgrades older versions.
+    pub const CURRENT_VERSION: u8 = 1;
 
     pub const LEN: usize = 8 + // discriminator
         8 + // dispute_id
@@ -67,5 +70,6 @@ impl AuthorDispute {
         8 + // bond_amount
         8 + // created_at
         (1 + 8) + // resolved_at
-        1; // bump
+        1 + // bump
+        1; // version
 }
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+use crate::identity::MAX_CAIP2_CHAIN_ID_LEN;
 
 #[account]
 pub struct ReputationConfig {
@@ -13,11 +16,50 @@ pub struct ReputationConfig {
     pub stake_weight: u32,            // Weight per lamport staked (default: 1)
     pub vouch_weight: u32,            // Points per vouch (default: 100)
     pub longevity_bonus: u32,         // Points per day registered (default: 10)
+
+    // Transitive trust
+    pub transitive_trust_bps: u16,    // Share of voucher reputation credited per vouch (default: 1000 = 10%)
+    pub max_trust_depth: u8,          // Upstream hops walked when refreshing trust depth (default: 3)
+
+    // Verified trust tier thresholds
+    pub verified_min_vouchers: u32,   // Unique live vouchers required (default: 3)
+    pub verified_min_total_stake: u64, // Voucher stake required (lamports, default: 1 SOL)
+    pub verified_min_author_bond: u64, // Author bond required (lamports, default: 0)
+    pub verified_dispute_lookback: i64, // Seconds an upheld dispute blocks verification (default: 90 days)
+    pub verified_min_account_age: i64, // Seconds since registration required (default: 30 days)
+
+    // Sybil resistance for vouch weight
+    pub min_voucher_age: i64,         // Seconds a voucher must be registered before its vouches count (default: 1 day)
+    pub min_voucher_reputation: u64,  // Voucher reputation required before its vouches count (default: 0)
+    pub full_weight_voucher_reputation: u64, // Voucher reputation that earns a full vouch weight (default: 100_000_000)
+
+    pub chain_context: String,        // CAIP-2 chain ID of this deployment, set at initialization
     
     pub bump: u8,
+    pub version: u8, // Layout version; accounts written before versioning read as 0
+
+    // Solana Agent Registry that `solana:` identity bindings must come from
+    pub agent_registry_program: Pubkey, // Registry program named in canonical IDs (default = none)
+    pub agent_registry_collection: Pubkey, // Core collection registry assets belong to (default = none)
+
+    pub chain_context_id: u32,        // Compact ID of `chain_context` stamped on every event
 }
 
 impl ReputationConfig {
+    pub const DEFAULT_TRANSITIVE_TRUST_BPS: u16 = 1_000;
+    pub const DEFAULT_MAX_TRUST_DEPTH: u8 = 3;
+    pub const MAX_TRUST_DEPTH_LIMIT: u8 = 8;
+    pub const DEFAULT_VERIFIED_MIN_VOUCHERS: u32 = 3;
+    pub const DEFAULT_VERIFIED_MIN_TOTAL_STAKE: u64 = 1_000_000_000;
+    pub const DEFAULT_VERIFIED_MIN_AUTHOR_BOND: u64 = 0;
+    pub const DEFAULT_VERIFIED_DISPUTE_LOOKBACK: i64 = 90 * 86_400;
+    pub const DEFAULT_VERIFIED_MIN_ACCOUNT_AGE: i64 = 30 * 86_400;
+    pub const DEFAULT_MIN_VOUCHER_AGE: i64 = 86_400;
+    pub const DEFAULT_MIN_VOUCHER_REPUTATION: u64 = 0;
+    pub const DEFAULT_FULL_WEIGHT_VOUCHER_REPUTATION: u64 = 100_000_000;
+    /// Bumped with each layout change; `account_migration` upgrades older versions.
+    pub const CURRENT_VERSION: u8 = 2;
+
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
         8 + // min_stake
@@ -28,5 +70,38 @@ impl ReputationConfig {
         4 + // stake_weight
         4 + // vouch_weight
         4 + // longevity_bonus
-        1; // bump
+        2 + // transitive_trust_bps
+        1 + // max_trust_depth
+        4 + // verified_min_vouchers
+        8 + // verified_min_total_stake
+        8 + // verified_min_author_bond
+        8 + // verified_dispute_lookback
+        8 + // verified_min_account_age
+        8 + // min_voucher_age
+        8 + // min_voucher_reputation
+        8 + // full_weight_voucher_reputation
+        (4 + MAX_CAIP2_CHAIN_ID_LEN) + // chain_context
+        1 + // bump
+        1 + // version
+        32 + // agent_registry_program
+        32 + // agent_registry_collection
+        4; // chain_context_id
+
+    /// First four bytes (little-endian) of the SHA-256 of a CAIP-2 chain ID. Events carry
+    /// this instead of the string; the config events carry both so indexers can map it.
+    pub fn compact_chain_id(chain_context: &str) -> u32 {
+        let digest = hash(chain_context.as_bytes()).to_bytes();
+        u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+    }
+
+    /// Sets the chain context and its compact ID together, returning the previous context.
+    pub fn set_chain_context(&mut self, chain_context: String) -> String {
+        self.chain_context_id = Self::compact_chain_id(&chain_context);
+        std::mem::replace(&mut self.chain_context, chain_context)
+    }
+
+    pub fn has_agent_registry(&self) -> bool {
+        self.agent_registry_program != Pubkey::default()
+            && self.agent_registry_collection != Pubkey::default()
+    }
 }
@@ -0,0 +1,71 @@
+//! Account fixtures shared by the program's unit tests.
+
+use anchor_lang::prelude::*;
+
+use super::{AgentProfile, ReputationConfig, TrustTier};
+
+pub(crate) const NOW: i64 = 1_700_000_000;
+pub(crate) const DEVNET: &str = "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1";
+
+pub(crate) fn config() -> ReputationConfig {
+    ReputationConfig {
+        authority: Pubkey::new_unique(),
+        min_stake: 0,
+        dispute_bond: 0,
+        min_author_bond_for_free_listing: 0,
+        slash_percentage: 50,
+        cooldown_period: 0,
+        stake_weight: 1,
+        vouch_weight: 100,
+        longevity_bonus: 10,
+        transitive_trust_bps: ReputationConfig::DEFAULT_TRANSITIVE_TRUST_BPS,
+        max_trust_depth: ReputationConfig::DEFAULT_MAX_TRUST_DEPTH,
+        verified_min_vouchers: ReputationConfig::DEFAULT_VERIFIED_MIN_VOUCHERS,
+        verified_min_total_stake: ReputationConfig::DEFAULT_VERIFIED_MIN_TOTAL_STAKE,
+        verified_min_author_bond: ReputationConfig::DEFAULT_VERIFIED_MIN_AUTHOR_BOND,
+        verified_dispute_lookback: ReputationConfig::DEFAULT_VERIFIED_DISPUTE_LOOKBACK,
+        verified_min_account_age: ReputationConfig::DEFAULT_VERIFIED_MIN_ACCOUNT_AGE,
+        min_voucher_age: ReputationConfig::DEFAULT_MIN_VOUCHER_AGE,
+        min_voucher_reputation: 10,
+        full_weight_voucher_reputation: 1_000,
+        chain_context: DEVNET.to_string(),
+        bump: 0,
+        version: ReputationConfig::CURRENT_VERSION,
+        agent_registry_program: Pubkey::default(),
+        agent_registry_collection: Pubkey::default(),
+        chain_context_id: ReputationConfig::compact_chain_id(DEVNET),
+    }
+}
+
+pub(crate) fn profile(reputation_score: u64, registered_at: i64) -> AgentProfile {
+    AgentProfile {
+        authority: Pubkey::new_unique(),
+        metadata_uri: String::new(),
+        reputation_score,
+        total_vouches_received: 0,
+        total_vouches_given: 0,
+        total_staked_for: 0,
+        author_bond_lamports: 0,
+        active_free_skill_listings: 0,
+        open_author_disputes: 0,
+        registered_at,
+        vouched_reputation_total: 0,
+        trust_depth: 0,
+        trust_depth_updated_at: 0,
+        trust_tier: TrustTier::Unverified,
+        last_upheld_dispute_at: 0,
+        effective_vouch_weight: 0,
+        low_reputation_vouches_received: 0,
+        active_skill_listings: 0,
+        agent_id: Pubkey::new_unique(),
+        delegate: Pubkey::default(),
+        bump: 0,
+        version: AgentProfile::CURRENT_VERSION,
+        counters_verified: true,
+        claimed_foreign_staked_for: 0,
+        claimed_foreign_vouch_weight: 0,
+        claimed_foreign_reputation_total: 0,
+        vouch_accounts_received: 0,
+        vouch_accounts_given: 0,
+    }
+}
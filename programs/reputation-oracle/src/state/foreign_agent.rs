@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+use super::agent::BPS_DENOMINATOR;
+use crate::identity::{MAX_CAIP2_CHAIN_ID_LEN, MAX_CANONICAL_AGENT_ID_LEN};
+
+/// Placeholder vouchee for an agent that lives on another chain. Vouches target the
+/// record's PDA exactly like a profile until the agent claims it from a Solana profile.
+#[account]
+pub struct ForeignAgentRecord {
+    pub canonical_agent_id: String,   // `<caip2>:<registryOrProgram>#<recordId>`
+    pub chain_context: String,        // CAIP-2 prefix of the canonical ID
+    pub created_by: Pubkey,           // Wallet that paid to open the record
+    pub created_at: i64,              // Timestamp
+    pub reputation_score: u64,        // Computed score from Solana-side backing only
+    pub total_vouches_received: u32,  // Live vouches targeting this record
+    pub total_staked_for: u64,        // Total SOL staked by vouchers for this record
+    pub vouched_reputation_total: u64, // Sum of voucher reputation snapshots across live vouches
+    pub effective_vouch_weight: u64,  // Sybil-adjusted vouch weight in basis points
+    pub low_reputation_vouches_received: u32, // Live counted vouches from low-reputation vouchers
+    pub claimed_by: Pubkey,           // AgentProfile that proved control (default = unclaimed)
+    pub claimed_at: i64,              // Claim timestamp (0 if unclaimed)
+    pub bump: u8,                     // PDA bump
+}
+
+impl ForeignAgentRecord {
+    pub const LEN: usize = 8 + // discriminator
+        (4 + MAX_CANONICAL_AGENT_ID_LEN) + // canonical_agent_id
+        (4 + MAX_CAIP2_CHAIN_ID_LEN) + // chain_context
+        32 + // created_by
+        8 + // created_at
+        8 + // reputation_score
+        4 + // total_vouches_received
+        8 + // total_staked_for
+        8 + // vouched_reputation_total
+        8 + // effective_vouch_weight
+        4 + // low_reputation_vouches_received
+        32 + // claimed_by
+        8 + // claimed_at
+        1; // bump
+
+    /// Same keying as `AgentIdentityBinding` so a record and its binding share a hash.
+    pub fn seed_hash(canonical_agent_id: &str) -> [u8; 32] {
+        hash(canonical_agent_id.as_bytes()).to_bytes()
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.claimed_by != Pubkey::default()
+    }
+
+    /// Profile scoring without longevity or author bond: neither exists on Solana
+    /// for a foreign agent, so only stake, vouch weight, and transitive trust count.
+    pub fn compute_reputation(&self, config: &super::ReputationConfig) -> u64 {
+        let stake_component = self
+            .total_staked_for
+            .saturating_mul(config.stake_weight as u64);
+        let vouch_component = self
+            .effective_vouch_weight
+            .saturating_mul(config.vouch_weight as u64)
+            / BPS_DENOMINATOR;
+        let transitive_component = ((self.vouched_reputation_total as u128)
+            .saturating_mul(config.transitive_trust_bps as u128)
+            / BPS_DENOMINATOR as u128) as u64;
+
+        stake_component
+            .saturating_add(vouch_component)
+            .saturating_add(transitive_component)
+    }
+
+    pub fn add_vouch_weight(&mut self, weight_bps: u32, low_reputation_voucher: bool) {
+        self.effective_vouch_weight = self.effective_vouch_weight.saturating_add(weight_bps as u64);
+        if low_reputation_voucher {
+            self.low_reputation_vouches_received =
+                self.low_reputation_vouches_received.saturating_add(1);
+        }
+    }
+
+    pub fn remove_vouch_weight(&mut self, weight_bps: u32, low_reputation_voucher: bool) {
+        self.effective_vouch_weight = self.effective_vouch_weight.saturating_sub(weight_bps as u64);
+        if low_reputation_voucher {
+            self.low_reputation_vouches_received =
+                self.low_reputation_vouches_received.saturating_sub(1);
+        }
+    }
+}
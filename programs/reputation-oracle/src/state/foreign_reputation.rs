@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+use crate::identity::{MAX_CAIP2_CHAIN_ID_LEN, MAX_CANONICAL_AGENT_ID_LEN};
+
+/// Foreign emitter whose VAAs are trusted to carry reputation summaries.
+#[account]
+pub struct WormholeEmitter {
+    pub chain: u16,            // Wormhole chain ID of the emitting chain
+    pub address: [u8; 32],     // Emitter contract address, left-padded to 32 bytes
+    pub registered_at: i64,    // Last registration timestamp
+    pub bump: u8,              // PDA bump
+    pub chain_context: String, // CAIP-2 chain ID whose agents this emitter may attest
+}
+
+impl WormholeEmitter {
+    pub const LEN: usize = 8 + // discriminator
+        2 + // chain
+        32 + // address
+        8 + // registered_at
+        1 + // bump
+        (4 + MAX_CAIP2_CHAIN_ID_LEN); // chain_context
+}
+
+/// Latest guardian-attested reputation summary for an agent on another chain.
+#[account]
+pub struct ForeignReputationAttestation {
+    pub canonical_agent_id: String,   // `<caip2>:<registryOrProgram>#<recordId>`
+    pub chain_context: String,        // CAIP-2 prefix of the canonical ID
+    pub emitter_chain: u16,           // Wormhole chain ID of the VAA emitter
+    pub emitter_address: [u8; 32],    // Registered emitter that signed the summary
+    pub sequence: u64,                // VAA sequence; updates must strictly increase it
+    pub posted_vaa: Pubkey,           // Core-bridge account the summary was read from
+    pub reputation_score: u64,        // Source-chain reputation score
+    pub total_vouches_received: u32,  // Source-chain vouch/endorsement count
+    pub feedback_count: u32,          // ERC-8004 feedback entries (0 for AgentVouch sources)
+    pub observed_at: i64,             // Source-chain timestamp of the summary
+    pub updated_at: i64,              // Solana timestamp of the last write
+    pub bump: u8,                     // PDA bump
+}
+
+impl ForeignReputationAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        (4 + MAX_CANONICAL_AGENT_ID_LEN) + // canonical_agent_id
+        (4 + MAX_CAIP2_CHAIN_ID_LEN) + // chain_context
+        2 + // emitter_chain
+        32 + // emitter_address
+        8 + // sequence
+        32 + // posted_vaa
+        8 + // reputation_score
+        4 + // total_vouches_received
+        4 + // feedback_count
+        8 + // observed_at
+        8 + // updated_at
+        1; // bump
+
+    pub fn seed_hash(canonical_agent_id: &str) -> [u8; 32] {
+        hash(canonical_agent_id.as_bytes()).to_bytes()
+    }
+
+    pub fn is_uninitialized(&self) -> bool {
+        self.canonical_agent_id.is_empty()
+    }
+
+    /// Sequences only order one emitter's messages. Once the chain's emitter has been
+    /// replaced, the summary's own timestamp keeps older data from overwriting newer.
+    pub fn is_superseded_by(&self, emitter_address: &[u8; 32], sequence: u64, observed_at: i64) -> bool {
+        if *emitter_address == self.emitter_address {
+            sequence > self.sequence
+        } else {
+            observed_at > self.observed_at
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation() -> ForeignReputationAttestation {
+        ForeignReputationAttestation {
+            canonical_agent_id: "eip155:8453:0x8004A169FB4a3325136EB29fA0ceB6D2e539a432#42".to_string(),
+            chain_context: "eip155:8453".to_string(),
+            emitter_chain: 30,
+            emitter_address: [0xab; 32],
+            sequence: 10,
+            posted_vaa: Pubkey::new_unique(),
+            reputation_score: 0,
+            total_vouches_received: 0,
+            feedback_count: 0,
+            observed_at: 1_760_000_000,
+            updated_at: 1_760_000_000,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn same_emitter_updates_follow_the_sequence() {
+        let stored = attestation();
+        assert!(stored.is_superseded_by(&[0xab; 32], 11, 0));
+        assert!(!stored.is_superseded_by(&[0xab; 32], 10, i64::MAX));
+    }
+
+    #[test]
+    fn replacement_emitter_updates_follow_the_observation_time() {
+        let stored = attestation();
+        assert!(stored.is_superseded_by(&[0xcd; 32], 0, 1_760_000_001));
+        assert!(!stored.is_superseded_by(&[0xcd; 32], u64::MAX, 1_760_000_000));
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+use crate::identity::{MAX_CAIP2_CHAIN_ID_LEN, MAX_CANONICAL_AGENT_ID_LEN};
+
+#[account]
+pub struct AgentIdentityBinding {
+    pub agent: Pubkey,                // AgentProfile that owns this registry identity
+    pub canonical_agent_id: String,   // `<caip2>:<registryOrProgram>#<recordId>`
+    pub chain_context: String,        // CAIP-2 prefix of the canonical ID
+    pub raw_upstream_label: String,   // Registry or SDK label kept verbatim for display
+    pub registry_asset: Pubkey,       // Verified core asset for `solana:` bindings, default otherwise
+    pub bound_at: i64,                // First bind timestamp
+    pub updated_at: i64,              // Last re-bind timestamp
+    pub attested_by: Pubkey,          // Config authority that attested a foreign binding (default = none)
+    pub attested_at: i64,             // Attestation timestamp (0 if never attested)
+    pub bump: u8,                     // PDA bump seed
+}
+
+impl AgentIdentityBinding {
+    pub const MAX_RAW_UPSTREAM_LABEL_LEN: usize = 64;
+
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        (4 + MAX_CANONICAL_AGENT_ID_LEN) + // canonical_agent_id
+        (4 + MAX_CAIP2_CHAIN_ID_LEN) + // chain_context
+        (4 + Self::MAX_RAW_UPSTREAM_LABEL_LEN) + // raw_upstream_label
+        32 + // registry_asset
+        8 + // bound_at
+        8 + // updated_at
+        32 + // attested_by
+        8 + // attested_at
+        1; // bump
+
+    pub fn is_registry_verified(&self) -> bool {
+        self.registry_asset != Pubkey::default()
+    }
+
+    /// Solana registry bindings prove control on-chain; other chains need an attestation
+    /// before the binding can be used to claim anything.
+    pub fn is_attested(&self) -> bool {
+        self.is_registry_verified() || self.attested_by != Pubkey::default()
+    }
+
+    /// Canonical IDs exceed the 32-byte seed limit, so bindings are keyed by their hash.
+    /// Keying by the ID alone also means one registry identity can bind to one agent.
+    pub fn seed_hash(canonical_agent_id: &str) -> [u8; 32] {
+        hash(canonical_agent_id.as_bytes()).to_bytes()
+    }
+}
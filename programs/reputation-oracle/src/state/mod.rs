@@ -3,15 +3,26 @@ pub mod author_bond;
 pub mod author_dispute;
 pub mod author_dispute_vouch_link;
 pub mod config;
+pub mod foreign_agent;
+pub mod foreign_reputation;
+pub mod identity_binding;
 pub mod purchase;
+pub mod session_key;
 pub mod skill_listing;
 pub mod vouch;
 
+#[cfg(test)]
+pub(crate) mod fixtures;
+
 pub use agent::*;
 pub use author_bond::*;
 pub use author_dispute::*;
 pub use author_dispute_vouch_link::*;
 pub use config::*;
+pub use foreign_agent::*;
+pub use foreign_reputation::*;
+pub use identity_binding::*;
 pub use purchase::*;
+pub use session_key::*;
 pub use skill_listing::*;
 pub use vouch::*;
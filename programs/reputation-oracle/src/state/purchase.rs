@@ -10,6 +10,9 @@ pub struct Purchase {
 }
 
 impl Purchase {
+    /// Receipts stay open this long so they remain available as dispute evidence.
+    pub const DISPUTE_WINDOW_SECONDS: i64 = 30 * 24 * 60 * 60;
+
     pub const SPACE: usize = 8 + // discriminator
         32 + // buyer
         32 + // skill_listing
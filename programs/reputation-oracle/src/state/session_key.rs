@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+pub const SESSION_SCOPE_PURCHASE: u8 = 1 << 0;
+pub const SESSION_SCOPE_CLAIM: u8 = 1 << 1;
+pub const SESSION_SCOPE_LIST: u8 = 1 << 2;
+pub const SESSION_SCOPE_UPDATE: u8 = 1 << 3;
+pub const SESSION_SCOPE_ALL: u8 =
+    SESSION_SCOPE_PURCHASE | SESSION_SCOPE_CLAIM | SESSION_SCOPE_LIST | SESSION_SCOPE_UPDATE;
+
+#[account]
+pub struct AgentSessionKey {
+    pub agent: Pubkey,            // AgentProfile this key acts for
+    pub session_key: Pubkey,      // Hot key allowed to sign scoped operations
+    pub scopes: u8,               // SESSION_SCOPE_* bitmask
+    pub spend_cap_lamports: u64,  // Total lamports the key may spend over its lifetime
+    pub spent_lamports: u64,      // Lamports spent so far against the cap
+    pub expires_at: i64,          // Unix timestamp after which the key is rejected
+    pub created_at: i64,          // Unix timestamp
+    pub bump: u8,                 // PDA bump seed
+    pub authority: Pubkey,        // Agent authority that issued the key; a rotation retires it
+}
+
+impl AgentSessionKey {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // session_key
+        1 + // scopes
+        8 + // spend_cap_lamports
+        8 + // spent_lamports
+        8 + // expires_at
+        8 + // created_at
+        1 + // bump
+        32; // authority
+
+    pub fn is_valid_scope_mask(scopes: u8) -> bool {
+        scopes != 0 && scopes & !SESSION_SCOPE_ALL == 0
+    }
+
+    /// Keys stay bound to the authority that issued them, so rotating the agent's
+    /// authority away from a compromised wallet also retires its session keys.
+    pub fn is_issued_by_current_authority(&self, agent_profile: &super::AgentProfile) -> bool {
+        self.authority == agent_profile.authority
+    }
+
+    pub fn allows(&self, scope: u8, now: i64) -> bool {
+        self.scopes & scope == scope && now < self.expires_at
+    }
+
+    /// Returns the new spent total, or `None` when the spend would exceed the cap.
+    pub fn checked_spend(&self, amount: u64) -> Option<u64> {
+        self.spent_lamports
+            .checked_add(amount)
+            .filter(|spent| *spent <= self.spend_cap_lamports)
+    }
+}
@@ -23,12 +23,15 @@ pub struct SkillListing {
     pub updated_at: i64,          // Last update timestamp
     pub status: SkillStatus,      // Active, Suspended, or Removed
     pub bump: u8,                 // PDA bump seed
+    pub version: u8,              // Layout version; accounts written before versioning read as 0
 }
 
 impl SkillListing {
     pub const MAX_NAME_LEN: usize = 64;
     pub const MAX_DESCRIPTION_LEN: usize = 256;
     pub const MAX_URI_LEN: usize = 256;
+    /// Bumped with each layout change; `account_migration` upgrades older versions.
+    pub const CURRENT_VERSION: u8 = 1;
     
     pub const SPACE: usize = 8 + // discriminator
         32 + // author
@@ -42,7 +45,8 @@ impl SkillListing {
         8 + // created_at
         8 + // updated_at
         1 + // status
-        1; // bump
+        1 + // bump
+        1; // version
 
     pub fn is_free_price(price_lamports: u64) -> bool {
         price_lamports == 0
@@ -4,12 +4,18 @@ use anchor_lang::prelude::*;
 pub struct Vouch {
     pub voucher: Pubkey,         // Who is vouching
     pub vouchee: Pubkey,         // Who is being vouched for
-    pub stake_amount: u64,       // SOL staked (lamports)
+    pub stake_amount: u64,       // SOL staked (lamports); residual stake once slashed
     pub created_at: i64,         // Timestamp
     pub status: VouchStatus,     // Active, Revoked, Slashed
     pub cumulative_revenue: u64, // Total revenue earned from marketplace purchases
     pub last_payout_at: i64,     // Last time voucher claimed revenue
     pub bump: u8,                // PDA bump
+    pub voucher_reputation_snapshot: u64, // Voucher's reputation_score when the relationship was opened
+    pub effective_weight_bps: u32, // Sybil-adjusted weight this vouch adds to the vouchee
+    pub low_reputation_voucher: bool, // Whether the weight was counted on the diminishing low-reputation curve
+    pub pending_dispute_links: u32, // Unsettled author-dispute links that lock this vouch's stake
+    pub version: u8,             // Layout version; accounts written before versioning read as 0
+    pub last_stake_increase_at: i64, // When stake was last added; the decrease cooldown runs from here
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +26,9 @@ pub enum VouchStatus {
 }
 
 impl Vouch {
+    /// Bumped with each layout change; `account_migration` upgrades older versions.
+    pub const CURRENT_VERSION: u8 = 1;
+
     pub const LEN: usize = 8 + // discriminator
         32 + // voucher
         32 + // vouchee
@@ -28,11 +37,34 @@ impl Vouch {
         1 + // status (enum)
         8 + // cumulative_revenue
         8 + // last_payout_at
-        1; // bump
+        1 + // bump
+        8 + // voucher_reputation_snapshot
+        4 + // effective_weight_bps
+        1 + // low_reputation_voucher
+        4 + // pending_dispute_links
+        1 + // version
+        8; // last_stake_increase_at
 
     pub fn is_uninitialized(&self) -> bool {
         self.voucher == Pubkey::default() && self.vouchee == Pubkey::default()
     }
+
+    pub fn is_locked_by_disputes(&self) -> bool {
+        self.pending_dispute_links > 0
+    }
+
+    /// Revoked vouches and slashed vouches whose remainder has been withdrawn hold
+    /// nothing but rent once no dispute can still reach them.
+    pub fn is_closable(&self) -> bool {
+        if self.is_locked_by_disputes() {
+            return false;
+        }
+        match self.status {
+            VouchStatus::Revoked => true,
+            VouchStatus::Slashed => self.stake_amount == 0,
+            VouchStatus::Active => false,
+        }
+    }
 }
 
 impl VouchStatus {
@@ -43,4 +75,62 @@ impl VouchStatus {
     pub fn counts_toward_author_wide_backing_snapshot(self) -> bool {
         matches!(self, Self::Active)
     }
+
+    /// Slashed vouches keep their residual stake at risk for every other dispute
+    /// that linked them while they were live, so overlapping disputes settle in turn.
+    pub fn slashable_by_linked_dispute(self) -> bool {
+        matches!(self, Self::Active | Self::Slashed)
+    }
+
+    /// Vouches revoked before revocation was locked behind open disputes left the
+    /// backing set legitimately; settlement skips them instead of failing.
+    pub fn exited_before_settlement(self) -> bool {
+        matches!(self, Self::Revoked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vouch(status: VouchStatus, stake_amount: u64, pending_dispute_links: u32) -> Vouch {
+        Vouch {
+            voucher: Pubkey::new_unique(),
+            vouchee: Pubkey::new_unique(),
+            stake_amount,
+            created_at: 0,
+            status,
+            cumulative_revenue: 0,
+            last_payout_at: 0,
+            bump: 0,
+            voucher_reputation_snapshot: 0,
+            effective_weight_bps: 10_000,
+            low_reputation_voucher: false,
+            pending_dispute_links,
+            version: Vouch::CURRENT_VERSION,
+            last_stake_increase_at: 0,
+        }
+    }
+
+    #[test]
+    fn settled_vouches_without_pending_links_are_closable() {
+        assert!(vouch(VouchStatus::Revoked, 0, 0).is_closable());
+        assert!(vouch(VouchStatus::Slashed, 0, 0).is_closable());
+    }
+
+    #[test]
+    fn live_vouches_are_never_closable() {
+        assert!(!vouch(VouchStatus::Active, 1_000, 0).is_closable());
+    }
+
+    #[test]
+    fn slashed_vouches_with_residual_stake_are_not_closable() {
+        assert!(!vouch(VouchStatus::Slashed, 1_000, 0).is_closable());
+    }
+
+    #[test]
+    fn revoked_vouches_with_pending_dispute_links_are_not_closable() {
+        assert!(!vouch(VouchStatus::Revoked, 0, 1).is_closable());
+        assert!(!vouch(VouchStatus::Slashed, 0, 2).is_closable());
+    }
 }
@@ -0,0 +1,437 @@
+//! Wormhole core-bridge account decoding and the AgentVouch reputation payload.
+//!
+//! The core bridge only writes a `PostedVAA` account after `verify_signatures` has
+//! filled a `SignatureSet` for the same body hash, so the program re-checks the
+//! bridge-owned accounts it is handed instead of re-verifying secp256k1 signatures:
+//! the posted VAA must point at the signature set, and the set must carry a guardian
+//! quorum for a guardian set that has not expired.
+//!
+//! Outbound summaries go through the bridge's `post_message`, signed by this program's
+//! emitter PDA. Foreign chains register that PDA as the Solana counterpart of their
+//! own `WormholeEmitter` entry.
+
+use anchor_lang::prelude::*;
+
+use crate::bytes::Reader;
+use crate::identity::{parse_canonical_agent_id, MAX_CANONICAL_AGENT_ID_LEN};
+
+pub const CORE_BRIDGE_PROGRAM_ID_MAINNET: Pubkey =
+    pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+pub const CORE_BRIDGE_PROGRAM_ID_DEVNET: Pubkey =
+    pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+/// Wormhole chain ID for Solana, used to tag outbound messages.
+pub const WORMHOLE_CHAIN_ID_SOLANA: u16 = 1;
+
+const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+
+/// Seed of this program's emitter PDA, which signs every outbound message.
+pub const OUTBOUND_EMITTER_SEED: &[u8] = b"emitter";
+/// Seed of the core-bridge message accounts this program posts, followed by the
+/// little-endian emitter sequence the message takes.
+pub const OUTBOUND_MESSAGE_SEED: &[u8] = b"reputation_message";
+
+const POST_MESSAGE_INSTRUCTION: u8 = 1;
+/// `ConsistencyLevel::Finalized` in the core bridge's borsh encoding.
+const CONSISTENCY_LEVEL_FINALIZED: u8 = 1;
+
+/// First byte of every AgentVouch payload, followed by the payload version.
+pub const REPUTATION_SUMMARY_PAYLOAD_ID: u8 = 1;
+pub const REPUTATION_SUMMARY_PAYLOAD_VERSION: u8 = 1;
+
+pub fn is_core_bridge_program(program_id: &Pubkey) -> bool {
+    *program_id == CORE_BRIDGE_PROGRAM_ID_MAINNET || *program_id == CORE_BRIDGE_PROGRAM_ID_DEVNET
+}
+
+/// Message fee from the core bridge's `BridgeData` (guardian set index, last lamports,
+/// guardian set expiration time, then the fee).
+pub fn parse_bridge_fee(data: &[u8]) -> Option<u64> {
+    Reader::at(data, 16).u64_le()
+}
+
+/// Next sequence of an emitter's `SequenceTracker`. The bridge creates the tracker
+/// with the emitter's first message, so a missing account means sequence zero.
+pub fn parse_emitter_sequence(data: &[u8]) -> Option<u64> {
+    if data.is_empty() {
+        return Some(0);
+    }
+    Reader::at(data, 0).u64_le()
+}
+
+/// Instruction data for the core bridge's `post_message` at finalized consistency.
+pub fn post_message_data(nonce: u32, payload: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + 4 + 4 + payload.len() + 1);
+    data.push(POST_MESSAGE_INSTRUCTION);
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(payload);
+    data.push(CONSISTENCY_LEVEL_FINALIZED);
+    data
+}
+
+/// `PostedVAAV1` as written by the core bridge (the `vaa` magic, then borsh fields).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostedVaa {
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub signature_set: Pubkey,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+pub fn parse_posted_vaa(data: &[u8]) -> Option<PostedVaa> {
+    let mut reader = Reader::at(data, 0);
+    if reader.array::<3>()? != *POSTED_VAA_MAGIC {
+        return None;
+    }
+    reader.u8()?; // vaa_version
+    let consistency_level = reader.u8()?;
+    let vaa_time = reader.u32_le()?;
+    let signature_set = reader.pubkey()?;
+    reader.u32_le()?; // submission_time
+    let nonce = reader.u32_le()?;
+    let sequence = reader.u64_le()?;
+    let emitter_chain = reader.u16_le()?;
+    let emitter_address = reader.array()?;
+    let payload = reader.borsh_bytes()?.to_vec();
+
+    Some(PostedVaa {
+        consistency_level,
+        vaa_time,
+        signature_set,
+        nonce,
+        sequence,
+        emitter_chain,
+        emitter_address,
+        payload,
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureSet {
+    pub signatures: Vec<bool>,
+    pub hash: [u8; 32],
+    pub guardian_set_index: u32,
+}
+
+impl SignatureSet {
+    pub fn signed_count(&self) -> usize {
+        self.signatures.iter().filter(|signed| **signed).count()
+    }
+}
+
+pub fn parse_signature_set(data: &[u8]) -> Option<SignatureSet> {
+    let mut reader = Reader::at(data, 0);
+    let len = reader.u32_le()? as usize;
+    let signatures = reader
+        .take(len)?
+        .iter()
+        .map(|flag| match flag {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let hash = reader.array()?;
+    let guardian_set_index = reader.u32_le()?;
+
+    Some(SignatureSet {
+        signatures,
+        hash,
+        guardian_set_index,
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GuardianSet {
+    pub index: u32,
+    pub guardian_count: usize,
+    pub creation_time: u32,
+    pub expiration_time: u32,
+}
+
+impl GuardianSet {
+    /// Same threshold the core bridge enforces: more than two thirds of the guardians.
+    pub fn quorum(&self) -> usize {
+        self.guardian_count * 2 / 3 + 1
+    }
+
+    /// Zero means the set is current; replaced sets stay valid until they expire.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expiration_time == 0 || i64::from(self.expiration_time) > now
+    }
+}
+
+pub fn parse_guardian_set(data: &[u8]) -> Option<GuardianSet> {
+    let mut reader = Reader::at(data, 0);
+    let index = reader.u32_le()?;
+    let guardian_count = reader.u32_le()? as usize;
+    reader.take(guardian_count.checked_mul(20)?)?;
+    let creation_time = reader.u32_le()?;
+    let expiration_time = reader.u32_le()?;
+
+    Some(GuardianSet {
+        index,
+        guardian_count,
+        creation_time,
+        expiration_time,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VaaVerificationError {
+    SignatureSetMismatch,
+    GuardianSetMismatch,
+    GuardianSetExpired,
+    InsufficientSignatures,
+}
+
+/// Ties a posted VAA to its signature set and checks guardian quorum. `signature_set_key`
+/// is the address of the account that `signature_set` was decoded from.
+pub fn verify_posted_vaa(
+    vaa: &PostedVaa,
+    signature_set_key: &Pubkey,
+    signature_set: &SignatureSet,
+    guardian_set: &GuardianSet,
+    now: i64,
+) -> std::result::Result<(), VaaVerificationError> {
+    if vaa.signature_set != *signature_set_key {
+        return Err(VaaVerificationError::SignatureSetMismatch);
+    }
+    if signature_set.guardian_set_index != guardian_set.index
+        || signature_set.signatures.len() != guardian_set.guardian_count
+    {
+        return Err(VaaVerificationError::GuardianSetMismatch);
+    }
+    if !guardian_set.is_active(now) {
+        return Err(VaaVerificationError::GuardianSetExpired);
+    }
+    if signature_set.signed_count() < guardian_set.quorum() {
+        return Err(VaaVerificationError::InsufficientSignatures);
+    }
+    Ok(())
+}
+
+/// Chain-tagged reputation summary carried in VAA payloads in both directions.
+/// Integers are big-endian, matching Wormhole payload conventions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReputationSummaryPayload {
+    pub canonical_agent_id: String,
+    pub reputation_score: u64,
+    pub total_vouches_received: u32,
+    pub feedback_count: u32,
+    pub observed_at: i64,
+}
+
+impl ReputationSummaryPayload {
+    pub fn encode(&self) -> Vec<u8> {
+        let id = self.canonical_agent_id.as_bytes();
+        let mut data = Vec::with_capacity(2 + 2 + id.len() + 8 + 4 + 4 + 8);
+        data.push(REPUTATION_SUMMARY_PAYLOAD_ID);
+        data.push(REPUTATION_SUMMARY_PAYLOAD_VERSION);
+        data.extend_from_slice(&(id.len() as u16).to_be_bytes());
+        data.extend_from_slice(id);
+        data.extend_from_slice(&self.reputation_score.to_be_bytes());
+        data.extend_from_slice(&self.total_vouches_received.to_be_bytes());
+        data.extend_from_slice(&self.feedback_count.to_be_bytes());
+        data.extend_from_slice(&self.observed_at.to_be_bytes());
+        data
+    }
+
+    /// Rejects unknown payload IDs or versions, non-canonical agent IDs, and trailing bytes.
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        let mut reader = Reader::at(payload, 0);
+        if reader.u8()? != REPUTATION_SUMMARY_PAYLOAD_ID
+            || reader.u8()? != REPUTATION_SUMMARY_PAYLOAD_VERSION
+        {
+            return None;
+        }
+        let id_len = reader.u16_be()? as usize;
+        if id_len > MAX_CANONICAL_AGENT_ID_LEN {
+            return None;
+        }
+        let canonical_agent_id = std::str::from_utf8(reader.take(id_len)?).ok()?;
+        parse_canonical_agent_id(canonical_agent_id)?;
+        let reputation_score = reader.u64_be()?;
+        let total_vouches_received = reader.u32_be()?;
+        let feedback_count = reader.u32_be()?;
+        let observed_at = reader.u64_be()? as i64;
+        if !reader.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            canonical_agent_id: canonical_agent_id.to_string(),
+            reputation_score,
+            total_vouches_received,
+            feedback_count,
+            observed_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_AGENT: &str = "eip155:8453:0x8004A169FB4a3325136EB29fA0ceB6D2e539a432#42";
+
+    fn summary() -> ReputationSummaryPayload {
+        ReputationSummaryPayload {
+            canonical_agent_id: BASE_AGENT.to_string(),
+            reputation_score: 9_001,
+            total_vouches_received: 12,
+            feedback_count: 340,
+            observed_at: 1_760_000_000,
+        }
+    }
+
+    fn posted_vaa_fixture(signature_set: Pubkey, payload: &[u8]) -> Vec<u8> {
+        let mut data = POSTED_VAA_MAGIC.to_vec();
+        data.push(1); // vaa_version
+        data.push(15); // consistency_level
+        data.extend_from_slice(&1_760_000_100u32.to_le_bytes());
+        data.extend_from_slice(signature_set.as_ref());
+        data.extend_from_slice(&1_760_000_200u32.to_le_bytes());
+        data.extend_from_slice(&7u32.to_le_bytes()); // nonce
+        data.extend_from_slice(&99u64.to_le_bytes()); // sequence
+        data.extend_from_slice(&30u16.to_le_bytes()); // Base
+        data.extend_from_slice(&[0xab; 32]);
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    fn signature_set_fixture(signed: usize, total: usize, index: u32) -> Vec<u8> {
+        let mut data = (total as u32).to_le_bytes().to_vec();
+        data.extend((0..total).map(|i| u8::from(i < signed)));
+        data.extend_from_slice(&[0x11; 32]);
+        data.extend_from_slice(&index.to_le_bytes());
+        data
+    }
+
+    fn guardian_set_fixture(index: u32, total: usize, expiration_time: u32) -> Vec<u8> {
+        let mut data = index.to_le_bytes().to_vec();
+        data.extend_from_slice(&(total as u32).to_le_bytes());
+        data.extend(std::iter::repeat_n(0x22, total * 20));
+        data.extend_from_slice(&1_700_000_000u32.to_le_bytes());
+        data.extend_from_slice(&expiration_time.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn payload_round_trips() {
+        let encoded = summary().encode();
+        assert_eq!(encoded[0], REPUTATION_SUMMARY_PAYLOAD_ID);
+        assert_eq!(ReputationSummaryPayload::decode(&encoded), Some(summary()));
+    }
+
+    #[test]
+    fn payload_rejects_bad_ids_versions_and_trailing_bytes() {
+        let mut bad_version = summary().encode();
+        bad_version[1] = 2;
+        assert!(ReputationSummaryPayload::decode(&bad_version).is_none());
+
+        let mut alias = summary();
+        alias.canonical_agent_id = "base#42".to_string();
+        assert!(ReputationSummaryPayload::decode(&alias.encode()).is_none());
+
+        let mut trailing = summary().encode();
+        trailing.push(0);
+        assert!(ReputationSummaryPayload::decode(&trailing).is_none());
+    }
+
+    #[test]
+    fn parses_posted_vaa_fixture() {
+        let signature_set = Pubkey::new_unique();
+        let payload = summary().encode();
+        let vaa = parse_posted_vaa(&posted_vaa_fixture(signature_set, &payload)).unwrap();
+        assert_eq!(vaa.signature_set, signature_set);
+        assert_eq!(vaa.sequence, 99);
+        assert_eq!(vaa.emitter_chain, 30);
+        assert_eq!(vaa.emitter_address, [0xab; 32]);
+        assert_eq!(vaa.payload, payload);
+
+        let mut wrong_magic = posted_vaa_fixture(signature_set, &payload);
+        wrong_magic[0] = b'm';
+        assert!(parse_posted_vaa(&wrong_magic).is_none());
+        let truncated = posted_vaa_fixture(signature_set, &payload);
+        assert!(parse_posted_vaa(&truncated[..truncated.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn verifies_guardian_quorum() {
+        let signature_set_key = Pubkey::new_unique();
+        let vaa = parse_posted_vaa(&posted_vaa_fixture(signature_set_key, &[])).unwrap();
+        let guardians = parse_guardian_set(&guardian_set_fixture(4, 19, 0)).unwrap();
+        assert_eq!(guardians.quorum(), 13);
+
+        let quorum = parse_signature_set(&signature_set_fixture(13, 19, 4)).unwrap();
+        assert_eq!(
+            verify_posted_vaa(&vaa, &signature_set_key, &quorum, &guardians, 0),
+            Ok(())
+        );
+
+        let short = parse_signature_set(&signature_set_fixture(12, 19, 4)).unwrap();
+        assert_eq!(
+            verify_posted_vaa(&vaa, &signature_set_key, &short, &guardians, 0),
+            Err(VaaVerificationError::InsufficientSignatures)
+        );
+
+        assert_eq!(
+            verify_posted_vaa(&vaa, &Pubkey::new_unique(), &quorum, &guardians, 0),
+            Err(VaaVerificationError::SignatureSetMismatch)
+        );
+
+        let stale_index = parse_signature_set(&signature_set_fixture(19, 19, 3)).unwrap();
+        assert_eq!(
+            verify_posted_vaa(&vaa, &signature_set_key, &stale_index, &guardians, 0),
+            Err(VaaVerificationError::GuardianSetMismatch)
+        );
+    }
+
+    #[test]
+    fn rejects_expired_guardian_sets() {
+        let signature_set_key = Pubkey::new_unique();
+        let vaa = parse_posted_vaa(&posted_vaa_fixture(signature_set_key, &[])).unwrap();
+        let expired = parse_guardian_set(&guardian_set_fixture(4, 19, 1_750_000_000)).unwrap();
+        let signatures = parse_signature_set(&signature_set_fixture(19, 19, 4)).unwrap();
+        assert!(expired.is_active(1_749_999_999));
+        assert_eq!(
+            verify_posted_vaa(&vaa, &signature_set_key, &signatures, &expired, 1_750_000_000),
+            Err(VaaVerificationError::GuardianSetExpired)
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_signature_flags() {
+        let mut data = signature_set_fixture(1, 3, 0);
+        data[4] = 2;
+        assert!(parse_signature_set(&data).is_none());
+    }
+
+    #[test]
+    fn encodes_post_message_and_reads_bridge_state() {
+        let payload = summary().encode();
+        let data = post_message_data(7, &payload);
+        assert_eq!(data[0], POST_MESSAGE_INSTRUCTION);
+        assert_eq!(data[1..5], 7u32.to_le_bytes());
+        assert_eq!(data[5..9], (payload.len() as u32).to_le_bytes());
+        assert_eq!(data[9..9 + payload.len()], payload[..]);
+        assert_eq!(data[data.len() - 1], CONSISTENCY_LEVEL_FINALIZED);
+
+        let mut bridge = 3u32.to_le_bytes().to_vec(); // guardian_set_index
+        bridge.extend_from_slice(&0u64.to_le_bytes()); // last_lamports
+        bridge.extend_from_slice(&86_400u32.to_le_bytes()); // guardian_set_expiration_time
+        bridge.extend_from_slice(&100u64.to_le_bytes()); // fee
+        assert_eq!(parse_bridge_fee(&bridge), Some(100));
+        assert_eq!(parse_bridge_fee(&bridge[..20]), None);
+
+        assert_eq!(parse_emitter_sequence(&[]), Some(0));
+        assert_eq!(parse_emitter_sequence(&42u64.to_le_bytes()), Some(42));
+        assert_eq!(parse_emitter_sequence(&[1, 2]), None);
+    }
+}
@@ -36,13 +36,20 @@ async function main() {
   const slashPercentage = 50; // 50% slashing
   const cooldownPeriod = new anchor.BN(60 * 60 * 24); // 1 day cooldown
 
+  // CAIP-2 chain ID of this deployment, e.g. solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1 for devnet.
+  const chainContext = process.env.CHAIN_CONTEXT;
+  if (!chainContext) {
+    throw new Error("Set CHAIN_CONTEXT to the deployment's solana: CAIP-2 chain ID");
+  }
+
   const tx = await program.methods
     .initializeConfig(
       minStake,
       disputeBond,
       minAuthorBondForFreeListing,
       slashPercentage,
-      cooldownPeriod
+      cooldownPeriod,
+      chainContext
     )
     .rpc();
 
@@ -8,6 +8,7 @@ const CURRENT_REPUTATION_CONFIG_LEN = 86;
 
 type Options = {
   apply: boolean;
+  chainContext?: string;
 };
 
 type ParsedConfig = {
@@ -26,10 +27,12 @@ type ParsedConfig = {
 
 function printUsage(): never {
   console.error(`Usage:
-  ts-node scripts/migrate-config.ts [--apply]
+  ts-node scripts/migrate-config.ts [--apply] --chain-context <caip2-chain-id>
 
 Notes:
   - Dry run is the default. Pass --apply to submit the migration transaction.
+  - These layouts predate the chain context, so --apply requires --chain-context
+    (e.g. solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1 for devnet).
   - Requires ANCHOR_PROVIDER_URL and ANCHOR_WALLET to point at the config authority.
 `);
   process.exit(1);
@@ -37,16 +40,26 @@ Notes:
 
 function parseArgs(argv: string[]): Options {
   let apply = false;
+  let chainContext: string | undefined;
 
-  for (const arg of argv) {
+  for (let i = 0; i < argv.length; i++) {
+    const arg = argv[i];
     if (arg === "--apply") {
       apply = true;
       continue;
     }
+    if (arg === "--chain-context" && argv[i + 1]) {
+      chainContext = argv[++i];
+      continue;
+    }
+    printUsage();
+  }
+
+  if (apply && !chainContext) {
     printUsage();
   }
 
-  return { apply };
+  return { apply, chainContext };
 }
 
 function readPubkey(data: Buffer, start: number) {
@@ -181,7 +194,7 @@ async function main() {
   }
 
   const tx = await program.methods
-    .migrateConfig()
+    .migrateConfig(options.chainContext ?? null)
     .accountsPartial({
       authority: provider.wallet.publicKey,
     })
@@ -98,7 +98,7 @@ describe("author-disputes", () => {
       },
       {
         pubkey: vouch,
-        isWritable: false,
+        isWritable: true,
         isSigner: false,
       },
     ]);
@@ -111,7 +111,7 @@ describe("author-disputes", () => {
     return entries.flatMap(({ vouch, voucherProfile }) => [
       {
         pubkey: getAuthorDisputeLinkPda(authorDispute, vouch),
-        isWritable: false,
+        isWritable: true,
         isSigner: false,
       },
       {
@@ -188,7 +188,8 @@ describe("author-disputes", () => {
           new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
           new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
           50,
-          new anchor.BN(86400)
+          new anchor.BN(86400),
+          "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1"
         )
         .accountsPartial({
           config: configPda,
@@ -692,6 +693,27 @@ describe("author-disputes", () => {
     );
     assert.equal(freeVouchOneLamportsBefore, freeVouchOneLamportsAfter);
     assert.equal(freeVouchTwoLamportsBefore, freeVouchTwoLamportsAfter);
+    assert.equal(freeVouchOneAccount.pendingDisputeLinks, 1);
+    assert.equal(freeVouchTwoAccount.pendingDisputeLinks, 1);
+
+    for (const vouch of [freeVouchOne, freeVouchTwo]) {
+      await program.methods
+        .releaseAuthorDisputeVouchLink()
+        .accountsPartial({
+          authorDispute,
+          link: getAuthorDisputeLinkPda(authorDispute, vouch),
+          vouch,
+          challenger: challenger.publicKey,
+        })
+        .rpc();
+    }
+
+    const releasedOne = await program.account.vouch.fetch(freeVouchOne);
+    const releasedTwo = await program.account.vouch.fetch(freeVouchTwo);
+    assert.equal(releasedOne.pendingDisputeLinks, 0);
+    assert.equal(releasedTwo.pendingDisputeLinks, 0);
+    assert.isNull(await provider.connection.getAccountInfo(linkOne));
+    assert.isNull(await provider.connection.getAccountInfo(linkTwo));
   });
 
   it("keeps free-skill disputes bond-only even if the listing price changes later", async () => {
@@ -853,6 +875,22 @@ describe("author-disputes", () => {
       "cannot be withdrawn while author disputes are open"
     );
 
+    await expectFailure(
+      program.methods
+        .revokeVouch()
+        .accountsPartial({
+          vouch: paidVouchOne,
+          voucherProfile: voucherOneProfile,
+          voucheeProfile: authorProfile,
+          config: configPda,
+          voucher: voucherOne.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voucherOne])
+        .rpc(),
+      "cannot be revoked while it is linked to unsettled author disputes"
+    );
+
     await program.methods
       .resolveAuthorDispute(disputeId, { upheld: {} })
       .accountsPartial({
@@ -884,6 +922,13 @@ describe("author-disputes", () => {
     assert.equal(authorBondAccount.amount.toNumber(), 0);
     assert.equal(vouchOneAccount.status.slashed !== undefined, true);
     assert.equal(vouchTwoAccount.status.slashed !== undefined, true);
+    assert.equal(vouchOneAccount.pendingDisputeLinks, 0);
+    assert.equal(vouchTwoAccount.pendingDisputeLinks, 0);
+    assert.isNull(
+      await provider.connection.getAccountInfo(
+        getAuthorDisputeLinkPda(authorDispute, paidVouchOne)
+      )
+    );
 
     const voucherOneProfileAccount = await program.account.agentProfile.fetch(
       voucherOneProfile
@@ -945,4 +990,287 @@ describe("author-disputes", () => {
       "cannot accept new stake in its current state"
     );
   });
+
+  async function runOverlappingPaidDisputes(
+    disputeCount: number,
+    label: string
+  ) {
+    const overlapAuthor = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        overlapAuthor.publicKey,
+        10 * anchor.web3.LAMPORTS_PER_SOL
+      )
+    );
+
+    const [overlapProfile] = PublicKey.findProgramAddressSync(
+      [Buffer.from("agent"), overlapAuthor.publicKey.toBuffer()],
+      program.programId
+    );
+    const overlapBond = getAuthorBondPda(overlapAuthor.publicKey);
+    const backers = [
+      { voucher: voucherOne, voucherProfile: voucherOneProfile },
+      { voucher: voucherTwo, voucherProfile: voucherTwoProfile },
+    ].map((backer) => ({
+      ...backer,
+      vouch: PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("vouch"),
+          backer.voucherProfile.toBuffer(),
+          overlapProfile.toBuffer(),
+        ],
+        program.programId
+      )[0],
+    }));
+
+    await program.methods
+      .registerAgent(`https://author-dispute.overlap-${label}`)
+      .accountsPartial({
+        agentProfile: overlapProfile,
+        authority: overlapAuthor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([overlapAuthor])
+      .rpc();
+
+    await program.methods
+      .depositAuthorBond(authorBondAmount)
+      .accountsPartial({
+        authorBond: overlapBond,
+        authorProfile: overlapProfile,
+        config: configPda,
+        author: overlapAuthor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([overlapAuthor])
+      .rpc();
+
+    const vouchesGivenBefore = await Promise.all(
+      backers.map(async ({ voucherProfile }) => {
+        const profile = await program.account.agentProfile.fetch(
+          voucherProfile
+        );
+        return profile.totalVouchesGiven;
+      })
+    );
+
+    for (const { voucher, voucherProfile, vouch } of backers) {
+      await program.methods
+        .vouch(stakeAmount)
+        .accountsPartial({
+          vouch,
+          voucherProfile,
+          voucheeProfile: overlapProfile,
+          config: configPda,
+          voucher: voucher.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([voucher])
+        .rpc();
+    }
+
+    const overlapSkillId = `overlap-${label}-${Date.now()}`;
+    const [overlapListing] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("skill"),
+        overlapAuthor.publicKey.toBuffer(),
+        Buffer.from(overlapSkillId),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createSkillListing(
+        overlapSkillId,
+        "ipfs://author-dispute-overlap-skill",
+        "Overlapping Dispute Skill",
+        "Paid skill used to test overlapping author disputes",
+        new anchor.BN(0.25 * anchor.web3.LAMPORTS_PER_SOL)
+      )
+      .accountsPartial({
+        skillListing: overlapListing,
+        authorProfile: overlapProfile,
+        config: configPda,
+        authorBond: null,
+        author: overlapAuthor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([overlapAuthor])
+      .rpc();
+
+    const disputes = Array.from({ length: disputeCount }, (_, index) => {
+      const disputeId = new anchor.BN(100 + index);
+      return {
+        disputeId,
+        authorDispute: getAuthorDisputePda(overlapAuthor.publicKey, disputeId),
+      };
+    });
+
+    // Every dispute snapshots the same live backing set before any resolves.
+    for (const { disputeId, authorDispute } of disputes) {
+      await program.methods
+        .openAuthorDispute(
+          disputeId,
+          { maliciousSkill: {} },
+          `https://example.com/evidence/overlap-${label}.json`
+        )
+        .accountsPartial({
+          authorDispute,
+          authorProfile: overlapProfile,
+          config: configPda,
+          skillListing: overlapListing,
+          purchase: null,
+          challenger: challenger.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+          getRemainingAccounts(
+            authorDispute,
+            backers.map(({ vouch }) => vouch)
+          )
+        )
+        .signers([challenger])
+        .rpc();
+    }
+
+    for (const { vouch } of backers) {
+      const vouchAccount = await program.account.vouch.fetch(vouch);
+      assert.equal(vouchAccount.pendingDisputeLinks, disputeCount);
+    }
+
+    for (const [index, { disputeId, authorDispute }] of disputes.entries()) {
+      const bondBefore = (
+        await program.account.authorBond.fetch(overlapBond)
+      ).amount.toNumber();
+      const stakesBefore = await Promise.all(
+        backers.map(async ({ vouch }) =>
+          (await program.account.vouch.fetch(vouch)).stakeAmount.toNumber()
+        )
+      );
+      const lamportsBefore = await Promise.all(
+        backers.map(({ vouch }) => provider.connection.getBalance(vouch))
+      );
+      const expectedTotalSlash = computeSlashAmount(
+        bondBefore + stakesBefore.reduce((sum, stake) => sum + stake, 0)
+      );
+
+      await program.methods
+        .resolveAuthorDispute(disputeId, { upheld: {} })
+        .accountsPartial({
+          authorDispute,
+          authorProfile: overlapProfile,
+          authorBond: overlapBond,
+          config: configPda,
+          authority: provider.wallet.publicKey,
+          challenger: challenger.publicKey,
+        })
+        .remainingAccounts(
+          getResolveRemainingAccounts(authorDispute, backers)
+        )
+        .rpc();
+
+      const resolved = await program.account.authorDispute.fetch(
+        authorDispute
+      );
+      assert.equal(resolved.status.resolved !== undefined, true);
+
+      const bondAfter = (
+        await program.account.authorBond.fetch(overlapBond)
+      ).amount.toNumber();
+      let vouchSlashed = 0;
+      for (const [backerIndex, { vouch }] of backers.entries()) {
+        const vouchAccount = await program.account.vouch.fetch(vouch);
+        const slashed =
+          stakesBefore[backerIndex] - vouchAccount.stakeAmount.toNumber();
+        assert.equal(vouchAccount.status.slashed !== undefined, true);
+        assert.equal(
+          vouchAccount.pendingDisputeLinks,
+          disputeCount - index - 1
+        );
+        assert.isAbove(vouchAccount.stakeAmount.toNumber(), 0);
+        assert.equal(
+          lamportsBefore[backerIndex] -
+            (await provider.connection.getBalance(vouch)),
+          slashed
+        );
+        vouchSlashed += slashed;
+      }
+      assert.equal(bondBefore - bondAfter + vouchSlashed, expectedTotalSlash);
+    }
+
+    const overlapProfileAccount = await program.account.agentProfile.fetch(
+      overlapProfile
+    );
+    assert.equal(overlapProfileAccount.totalVouchesReceived, 0);
+    assert.equal(Number(overlapProfileAccount.totalStakedFor), 0);
+    assert.equal(overlapProfileAccount.openAuthorDisputes, 0);
+
+    // Backers leave the voucher counts exactly once, however many disputes settle.
+    for (const [index, { voucherProfile }] of backers.entries()) {
+      const profile = await program.account.agentProfile.fetch(voucherProfile);
+      assert.equal(profile.totalVouchesGiven, vouchesGivenBefore[index]);
+    }
+
+    for (const { voucher, voucherProfile, vouch } of backers) {
+      const residualStake = (
+        await program.account.vouch.fetch(vouch)
+      ).stakeAmount.toNumber();
+      const vouchLamportsBefore = await provider.connection.getBalance(vouch);
+
+      await program.methods
+        .withdrawSlashedVouchRemainder()
+        .accountsPartial({
+          vouch,
+          voucherProfile,
+          voucher: voucher.publicKey,
+        })
+        .signers([voucher])
+        .rpc();
+
+      const withdrawn = await program.account.vouch.fetch(vouch);
+      assert.equal(withdrawn.stakeAmount.toNumber(), 0);
+      assert.equal(withdrawn.status.slashed !== undefined, true);
+      assert.equal(
+        vouchLamportsBefore - (await provider.connection.getBalance(vouch)),
+        residualStake
+      );
+
+      await expectFailure(
+        program.methods
+          .withdrawSlashedVouchRemainder()
+          .accountsPartial({
+            vouch,
+            voucherProfile,
+            voucher: voucher.publicKey,
+          })
+          .signers([voucher])
+          .rpc(),
+        "no remaining stake to withdraw"
+      );
+
+      await program.methods
+        .closeVouch()
+        .accountsPartial({
+          vouch,
+          voucherProfile,
+          vouchee: overlapProfile,
+          voucher: voucher.publicKey,
+        })
+        .signers([voucher])
+        .rpc();
+      assert.isNull(await provider.connection.getAccountInfo(vouch));
+    }
+  }
+
+  it("settles two overlapping paid disputes against residual stake", async () => {
+    await runOverlappingPaidDisputes(2, "two");
+  });
+
+  it("settles three overlapping paid disputes against residual stake", async () => {
+    await runOverlappingPaidDisputes(3, "three");
+  });
+
+  it("settles N overlapping paid disputes against residual stake", async () => {
+    await runOverlappingPaidDisputes(5, "many");
+  });
 });
@@ -78,7 +78,8 @@ describe("marketplace", () => {
           new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
           new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL),
           50,
-          new anchor.BN(86400)
+          new anchor.BN(86400),
+          "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1"
         )
         .accounts({
           config: configPda,
@@ -284,6 +285,74 @@ describe("marketplace", () => {
     );
   });
 
+  it("Rejects purchases when the author is below the requested trust tier", async () => {
+    const skillId = "tier-gate-" + Date.now();
+    const price = new anchor.BN(0.01 * anchor.web3.LAMPORTS_PER_SOL);
+    const authorProfile = getAgentPda(author.publicKey);
+
+    const [skillListing] = PublicKey.findProgramAddressSync(
+      [Buffer.from("skill"), author.publicKey.toBuffer(), Buffer.from(skillId)],
+      program.programId
+    );
+    const [purchase] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("purchase"),
+        buyer.publicKey.toBuffer(),
+        skillListing.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createSkillListing(
+        skillId,
+        "ipfs://QmTierGate",
+        "Tier Gate Skill",
+        "Requires a verified author",
+        price
+      )
+      .accounts({
+        skillListing,
+        authorProfile,
+        config: configPda,
+        authorBond: null,
+        author: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    const profile = await program.account.agentProfile.fetch(authorProfile);
+    assert.isUndefined(profile.trustTier.verified);
+
+    const accounts = {
+      skillListing,
+      purchase,
+      author: author.publicKey,
+      authorProfile,
+      buyer: buyer.publicKey,
+      systemProgram: SystemProgram.programId,
+    };
+
+    await expectFailure(
+      program.methods
+        .purchaseSkillWithMinTier({ verified: {} })
+        .accounts(accounts)
+        .signers([buyer])
+        .rpc(),
+      "AuthorBelowMinimumTrustTier"
+    );
+
+    await program.methods
+      .purchaseSkillWithMinTier({ unverified: {} })
+      .accounts(accounts)
+      .signers([buyer])
+      .rpc();
+
+    const purchaseRecord = await program.account.purchase.fetch(purchase);
+    assert.equal(purchaseRecord.pricePaid.toString(), price.toString());
+  });
+
   it("Voucher claims revenue after purchase (end-to-end)", async () => {
     const skillId = "claim-test-" + Date.now();
     const price = new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL);
@@ -729,4 +798,483 @@ describe("marketplace", () => {
     );
     assert.equal(authorProfileAfterWithdraw.activeFreeSkillListings, 0);
   });
+
+  it("keeps purchase receipts and profiles open until they are safe to close", async () => {
+    const closingAuthor = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        closingAuthor.publicKey,
+        5 * anchor.web3.LAMPORTS_PER_SOL
+      )
+    );
+    const closingProfile = getAgentPda(closingAuthor.publicKey);
+    const skillId = "closing-" + Date.now();
+    const [skillListing] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("skill"),
+        closingAuthor.publicKey.toBuffer(),
+        Buffer.from(skillId),
+      ],
+      program.programId
+    );
+    const [purchase] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("purchase"),
+        buyer.publicKey.toBuffer(),
+        skillListing.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .registerAgent("https://closing-author.example")
+      .accounts({
+        agentProfile: closingProfile,
+        authority: closingAuthor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([closingAuthor])
+      .rpc();
+
+    await program.methods
+      .createSkillListing(
+        skillId,
+        "ipfs://QmClosing",
+        "Closing Skill",
+        "Skill used to test account closing",
+        new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL)
+      )
+      .accounts({
+        skillListing,
+        authorProfile: closingProfile,
+        config: configPda,
+        authorBond: null,
+        author: closingAuthor.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([closingAuthor])
+      .rpc();
+
+    await program.methods
+      .purchaseSkill()
+      .accounts({
+        skillListing,
+        purchase,
+        author: closingAuthor.publicKey,
+        authorProfile: closingProfile,
+        buyer: buyer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    await expectFailure(
+      program.methods
+        .closePurchase()
+        .accountsPartial({
+          purchase,
+          skillListing,
+          authorProfile: closingProfile,
+          buyer: buyer.publicKey,
+        })
+        .signers([buyer])
+        .rpc(),
+      "Purchase is still inside its dispute window"
+    );
+
+    await expectFailure(
+      program.methods
+        .closeAgentProfile()
+        .accountsPartial({
+          agentProfile: closingProfile,
+          authority: closingAuthor.publicKey,
+        })
+        .signers([closingAuthor])
+        .rpc(),
+      "Remove every skill listing before closing the profile"
+    );
+
+    await program.methods
+      .removeSkillListing(skillId)
+      .accounts({
+        skillListing,
+        authorProfile: closingProfile,
+        author: closingAuthor.publicKey,
+      })
+      .signers([closingAuthor])
+      .rpc();
+
+    await program.methods
+      .closeAgentProfile()
+      .accountsPartial({
+        agentProfile: closingProfile,
+        authority: closingAuthor.publicKey,
+      })
+      .signers([closingAuthor])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(closingProfile));
+  });
+
+  it("Lets a scoped session key purchase within its spend cap until revoked", async () => {
+    const buyerProfile = getAgentPda(buyer.publicKey);
+    const authorProfile = getAgentPda(author.publicKey);
+    const sessionSigner = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        sessionSigner.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      )
+    );
+
+    const price = new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL);
+    const listings = [0, 1, 2].map((index) => {
+      const skillId = `session-${index}-${Date.now()}`;
+      const [skillListing] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("skill"),
+          author.publicKey.toBuffer(),
+          Buffer.from(skillId),
+        ],
+        program.programId
+      );
+      const [purchase] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("purchase"),
+          buyer.publicKey.toBuffer(),
+          skillListing.toBuffer(),
+        ],
+        program.programId
+      );
+      return { skillId, skillListing, purchase };
+    });
+
+    for (const { skillId, skillListing } of listings) {
+      await program.methods
+        .createSkillListing(
+          skillId,
+          "ipfs://QmSession",
+          "Session Skill",
+          "Bought with a session key",
+          price
+        )
+        .accounts({
+          skillListing,
+          authorProfile,
+          config: configPda,
+          authorBond: null,
+          author: author.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([author])
+        .rpc();
+    }
+
+    const [agentSessionKey] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("session_key"),
+        buyerProfile.toBuffer(),
+        sessionSigner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const purchaseScope = 1;
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 3600);
+
+    await program.methods
+      .createAgentSessionKey(
+        sessionSigner.publicKey,
+        purchaseScope,
+        price.muln(3).divn(2),
+        expiresAt
+      )
+      .accountsPartial({
+        agentSessionKey,
+        agentProfile: buyerProfile,
+        authority: buyer.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const purchaseWithSession = (index: number) =>
+      program.methods
+        .purchaseSkillWithSession(null)
+        .accountsPartial({
+          skillListing: listings[index].skillListing,
+          purchase: listings[index].purchase,
+          author: author.publicKey,
+          authorProfile,
+          agentProfile: buyerProfile,
+          agentSessionKey,
+          sessionSigner: sessionSigner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sessionSigner])
+        .rpc();
+
+    await purchaseWithSession(0);
+
+    const receipt = await program.account.purchase.fetch(listings[0].purchase);
+    assert.equal(receipt.buyer.toBase58(), buyer.publicKey.toBase58());
+    const session = await program.account.agentSessionKey.fetch(
+      agentSessionKey
+    );
+    assert.equal(session.spentLamports.toString(), price.toString());
+
+    // Agent-keyed receipts close through the profile owner, under the same dispute window.
+    const closeAgentPurchase = (authority: Keypair) =>
+      program.methods
+        .closeAgentPurchase()
+        .accountsPartial({
+          purchase: listings[0].purchase,
+          agentProfile: buyerProfile,
+          skillListing: listings[0].skillListing,
+          authorProfile,
+          config: configPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    await expectFailure(closeAgentPurchase(sessionSigner), "UnauthorizedAgentAuthority");
+    await expectFailure(closeAgentPurchase(buyer), "DisputeWindowOpen");
+
+    await expectFailure(
+      purchaseWithSession(1),
+      "Purchase would exceed the session key spend cap"
+    );
+
+    await program.methods
+      .revokeAgentSessionKey()
+      .accountsPartial({
+        agentSessionKey,
+        agentProfile: buyerProfile,
+        authority: buyer.publicKey,
+      })
+      .signers([buyer])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(agentSessionKey));
+    await expectFailure(purchaseWithSession(2), "AccountNotInitialized");
+  });
+
+  it("Lets list- and update-scoped session keys manage the author's listings", async () => {
+    const authorProfile = getAgentPda(author.publicKey);
+    const sessionSigner = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        sessionSigner.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      )
+    );
+    const [agentSessionKey] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("session_key"),
+        authorProfile.toBuffer(),
+        sessionSigner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    const listScope = 1 << 2;
+    const updateScope = 1 << 3;
+    const createSessionKey = (scopes: number) =>
+      program.methods
+        .createAgentSessionKey(
+          sessionSigner.publicKey,
+          scopes,
+          new anchor.BN(anchor.web3.LAMPORTS_PER_SOL),
+          new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+        )
+        .accountsPartial({
+          agentSessionKey,
+          agentProfile: authorProfile,
+          authority: author.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([author])
+        .rpc();
+
+    const skillId = `session-listing-${Date.now()}`;
+    const [skillListing] = PublicKey.findProgramAddressSync(
+      [Buffer.from("skill"), author.publicKey.toBuffer(), Buffer.from(skillId)],
+      program.programId
+    );
+    const price = new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL);
+    const listingAccounts = {
+      skillListing,
+      authorProfile,
+      agentSessionKey,
+      config: configPda,
+      authorBond: null,
+      sessionSigner: sessionSigner.publicKey,
+    };
+    const createWithSession = () =>
+      program.methods
+        .createSkillListingWithSession(
+          skillId,
+          "ipfs://QmSessionListing",
+          "Session Listing",
+          "Listed with a session key",
+          price
+        )
+        .accountsPartial({
+          ...listingAccounts,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sessionSigner])
+        .rpc();
+
+    await createSessionKey(updateScope);
+    await expectFailure(createWithSession(), "lacks the list scope");
+    await program.methods
+      .revokeAgentSessionKey()
+      .accountsPartial({
+        agentSessionKey,
+        agentProfile: authorProfile,
+        authority: author.publicKey,
+      })
+      .signers([author])
+      .rpc();
+
+    await createSessionKey(listScope | updateScope);
+    const before = await program.account.agentProfile.fetch(authorProfile);
+    await createWithSession();
+
+    let listing = await program.account.skillListing.fetch(skillListing);
+    assert.equal(listing.author.toBase58(), author.publicKey.toBase58());
+    const after = await program.account.agentProfile.fetch(authorProfile);
+    assert.equal(after.activeSkillListings, before.activeSkillListings + 1);
+    const rent = await provider.connection.getBalance(skillListing);
+    const session = await program.account.agentSessionKey.fetch(agentSessionKey);
+    assert.equal(session.spentLamports.toNumber(), rent);
+
+    await program.methods
+      .updateSkillListingWithSession(
+        skillId,
+        "ipfs://QmSessionListingV2",
+        "Session Listing v2",
+        "Updated with a session key",
+        price.muln(2)
+      )
+      .accountsPartial(listingAccounts)
+      .signers([sessionSigner])
+      .rpc();
+    listing = await program.account.skillListing.fetch(skillListing);
+    assert.equal(listing.name, "Session Listing v2");
+    assert.equal(listing.priceLamports.toString(), price.muln(2).toString());
+  });
+
+  it("Retires session keys when the agent authority rotates", async () => {
+    const owner = Keypair.generate();
+    const newOwner = Keypair.generate();
+    const sessionSigner = Keypair.generate();
+    for (const wallet of [owner, sessionSigner]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          wallet.publicKey,
+          2 * anchor.web3.LAMPORTS_PER_SOL
+        )
+      );
+    }
+
+    const agentProfile = getAgentPda(owner.publicKey);
+    const authorProfile = getAgentPda(author.publicKey);
+    await program.methods
+      .registerAgent("https://rotating-buyer.agent")
+      .accounts({
+        agentProfile,
+        authority: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    const skillId = `session-rotation-${Date.now()}`;
+    const [skillListing] = PublicKey.findProgramAddressSync(
+      [Buffer.from("skill"), author.publicKey.toBuffer(), Buffer.from(skillId)],
+      program.programId
+    );
+    const [purchase] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("purchase"),
+        owner.publicKey.toBuffer(),
+        skillListing.toBuffer(),
+      ],
+      program.programId
+    );
+    const price = new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL);
+    await program.methods
+      .createSkillListing(
+        skillId,
+        "ipfs://QmRotation",
+        "Rotation Skill",
+        "Offered after a key rotation",
+        price
+      )
+      .accounts({
+        skillListing,
+        authorProfile,
+        config: configPda,
+        authorBond: null,
+        author: author.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([author])
+      .rpc();
+
+    const [agentSessionKey] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("session_key"),
+        agentProfile.toBuffer(),
+        sessionSigner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    await program.methods
+      .createAgentSessionKey(
+        sessionSigner.publicKey,
+        1,
+        price,
+        new anchor.BN(Math.floor(Date.now() / 1000) + 3600)
+      )
+      .accountsPartial({
+        agentSessionKey,
+        agentProfile,
+        authority: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    const session = await program.account.agentSessionKey.fetch(agentSessionKey);
+    assert.equal(session.authority.toBase58(), owner.publicKey.toBase58());
+
+    await program.methods
+      .rotateAgentAuthority()
+      .accountsPartial({
+        agentProfile,
+        authority: owner.publicKey,
+        newAuthority: newOwner.publicKey,
+      })
+      .signers([owner, newOwner])
+      .rpc();
+
+    await expectFailure(
+      program.methods
+        .purchaseSkillWithSession(null)
+        .accountsPartial({
+          skillListing,
+          purchase,
+          author: author.publicKey,
+          authorProfile,
+          agentProfile,
+          agentSessionKey,
+          sessionSigner: sessionSigner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([sessionSigner])
+        .rpc(),
+      "issued by an authority the agent has since rotated away from"
+    );
+  });
 });
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { ReputationOracle } from "../target/types/reputation_oracle";
 import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
 import { assert } from "chai";
+import { createHash } from "crypto";
 
 describe("reputation-oracle", () => {
   const provider = anchor.AnchorProvider.env();
@@ -75,7 +76,8 @@ describe("reputation-oracle", () => {
           disputeBond,
           minAuthorBondForFreeListing,
           slashPercentage,
-          cooldownPeriod
+          cooldownPeriod,
+          "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1"
         )
         .accounts({
           config: configPda,
@@ -98,6 +100,7 @@ describe("reputation-oracle", () => {
     assert.equal(config.slashPercentage, slashPercentage);
     assert.equal(config.stakeWeight, 1);
     assert.equal(config.vouchWeight, 100);
+    assert.equal(config.chainContext, "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1");
   });
 
   it("Registers agent 1", async () => {
@@ -202,6 +205,11 @@ describe("reputation-oracle", () => {
     assert.equal(agent2Profile.totalVouchesReceived, 1);
     assert.isTrue(agent2Profile.reputationScore.toNumber() > 0);
 
+    // Agent 1 registered moments ago, so the vouch counts raw but carries no
+    // sybil-adjusted weight until the voucher clears the minimum age.
+    assert.equal(vouch.effectiveWeightBps, 0);
+    assert.equal(agent2Profile.effectiveVouchWeight.toNumber(), 0);
+
     console.log(
       "Agent 2 reputation score:",
       agent2Profile.reputationScore.toString()
@@ -468,4 +476,834 @@ describe("reputation-oracle", () => {
     assert.equal(after.activeFreeSkillListings, before.activeFreeSkillListings);
     assert.equal(after.openAuthorDisputes, before.openAuthorDisputes);
   });
+
+  it("Refreshes Agent 2 trust depth from its live vouch graph", async () => {
+    const agent1Pda = getAgentPda(agent1.publicKey);
+    const agent2Pda = getAgentPda(agent2.publicKey);
+    const [vouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), agent1Pda.toBuffer(), agent2Pda.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .refreshTrustDepth()
+        .accounts({ agentProfile: agent2Pda, config: configPda })
+        .rpc();
+      assert.fail("Expected the incomplete trust graph to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "IncompleteTrustGraph");
+    }
+
+    await program.methods
+      .refreshTrustDepth()
+      .accounts({ agentProfile: agent2Pda, config: configPda })
+      .remainingAccounts([
+        { pubkey: vouchPda, isWritable: false, isSigner: false },
+        { pubkey: agent1Pda, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+
+    const agent2Profile = await program.account.agentProfile.fetch(agent2Pda);
+    const vouch = await program.account.vouch.fetch(vouchPda);
+    assert.equal(agent2Profile.trustDepth, 1);
+    assert.isTrue(agent2Profile.trustDepthUpdatedAt.toNumber() > 0);
+    assert.equal(
+      agent2Profile.vouchedReputationTotal.toString(),
+      vouch.voucherReputationSnapshot.toString()
+    );
+  });
+
+  it("Returns a reputation attestation through return data", async () => {
+    const agent2Pda = getAgentPda(agent2.publicKey);
+    const profile = await program.account.agentProfile.fetch(agent2Pda);
+
+    const attestation = await program.methods
+      .getReputation()
+      .accounts({ agentProfile: agent2Pda, config: configPda })
+      .view();
+
+    assert.equal(attestation.version, 1);
+    assert.equal(attestation.agent.toString(), agent2Pda.toString());
+    assert.equal(attestation.authority.toString(), agent2.publicKey.toString());
+    assert.equal(
+      attestation.totalStakedFor.toString(),
+      profile.totalStakedFor.toString()
+    );
+    assert.equal(
+      attestation.totalVouchesReceived,
+      profile.totalVouchesReceived
+    );
+    assert.isTrue(attestation.slot.toNumber() > 0);
+  });
+
+  it("Blocks partial unstaking before the vouch cooldown elapses", async () => {
+    const agent1Pda = getAgentPda(agent1.publicKey);
+    const agent2Pda = getAgentPda(agent2.publicKey);
+    const [vouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), agent1Pda.toBuffer(), agent2Pda.toBuffer()],
+      program.programId
+    );
+    const before = await program.account.vouch.fetch(vouchPda);
+
+    try {
+      await program.methods
+        .decreaseVouchStake(new anchor.BN(0.01 * anchor.web3.LAMPORTS_PER_SOL))
+        .accounts({
+          vouch: vouchPda,
+          voucherProfile: agent1Pda,
+          voucheeProfile: agent2Pda,
+          config: configPda,
+          voucher: agent1.publicKey,
+        })
+        .signers([agent1])
+        .rpc();
+      assert.fail("Expected the cooldown to block the stake decrease");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "CooldownNotElapsed");
+    }
+
+    const after = await program.account.vouch.fetch(vouchPda);
+    assert.equal(after.stakeAmount.toString(), before.stakeAmount.toString());
+  });
+
+  it("Rotates the agent authority while keeping the profile and delegate-managed listings", async () => {
+    const owner = Keypair.generate();
+    const operator = Keypair.generate();
+    const newOwner = Keypair.generate();
+    for (const wallet of [owner, operator, newOwner]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          wallet.publicKey,
+          2 * anchor.web3.LAMPORTS_PER_SOL
+        )
+      );
+    }
+
+    const agentPda = getAgentPda(owner.publicKey);
+    await program.methods
+      .registerAgent("https://rotating-agent.example")
+      .accounts({
+        agentProfile: agentPda,
+        authority: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .setAgentDelegate(operator.publicKey)
+      .accountsPartial({ agentProfile: agentPda, authority: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    const skillId = "delegated-" + Date.now();
+    const [skillListing] = PublicKey.findProgramAddressSync(
+      [Buffer.from("skill"), owner.publicKey.toBuffer(), Buffer.from(skillId)],
+      program.programId
+    );
+    await program.methods
+      .createSkillListing(
+        skillId,
+        "ipfs://QmDelegated",
+        "Delegated Skill",
+        "Listed by the operational key",
+        new anchor.BN(0.01 * anchor.web3.LAMPORTS_PER_SOL)
+      )
+      .accountsPartial({
+        skillListing,
+        authorProfile: agentPda,
+        config: configPda,
+        authorBond: null,
+        author: operator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([operator])
+      .rpc();
+
+    const listing = await program.account.skillListing.fetch(skillListing);
+    assert.equal(listing.author.toBase58(), owner.publicKey.toBase58());
+
+    const before = await program.account.agentProfile.fetch(agentPda);
+    await program.methods
+      .rotateAgentAuthority()
+      .accountsPartial({
+        agentProfile: agentPda,
+        authority: owner.publicKey,
+        newAuthority: newOwner.publicKey,
+      })
+      .signers([owner, newOwner])
+      .rpc();
+
+    const rotated = await program.account.agentProfile.fetch(agentPda);
+    assert.equal(rotated.authority.toBase58(), newOwner.publicKey.toBase58());
+    assert.equal(rotated.agentId.toBase58(), owner.publicKey.toBase58());
+    assert.equal(rotated.delegate.toBase58(), PublicKey.default.toBase58());
+    assert.equal(
+      rotated.registeredAt.toString(),
+      before.registeredAt.toString()
+    );
+    assert.equal(rotated.activeSkillListings, 1);
+
+    try {
+      await program.methods
+        .registerAgent("https://hijack.example")
+        .accounts({
+          agentProfile: agentPda,
+          authority: owner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([owner])
+        .rpc();
+      assert.fail("Expected the rotated-away key to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "AgentAuthorityRotated");
+    }
+
+    await program.methods
+      .removeSkillListing(skillId)
+      .accountsPartial({
+        skillListing,
+        authorProfile: agentPda,
+        author: newOwner.publicKey,
+      })
+      .signers([newOwner])
+      .rpc();
+
+    const removed = await program.account.skillListing.fetch(skillListing);
+    assert.equal(removed.status.removed !== undefined, true);
+  });
+
+  it("Binds a CAIP-2 canonical registry identity to an agent", async () => {
+    const agentPda = getAgentPda(agent1.publicKey);
+    const canonicalAgentId =
+      "eip155:8453:0x8004A169FB4a3325136EB29fA0ceB6D2e539a432#" + Date.now();
+    const [identityBinding] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("identity_binding"),
+        createHash("sha256").update(canonicalAgentId).digest(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .bindAgentIdentity(canonicalAgentId, "base")
+      .accountsPartial({
+        identityBinding,
+        agentProfile: agentPda,
+        registryAsset: null,
+        authority: agent1.publicKey,
+      })
+      .signers([agent1])
+      .rpc();
+
+    const binding = await program.account.agentIdentityBinding.fetch(
+      identityBinding
+    );
+    assert.equal(binding.agent.toBase58(), agentPda.toBase58());
+    assert.equal(binding.canonicalAgentId, canonicalAgentId);
+    assert.equal(binding.chainContext, "eip155:8453");
+    assert.equal(binding.rawUpstreamLabel, "base");
+    assert.equal(binding.registryAsset.toBase58(), PublicKey.default.toBase58());
+
+    const aliasId = "solana:mainnet-beta#1";
+    const [aliasBinding] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("identity_binding"),
+        createHash("sha256").update(aliasId).digest(),
+      ],
+      program.programId
+    );
+    try {
+      await program.methods
+        .bindAgentIdentity(aliasId, "solana:mainnet-beta")
+        .accountsPartial({
+          identityBinding: aliasBinding,
+          agentProfile: agentPda,
+          registryAsset: null,
+          authority: agent1.publicKey,
+        })
+        .signers([agent1])
+        .rpc();
+      assert.fail("Expected a non-CAIP identity to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "InvalidCanonicalAgentId");
+    }
+
+    // Solana registry IDs must name this deployment's chain and configured
+    // registry, and prove ownership of the core asset they name.
+    const coreAsset = Keypair.generate().publicKey;
+    const registryProgram = Keypair.generate().publicKey;
+    const registryCollection = Keypair.generate().publicKey;
+    const bindSolanaId = async (solanaId: string) => {
+      const [solanaBinding] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("identity_binding"),
+          createHash("sha256").update(solanaId).digest(),
+        ],
+        program.programId
+      );
+      await program.methods
+        .bindAgentIdentity(solanaId, "solana:devnet")
+        .accountsPartial({
+          identityBinding: solanaBinding,
+          agentProfile: agentPda,
+          registryAsset: null,
+          authority: agent1.publicKey,
+        })
+        .signers([agent1])
+        .rpc();
+    };
+    const devnetId = (registry: PublicKey) =>
+      "solana:EtWTRABZaYq6iMfeYKouRu166VU2xqa1:" +
+      registry.toBase58() +
+      "#" +
+      coreAsset.toBase58();
+
+    try {
+      await bindSolanaId(devnetId(registryProgram));
+      assert.fail("Expected a Solana binding without a configured registry to fail");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "AgentRegistryNotConfigured");
+    }
+
+    await program.methods
+      .updateAgentRegistry(registryProgram, registryCollection)
+      .accountsPartial({ config: configPda, authority: provider.wallet.publicKey })
+      .rpc();
+
+    try {
+      await bindSolanaId(
+        "solana:5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp:" +
+          registryProgram.toBase58() +
+          "#" +
+          coreAsset.toBase58()
+      );
+      assert.fail("Expected a binding for another Solana cluster to fail");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "RegistryChainMismatch");
+    }
+
+    try {
+      await bindSolanaId(devnetId(program.programId));
+      assert.fail("Expected a binding for an unconfigured registry to fail");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "UnknownAgentRegistry");
+    }
+
+    try {
+      await bindSolanaId(devnetId(registryProgram));
+      assert.fail("Expected a Solana registry binding without its asset to fail");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "MissingRegistryAsset");
+    }
+  });
+
+  it("Lets the config authority reassign a squatted foreign binding before attesting it", async () => {
+    const canonicalAgentId =
+      "eip155:8453:0x8004A169FB4a3325136EB29fA0ceB6D2e539a432#squatted-" +
+      Date.now();
+    const [identityBinding] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("identity_binding"),
+        createHash("sha256").update(canonicalAgentId).digest(),
+      ],
+      program.programId
+    );
+    const squatterProfile = getAgentPda(agent2.publicKey);
+    const ownerProfile = getAgentPda(agent3.publicKey);
+    const bindAs = (agent: Keypair, agentProfile: PublicKey) =>
+      program.methods
+        .bindAgentIdentity(canonicalAgentId, "base")
+        .accountsPartial({
+          identityBinding,
+          agentProfile,
+          registryAsset: null,
+          authority: agent.publicKey,
+        })
+        .signers([agent])
+        .rpc();
+
+    await bindAs(agent2, squatterProfile);
+    try {
+      await bindAs(agent3, ownerProfile);
+      assert.fail("Expected a foreign binding to stay first-come");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "IdentityBoundToAnotherAgent");
+    }
+
+    const reassign = () =>
+      program.methods
+        .reassignAgentIdentity()
+        .accountsPartial({
+          identityBinding,
+          agentProfile: ownerProfile,
+          config: configPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+    await reassign();
+    let binding = await program.account.agentIdentityBinding.fetch(identityBinding);
+    assert.equal(binding.agent.toBase58(), ownerProfile.toBase58());
+    assert.equal(binding.rawUpstreamLabel, "");
+
+    await bindAs(agent3, ownerProfile);
+    binding = await program.account.agentIdentityBinding.fetch(identityBinding);
+    assert.equal(binding.rawUpstreamLabel, "base");
+
+    await program.methods
+      .attestAgentIdentity()
+      .accountsPartial({
+        identityBinding,
+        config: configPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    try {
+      await reassign();
+      assert.fail("Expected an attested binding to be final");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "BindingAlreadyAttested");
+    }
+  });
+
+  it("Lets Solana agents underwrite a foreign agent that later claims its record", async () => {
+    const canonicalAgentId =
+      "eip155:8453:0x8004A169FB4a3325136EB29fA0ceB6D2e539a432#foreign-" +
+      Date.now();
+    const idHash = createHash("sha256").update(canonicalAgentId).digest();
+    const [foreignAgent] = PublicKey.findProgramAddressSync(
+      [Buffer.from("foreign_agent"), idHash],
+      program.programId
+    );
+    const [identityBinding] = PublicKey.findProgramAddressSync(
+      [Buffer.from("identity_binding"), idHash],
+      program.programId
+    );
+    const voucherProfile = getAgentPda(agent1.publicKey);
+    const claimantProfile = getAgentPda(agent3.publicKey);
+    const [vouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), voucherProfile.toBuffer(), foreignAgent.toBuffer()],
+      program.programId
+    );
+    const stake = new anchor.BN(0.05 * anchor.web3.LAMPORTS_PER_SOL);
+
+    await program.methods
+      .createForeignAgentRecord(canonicalAgentId)
+      .accountsPartial({ foreignAgent, payer: provider.wallet.publicKey })
+      .rpc();
+
+    await program.methods
+      .vouchForeignAgent(stake)
+      .accountsPartial({
+        vouch: vouchPda,
+        voucherProfile,
+        foreignAgent,
+        config: configPda,
+        voucher: agent1.publicKey,
+      })
+      .signers([agent1])
+      .rpc();
+
+    let record = await program.account.foreignAgentRecord.fetch(foreignAgent);
+    assert.equal(record.chainContext, "eip155:8453");
+    assert.equal(record.totalVouchesReceived, 1);
+    assert.equal(record.totalStakedFor.toString(), stake.toString());
+    assert.isTrue(record.reputationScore.gtn(0));
+
+    await program.methods
+      .bindAgentIdentity(canonicalAgentId, "base")
+      .accountsPartial({
+        identityBinding,
+        agentProfile: claimantProfile,
+        registryAsset: null,
+        authority: agent3.publicKey,
+      })
+      .signers([agent3])
+      .rpc();
+
+    const claimAccounts = {
+      foreignAgent,
+      identityBinding,
+      agentProfile: claimantProfile,
+      authority: agent3.publicKey,
+    };
+    try {
+      await program.methods
+        .claimForeignAgentRecord()
+        .accountsPartial(claimAccounts)
+        .signers([agent3])
+        .rpc();
+      assert.fail("Expected an unattested binding to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "BindingNotAttested");
+    }
+
+    await program.methods
+      .attestAgentIdentity()
+      .accountsPartial({
+        identityBinding,
+        config: configPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    // A claimant backing the record itself would carry its own stake into its score.
+    const [selfVouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), claimantProfile.toBuffer(), foreignAgent.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .vouchForeignAgent(stake)
+      .accountsPartial({
+        vouch: selfVouchPda,
+        voucherProfile: claimantProfile,
+        foreignAgent,
+        config: configPda,
+        voucher: agent3.publicKey,
+      })
+      .signers([agent3])
+      .rpc();
+    try {
+      await program.methods
+        .claimForeignAgentRecord()
+        .accountsPartial({ ...claimAccounts, claimantVouch: selfVouchPda })
+        .signers([agent3])
+        .rpc();
+      assert.fail("Expected a claimant with a live vouch on the record to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "ClaimantVouchLive");
+    }
+    await program.methods
+      .revokeForeignVouch()
+      .accountsPartial({
+        vouch: selfVouchPda,
+        voucherProfile: claimantProfile,
+        foreignAgent,
+        claimantProfile: null,
+        config: configPda,
+        voucher: agent3.publicKey,
+      })
+      .signers([agent3])
+      .rpc();
+
+    const unclaimed = await program.account.agentProfile.fetch(claimantProfile);
+    await program.methods
+      .claimForeignAgentRecord()
+      .accountsPartial(claimAccounts)
+      .signers([agent3])
+      .rpc();
+
+    record = await program.account.foreignAgentRecord.fetch(foreignAgent);
+    assert.equal(record.claimedBy.toBase58(), claimantProfile.toBase58());
+    let claimant = await program.account.agentProfile.fetch(claimantProfile);
+    assert.equal(claimant.claimedForeignStakedFor.toString(), stake.toString());
+    assert.isTrue(claimant.reputationScore.gt(unclaimed.reputationScore));
+
+    const revokeAccounts = {
+      vouch: vouchPda,
+      voucherProfile,
+      foreignAgent,
+      config: configPda,
+      voucher: agent1.publicKey,
+    };
+    try {
+      await program.methods
+        .revokeForeignVouch()
+        .accountsPartial({ ...revokeAccounts, claimantProfile: null })
+        .signers([agent1])
+        .rpc();
+      assert.fail("Expected a claimed record's revoke to require the claimant profile");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "MissingClaimantProfile");
+    }
+    await program.methods
+      .revokeForeignVouch()
+      .accountsPartial({ ...revokeAccounts, claimantProfile })
+      .signers([agent1])
+      .rpc();
+
+    record = await program.account.foreignAgentRecord.fetch(foreignAgent);
+    assert.equal(record.totalVouchesReceived, 0);
+    assert.equal(record.totalStakedFor.toString(), "0");
+    claimant = await program.account.agentProfile.fetch(claimantProfile);
+    assert.equal(claimant.claimedForeignStakedFor.toString(), "0");
+    const vouch = await program.account.vouch.fetch(vouchPda);
+    assert.equal(vouch.status.revoked !== undefined, true);
+  });
+
+  it("Publishes chain-tagged reputation messages and registers Wormhole emitters", async () => {
+    const baseChain = 30;
+    const emitterAddress = Array.from(Buffer.alloc(32, 7));
+    const chainSeed = Buffer.alloc(2);
+    chainSeed.writeUInt16LE(baseChain);
+    const [wormholeEmitter] = PublicKey.findProgramAddressSync(
+      [Buffer.from("wormhole_emitter"), chainSeed],
+      program.programId
+    );
+    await program.methods
+      .registerWormholeEmitter(baseChain, emitterAddress, "eip155:8453")
+      .accountsPartial({
+        wormholeEmitter,
+        config: configPda,
+        authority: provider.wallet.publicKey,
+      })
+      .rpc();
+    const emitter = await program.account.wormholeEmitter.fetch(wormholeEmitter);
+    assert.equal(emitter.chain, baseChain);
+    assert.deepEqual(Array.from(emitter.address), emitterAddress);
+    assert.equal(emitter.chainContext, "eip155:8453");
+
+    // The local validator has no core bridge; publishing must refuse any other program.
+    const agentProfile = getAgentPda(agent1.publicKey);
+    const [reputationMessage] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation_message"), Buffer.alloc(8)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .publishReputationMessage()
+        .accountsPartial({
+          agentProfile,
+          config: configPda,
+          coreBridgeConfig: Keypair.generate().publicKey,
+          coreMessage: reputationMessage,
+          coreEmitterSequence: Keypair.generate().publicKey,
+          coreFeeCollector: Keypair.generate().publicKey,
+          coreBridgeProgram: anchor.web3.SystemProgram.programId,
+          payer: provider.wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("Expected publishing outside the Wormhole core bridge to fail");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "NotCoreBridgeProgram");
+    }
+
+    try {
+      await program.methods
+        .updateChainContext("eip155:8453")
+        .accountsPartial({ config: configPda, authority: provider.wallet.publicKey })
+        .rpc();
+      assert.fail("Expected a non-Solana chain context to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "InvalidChainContext");
+    }
+  });
+
+  it("Emits registration and reputation-change events with chain context", async () => {
+    const eventParser = new anchor.EventParser(program.programId, program.coder);
+    const eventsFor = async (signature: string) => {
+      await provider.connection.confirmTransaction(signature, "confirmed");
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      return Array.from(eventParser.parseLogs(tx?.meta?.logMessages ?? []));
+    };
+    const config = await program.account.reputationConfig.fetch(configPda);
+    const agent3Pda = getAgentPda(agent3.publicKey);
+
+    const registerSig = await program.methods
+      .registerAgent("https://example.com/agent3-v2.json")
+      .accountsPartial({ agentProfile: agent3Pda, authority: agent3.publicKey })
+      .signers([agent3])
+      .rpc();
+    const registered = (await eventsFor(registerSig)).find(
+      (event) => event.name === "agentRegistered"
+    );
+    assert.isDefined(registered);
+    assert.equal(registered!.data.eventVersion, 1);
+    assert.equal(registered!.data.chainContextId, config.chainContextId);
+    assert.equal(registered!.data.isNew, false);
+    assert.equal(registered!.data.metadataUri, "https://example.com/agent3-v2.json");
+
+    const before = await program.account.agentProfile.fetch(agent3Pda);
+    const depositSig = await program.methods
+      .depositAuthorBond(new anchor.BN(0.01 * anchor.web3.LAMPORTS_PER_SOL))
+      .accountsPartial({
+        authorBond: getAuthorBondPda(agent3.publicKey),
+        authorProfile: agent3Pda,
+        config: configPda,
+        author: agent3.publicKey,
+      })
+      .signers([agent3])
+      .rpc();
+    const after = await program.account.agentProfile.fetch(agent3Pda);
+    const scoreChanged = (await eventsFor(depositSig)).find(
+      (event) => event.name === "reputationScoreChanged"
+    );
+    assert.isDefined(scoreChanged);
+    assert.equal(scoreChanged!.data.agent.toBase58(), agent3Pda.toBase58());
+    assert.equal(
+      scoreChanged!.data.previousScore.toString(),
+      before.reputationScore.toString()
+    );
+    assert.equal(
+      scoreChanged!.data.reputationScore.toString(),
+      after.reputationScore.toString()
+    );
+  });
+
+  it("Lets the config authority reconcile agent counters from live vouches", async () => {
+    const agent1Pda = getAgentPda(agent1.publicKey);
+    const agent2Pda = getAgentPda(agent2.publicKey);
+    const [vouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), agent1Pda.toBuffer(), agent2Pda.toBuffer()],
+      program.programId
+    );
+    const counterAccounts = [
+      { pubkey: vouchPda, isWritable: false, isSigner: false },
+    ];
+
+    try {
+      await program.methods
+        .reconcileAgentProfile()
+        .accountsPartial({
+          agentProfile: agent2Pda,
+          authorBond: getAuthorBondPda(agent2.publicKey),
+          config: configPda,
+          authority: agent2.publicKey,
+        })
+        .remainingAccounts(counterAccounts)
+        .signers([agent2])
+        .rpc();
+      assert.fail("Expected a non-authority reconcile to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "UnauthorizedReconcileAuthority");
+    }
+
+    try {
+      await program.methods
+        .reconcileAgentProfile()
+        .accountsPartial({
+          agentProfile: agent2Pda,
+          authorBond: getAuthorBondPda(agent2.publicKey),
+          config: configPda,
+          authority: provider.wallet.publicKey,
+        })
+        .remainingAccounts([
+          ...counterAccounts,
+          { pubkey: agent1Pda, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+      assert.fail("Expected a non-counter account to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "UnsupportedCounterAccount");
+    }
+
+    const before = await program.account.agentProfile.fetch(agent2Pda);
+    await program.methods
+      .reconcileAgentProfile()
+      .accountsPartial({
+        agentProfile: agent2Pda,
+        authorBond: getAuthorBondPda(agent2.publicKey),
+        config: configPda,
+        authority: provider.wallet.publicKey,
+      })
+      .remainingAccounts(counterAccounts)
+      .rpc();
+
+    const after = await program.account.agentProfile.fetch(agent2Pda);
+    const vouch = await program.account.vouch.fetch(vouchPda);
+    assert.equal(after.totalVouchesReceived, 1);
+    assert.equal(after.totalVouchesGiven, before.totalVouchesGiven);
+    assert.equal(after.totalStakedFor.toString(), vouch.stakeAmount.toString());
+    assert.equal(
+      after.effectiveVouchWeight.toString(),
+      vouch.effectiveWeightBps.toString()
+    );
+    assert.equal(after.authorBondLamports.toNumber(), 0);
+    assert.equal(after.activeSkillListings, 0);
+  });
+
+  it("Lets anyone sync an agent's counters once every live vouch is supplied", async () => {
+    const agent1Pda = getAgentPda(agent1.publicKey);
+    const agent2Pda = getAgentPda(agent2.publicKey);
+    const [vouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), agent1Pda.toBuffer(), agent2Pda.toBuffer()],
+      program.programId
+    );
+    const accounts = {
+      agentProfile: agent2Pda,
+      authorBond: getAuthorBondPda(agent2.publicKey),
+      config: configPda,
+      caller: agent1.publicKey,
+    };
+
+    try {
+      await program.methods
+        .syncAgentProfile()
+        .accountsPartial(accounts)
+        .signers([agent1])
+        .rpc();
+      assert.fail("Expected a sync without the live vouch to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "IncompleteAgentAccounts");
+    }
+
+    const before = await program.account.agentProfile.fetch(agent2Pda);
+    await program.methods
+      .syncAgentProfile()
+      .accountsPartial(accounts)
+      .remainingAccounts([{ pubkey: vouchPda, isWritable: false, isSigner: false }])
+      .signers([agent1])
+      .rpc();
+
+    const after = await program.account.agentProfile.fetch(agent2Pda);
+    assert.equal(after.totalVouchesReceived, before.totalVouchesReceived);
+    assert.equal(after.totalStakedFor.toString(), before.totalStakedFor.toString());
+    assert.equal(after.reputationScore.toString(), before.reputationScore.toString());
+  });
+
+  it("Skips current accounts in a migrate_accounts batch and gates it on the config authority", async () => {
+    const agent1Pda = getAgentPda(agent1.publicKey);
+    const agent2Pda = getAgentPda(agent2.publicKey);
+    const [vouchPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vouch"), agent1Pda.toBuffer(), agent2Pda.toBuffer()],
+      program.programId
+    );
+    const batch = [agent2Pda, vouchPda].map((pubkey) => ({
+      pubkey,
+      isWritable: true,
+      isSigner: false,
+    }));
+
+    try {
+      await program.methods
+        .migrateAccounts()
+        .accountsPartial({ config: configPda, authority: agent2.publicKey })
+        .remainingAccounts(batch)
+        .signers([agent2])
+        .rpc();
+      assert.fail("Expected a non-authority migration to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "UnauthorizedMigrationAuthority");
+    }
+
+    try {
+      await program.methods
+        .migrateAccounts()
+        .accountsPartial({ config: configPda, authority: provider.wallet.publicKey })
+        .remainingAccounts([{ pubkey: agent2.publicKey, isWritable: true, isSigner: false }])
+        .rpc();
+      assert.fail("Expected a wallet account to be rejected");
+    } catch (error: any) {
+      assert.include(String(error?.message ?? error), "MigrationAccountNotOwnedByProgram");
+    }
+
+    const profileBefore = await provider.connection.getAccountInfo(agent2Pda);
+    const vouchBefore = await provider.connection.getAccountInfo(vouchPda);
+    await program.methods
+      .migrateAccounts()
+      .accountsPartial({ config: configPda, authority: provider.wallet.publicKey })
+      .remainingAccounts(batch)
+      .rpc();
+
+    const profileAfter = await provider.connection.getAccountInfo(agent2Pda);
+    const vouchAfter = await provider.connection.getAccountInfo(vouchPda);
+    assert.isTrue(profileAfter!.data.equals(profileBefore!.data));
+    assert.isTrue(vouchAfter!.data.equals(vouchBefore!.data));
+    assert.equal((await program.account.vouch.fetch(vouchPda)).version, 1);
+    assert.equal((await program.account.agentProfile.fetch(agent2Pda)).version, 1);
+  });
 });